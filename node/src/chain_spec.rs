@@ -1,8 +1,21 @@
-use ibp_node_runtime::{AccountId, RuntimeGenesisConfig, Signature, WASM_BINARY};
+use ibp_node_runtime::{
+	pallet_ibp_alert::{
+		AlertDescription, AlertSeverity, AlertType, AlertTypeInfo, AlertTypeStatus,
+		EscalationPolicy,
+	},
+	pallet_ibp_member::{MemberName, MemberStatus, MembershipLevel},
+	pallet_ibp_service::{
+		ChainData, ChainId, ChainRelation, ChainStatus, DomainId, DomainOwner, Hostname, ServiceId,
+		ServiceStatus, ServiceType, TokenSymbol,
+	},
+	opaque::SessionKeys,
+	AccountId, BlockNumber, RuntimeGenesisConfig, Signature, HOURS, MINUTES, WASM_BINARY,
+};
+use serde::Deserialize;
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{sr25519, Pair, Public, H256};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
 // The URL for the telemetry server.
@@ -11,6 +24,151 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<RuntimeGenesisConfig>;
 
+/// Chain id prefix selecting the local testnet seeded with a service catalog,
+/// e.g. `build-spec --chain catalog:./services.json`
+pub const CATALOG_PREFIX: &str = "catalog:";
+
+/// A chain entry in a catalog file. A chain with a `relayChain` and `paraId` is a parachain,
+/// otherwise a relay chain unless `solo` is set.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChainEntry {
+	pub id: String,
+	pub genesis_hash: H256,
+	#[serde(default)]
+	pub relay_chain: Option<String>,
+	#[serde(default)]
+	pub para_id: Option<u32>,
+	#[serde(default)]
+	pub solo: bool,
+	pub ss58_prefix: u16,
+	pub token_symbol: String,
+}
+
+impl ChainEntry {
+	fn relation(&self) -> Result<ChainRelation, String> {
+		match (&self.relay_chain, self.para_id, self.solo) {
+			(Some(relay_chain), Some(para_id), false) =>
+				Ok(ChainRelation::Parachain { relay_chain: bounded_id(relay_chain)?, para_id }),
+			(None, None, false) => Ok(ChainRelation::Relay),
+			(None, None, true) => Ok(ChainRelation::Solo),
+			_ => Err(format!("Chain `{}` needs both relayChain and paraId, and is not solo", self.id)),
+		}
+	}
+}
+
+/// A service entry in a catalog file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceEntry {
+	pub id: String,
+	pub chain_id: String,
+	pub service_type: ServiceType,
+	pub level: MembershipLevel,
+	#[serde(default)]
+	pub status: ServiceStatus,
+}
+
+/// A domain entry in a catalog file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DomainEntry {
+	pub id: String,
+	/// The owning member, the programme when unset
+	#[serde(default)]
+	pub owner: Option<AccountId>,
+	/// (service id, hostname) pairs served under the domain
+	#[serde(default)]
+	pub services: Vec<(String, String)>,
+}
+
+/// The IBP service catalog used to seed `pallet-ibp-service` at genesis.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceCatalog {
+	/// Chains of the services, relay chains before their parachains
+	#[serde(default)]
+	pub chains: Vec<ChainEntry>,
+	pub services: Vec<ServiceEntry>,
+	/// (member, service id) pairs exempt from a service
+	#[serde(default)]
+	pub overrides: Vec<(AccountId, String)>,
+	#[serde(default)]
+	pub domains: Vec<DomainEntry>,
+}
+
+impl ServiceCatalog {
+	/// Load a catalog from a JSON file.
+	pub fn from_json_file(path: &str) -> Result<Self, String> {
+		let file = std::fs::File::open(path)
+			.map_err(|e| format!("Error opening service catalog `{}`: {}", path, e))?;
+		serde_json::from_reader(std::io::BufReader::new(file))
+			.map_err(|e| format!("Error parsing service catalog `{}`: {}", path, e))
+	}
+
+	/// Convert the catalog into the `ibpService` genesis config patch.
+	fn into_genesis(self) -> Result<serde_json::Value, String> {
+		let chains = self
+			.chains
+			.iter()
+			.map(|c| {
+				let chain = ChainData {
+					genesis_hash: c.genesis_hash.to_fixed_bytes(),
+					relation: c.relation()?,
+					ss58_prefix: c.ss58_prefix,
+					token_symbol: bounded_id::<TokenSymbol>(&c.token_symbol)?,
+					status: ChainStatus::Active,
+				};
+				Ok((bounded_id::<ChainId>(&c.id)?, chain))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+		let services = self
+			.services
+			.into_iter()
+			.map(|s| {
+				Ok((
+					bounded_id::<ServiceId>(&s.id)?,
+					bounded_id::<ChainId>(&s.chain_id)?,
+					s.service_type,
+					s.level,
+					s.status,
+				))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+		let service_overrides = self
+			.overrides
+			.into_iter()
+			.map(|(member, service_id)| Ok((member, bounded_id::<ServiceId>(&service_id)?)))
+			.collect::<Result<Vec<_>, String>>()?;
+		let domains = self
+			.domains
+			.into_iter()
+			.map(|d| {
+				let owner = d.owner.map_or(DomainOwner::Programme, DomainOwner::Member);
+				let services = d
+					.services
+					.iter()
+					.map(|(service_id, hostname)| {
+						Ok((bounded_id::<ServiceId>(service_id)?, bounded_id::<Hostname>(hostname)?))
+					})
+					.collect::<Result<Vec<_>, String>>()?;
+				Ok((bounded_id::<DomainId>(&d.id)?, owner, services))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+		Ok(serde_json::json!({
+			"chains": chains,
+			"services": services,
+			"serviceOverrides": service_overrides,
+			"domains": domains,
+		}))
+	}
+}
+
+/// Convert a catalog string into a bounded id, rejecting ids that are too long.
+fn bounded_id<B: TryFrom<Vec<u8>>>(id: &str) -> Result<B, String> {
+	B::try_from(id.as_bytes().to_vec()).map_err(|_| format!("Id `{}` is too long", id))
+}
+
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -28,9 +186,54 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate a validator account with its Aura and Grandpa session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+/// Generate an active IBP member from seed, named after the seed.
+pub fn ibp_member_from_seed(
+	seed: &str,
+	level: MembershipLevel,
+) -> (AccountId, MemberName, MemberStatus, MembershipLevel) {
+	let name = MemberName::truncate_from(seed.as_bytes().to_vec());
+	(get_account_id_from_seed::<sr25519::Public>(seed), name, MemberStatus::Active, level)
+}
+
+/// The alert types the IBP monitoring raises, registered at genesis.
+pub fn ibp_alert_types() -> Vec<(AlertType, AlertTypeInfo<BlockNumber>)> {
+	[
+		("HostDown", AlertSeverity::Critical, "The host does not respond"),
+		("ServiceDown", AlertSeverity::Critical, "The service does not respond"),
+		("InstanceDown", AlertSeverity::Critical, "An instance behind the service is down"),
+		("BlackboxProbeFailed", AlertSeverity::Warning, "The blackbox probe failed"),
+		("BlackboxProbeHttpFailure", AlertSeverity::Warning, "The probe got an HTTP error"),
+		("NoIncomingConnection", AlertSeverity::Warning, "The node has no incoming peers"),
+		("BlockFinalizationLaggingBehind", AlertSeverity::Warning, "Finalization lags behind"),
+		("BlackboxSslCertificateWillExpireSoon", AlertSeverity::Info, "TLS certificate expires soon"),
+		("SubstrateServiceTooManyRestarts", AlertSeverity::Warning, "The node restarted too often"),
+		("Other", AlertSeverity::Info, "Any other alert"),
+	]
+	.into_iter()
+	.map(|(code, severity, description)| {
+		let alert_type = AlertTypeInfo {
+			severity,
+			description: AlertDescription::truncate_from(description.as_bytes().to_vec()),
+			expires_after: None,
+			escalation: EscalationPolicy::None,
+			status: AlertTypeStatus::Active,
+		};
+		(AlertType::truncate_from(code.as_bytes().to_vec()), alert_type)
+	})
+	.collect()
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -53,18 +256,45 @@ pub fn development_config() -> Result<ChainSpec, String> {
 			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 		],
+		// IBP members
+		vec![
+			ibp_member_from_seed("Alice", MembershipLevel::Six),
+			ibp_member_from_seed("Bob", MembershipLevel::One),
+		],
+		// IBP curators
+		vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+		// IBP monitors
+		vec![get_account_id_from_seed::<sr25519::Public>("Bob")],
+		// IBP service catalog
+		ServiceCatalog::default(),
+		// Session length, short to exercise session housekeeping
+		10 * MINUTES,
 		true,
-	))
+	)?)
 	.build())
 }
 
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	local_testnet_config_with_catalog("Local Testnet", "local_testnet", ServiceCatalog::default())
+}
+
+/// Local testnet with the IBP service catalog read from a JSON file.
+pub fn catalog_testnet_config(path: &str) -> Result<ChainSpec, String> {
+	let catalog = ServiceCatalog::from_json_file(path)?;
+	local_testnet_config_with_catalog("IBP Local Testnet", "ibp_local_testnet", catalog)
+}
+
+fn local_testnet_config_with_catalog(
+	name: &str,
+	id: &str,
+	catalog: ServiceCatalog,
+) -> Result<ChainSpec, String> {
 	Ok(ChainSpec::builder(
 		WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?,
 		None,
 	)
-	.with_name("Local Testnet")
-	.with_id("local_testnet")
+	.with_name(name)
+	.with_id(id)
 	.with_chain_type(ChainType::Local)
 	.with_genesis_config_patch(testnet_genesis(
 		// Initial PoA authorities
@@ -86,47 +316,73 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 		],
+		// IBP members
+		vec![
+			ibp_member_from_seed("Alice", MembershipLevel::Six),
+			ibp_member_from_seed("Bob", MembershipLevel::Five),
+			ibp_member_from_seed("Charlie", MembershipLevel::Three),
+			ibp_member_from_seed("Dave", MembershipLevel::One),
+			ibp_member_from_seed("Eve", MembershipLevel::None),
+		],
+		// IBP curators
+		vec![
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			get_account_id_from_seed::<sr25519::Public>("Bob"),
+		],
+		// IBP monitors
+		vec![get_account_id_from_seed::<sr25519::Public>("Eve")],
+		// IBP service catalog
+		catalog,
+		// Session length
+		HOURS,
 		true,
-	))
+	)?)
 	.build())
 }
 
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	ibp_members: Vec<(AccountId, MemberName, MemberStatus, MembershipLevel)>,
+	ibp_curators: Vec<AccountId>,
+	ibp_monitors: Vec<AccountId>,
+	ibp_service_catalog: ServiceCatalog,
+	session_length: BlockNumber,
 	_enable_println: bool,
-) -> serde_json::Value {
-	serde_json::json!({
+) -> Result<serde_json::Value, String> {
+	Ok(serde_json::json!({
 		"balances": {
 			// Configure endowed accounts with initial balance of 1 << 60.
 			"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
 		},
+		// Aura and Grandpa authorities are set from the session keys
 		"aura": {
-			"authorities": initial_authorities.iter().map(|x| (x.0.clone())).collect::<Vec<_>>(),
+			"authorities": Vec::<AuraId>::new(),
 		},
 		"grandpa": {
-			"authorities": initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect::<Vec<_>>(),
+			"authorities": Vec::<(GrandpaId, u64)>::new(),
+		},
+		"session": {
+			"keys": initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect::<Vec<_>>(),
 		},
 		"sudo": {
 			// Assign network admin rights.
 			"key": Some(root_key),
 		},
-    // "ibp_member": {
-    //   "members": vec![
-    //       (
-    //         // Alice's AccountId
-    //         get_account_id_from_seed::<sr25519::Public>("Alice"),
-    //         1, // MemberStatus::Active
-    //         0, // MembershipLevel::Zero
-    //       ),
-    //     ],
-    //     "curators": vec![
-    //       // Alice's AccountId as a curator
-    //       get_account_id_from_seed::<sr25519::Public>("Alice"),
-    //     ],
-    //     "curator_count": 1,
-    // },
-  })
+		"ibpMember": {
+			"members": ibp_members,
+			"curators": ibp_curators,
+			"monitors": ibp_monitors,
+			"sessionLength": session_length,
+		},
+		"ibpService": ibp_service_catalog.into_genesis()?,
+		"ibpAlert": {
+			"alertTypes": ibp_alert_types(),
+		},
+	}))
 }
@@ -39,6 +39,10 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			id if id.starts_with(chain_spec::CATALOG_PREFIX) => {
+				let path = &id[chain_spec::CATALOG_PREFIX.len()..];
+				Box::new(chain_spec::catalog_testnet_config(path)?)
+			},
 			path => {
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?)
 			},
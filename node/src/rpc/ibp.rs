@@ -0,0 +1,948 @@
+//! The `ibp_*` RPC namespace.
+//!
+//! These methods call into the `IbpApi` runtime API at a chosen block and return JSON with
+//! SS58 account ids and UTF-8 ids instead of hex-encoded `BoundedVec`s.
+//!
+//! The `ibp_subscribe*` methods decode IBP events from finalized blocks and push only the
+//! changes the client asked about. Events are decoded with the runtime the node was built with,
+//! a subscription is closed with an error once a block runs a different runtime version.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use futures::StreamExt;
+use ibp_node_runtime::{
+	opaque::Block,
+	pallet_ibp_alert::{self, AlertSeverity, AlertTypeStatus, EscalationPolicy},
+	pallet_ibp_check::HealthCheckStatus,
+	pallet_ibp_member::{self, AuditAction, AuditEntry, MemberStatus, MembershipLevel},
+	pallet_ibp_service::{self, ServiceStatus, ServiceType},
+	AccountId, Balance, BlockNumber, Hash, RuntimeEvent, VERSION,
+};
+use jsonrpsee::{
+	core::{async_trait, RpcResult, SubscriptionResult},
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+	PendingSubscriptionSink, SubscriptionMessage,
+};
+use sc_client_api::{Backend, BlockchainEvents, StateBackend};
+use pallet_ibp_runtime_api::{
+	AlertFilter, AlertInfo, AlertInfoBeforeV5, AlertStats, AlertType, AlertTypeInfo, CheckInfo,
+	IbpApi as IbpRuntimeApi, IbpRewardApi, MemberInfo, PayoutPreview, PendingAlertInfo, RollupInfo,
+	ServiceInfo,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{PerThing, Perbill};
+
+/// A member with a readable name.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+	/// Member account
+	pub id: Option<AccountId>,
+	/// Member name
+	pub name: String,
+	/// Member status
+	pub status: MemberStatus,
+	/// Membership level
+	pub level: MembershipLevel,
+}
+
+/// A service with readable ids.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+	/// Service id, e.g. `polkadot-rpc`
+	pub id: String,
+	/// Chain id, e.g. `polkadot`
+	pub chain_id: String,
+	/// Service type
+	pub service_type: ServiceType,
+	/// Membership level where the service applies
+	pub level: MembershipLevel,
+	/// Service status
+	pub status: ServiceStatus,
+}
+
+/// An alert with readable ids.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+	/// Monitor that raised the alert
+	pub monitor_id: AccountId,
+	/// Alert id, unique per monitor
+	pub alert_id: u64,
+	/// Member the alert applies to
+	pub member_id: AccountId,
+	/// Domain the alert applies to
+	pub domain_id: String,
+	/// Service the alert applies to
+	pub service_id: String,
+	/// Alert type
+	pub alert_type: String,
+	/// Alert severity
+	pub severity: AlertSeverity,
+	/// Unix time in milliseconds the alert was raised at
+	pub raised_at: u64,
+	/// Member that acknowledged the alert
+	pub acknowledged_by: Option<AccountId>,
+	/// Unix time in milliseconds the alert was acknowledged at
+	pub acknowledged_at: Option<u64>,
+}
+
+/// A registered alert type, returned by `ibp_alertTypes`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertKind {
+	/// Code monitors raise the alert with
+	pub code: String,
+	/// Severity of every alert of the type
+	pub severity: AlertSeverity,
+	/// What the alert means
+	pub description: String,
+	/// Blocks after which alerts of the type expire, the pallet default if omitted
+	pub expires_after: Option<BlockNumber>,
+	/// What happens when alerts of the type are not dealt with
+	pub escalation: EscalationPolicy<BlockNumber>,
+	/// Whether new alerts of the type are accepted
+	pub status: AlertTypeStatus,
+}
+
+/// Response times of a member, returned by `ibp_alertStats`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTimes {
+	/// Recently resolved alerts
+	pub resolved: u32,
+	/// Resolved alerts the member acknowledged
+	pub acknowledged: u32,
+	/// Mean time to acknowledge in milliseconds
+	pub mtta_ms: Option<u64>,
+	/// Mean time to resolve in milliseconds
+	pub mttr_ms: Option<u64>,
+}
+
+/// An alert waiting for more monitors to report it, returned by `ibp_pendingAlerts`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAlert {
+	/// Member the alert applies to
+	pub member_id: AccountId,
+	/// Service the alert applies to
+	pub service_id: String,
+	/// Alert type
+	pub alert_type: String,
+	/// Block the alert expires at unless enough monitors report it
+	pub expires_at: BlockNumber,
+	/// Monitors that reported the alert
+	pub monitors: Vec<AccountId>,
+	/// Number of monitors that must report the alert
+	pub required: u32,
+}
+
+/// Filter for `ibp_activeAlerts`, omitted fields match any alert.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertQuery {
+	/// Only alerts for this member
+	pub member_id: Option<AccountId>,
+	/// Only alerts raised by this monitor
+	pub monitor_id: Option<AccountId>,
+	/// Only alerts for this service
+	pub service_id: Option<String>,
+	/// Only alerts for this domain
+	pub domain_id: Option<String>,
+	/// Only alerts of this type
+	pub alert_type: Option<String>,
+}
+
+/// A health check with readable ids.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Check {
+	/// Monitor that submitted the check
+	pub monitor_id: AccountId,
+	/// Block at which the check was registered
+	pub block_number: BlockNumber,
+	/// Domain that was checked
+	pub domain_id: String,
+	/// Check status
+	pub status: HealthCheckStatus,
+}
+
+/// Uptime of a member on a service over one epoch, returned by `ibp_uptimeRollups`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochUptime {
+	/// The epoch, the block number divided by the epoch length
+	pub epoch: u32,
+	/// Checks that found the service up
+	pub up: u32,
+	/// Checks that found the service down
+	pub down: u32,
+	/// All checks
+	pub total: u32,
+	/// Percentage of checks that found the service up
+	pub uptime_percent: f64,
+	/// Median latency in milliseconds, if the monitors measured it
+	pub latency_p50: Option<u32>,
+	/// 90th percentile latency in milliseconds
+	pub latency_p90: Option<u32>,
+	/// 99th percentile latency in milliseconds
+	pub latency_p99: Option<u32>,
+}
+
+/// Health of one member providing a service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberHealth {
+	/// The member
+	pub member: Member,
+	/// The most recent health check, if any
+	pub last_check: Option<Check>,
+	/// Alerts currently raised for this member and service
+	pub alerts: Vec<Alert>,
+}
+
+/// Health of a service across the members that must provide it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceHealth {
+	/// The service
+	pub service: Service,
+	/// One entry per member that must provide the service
+	pub members: Vec<MemberHealth>,
+}
+
+/// Compliance of a member for one required service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceCompliance {
+	/// The service
+	pub service: Service,
+	/// The most recent health check, if any
+	pub last_check: Option<Check>,
+	/// Alerts currently raised for this member and service
+	pub alerts: Vec<Alert>,
+	/// The last check is `Active` and there are no alerts
+	pub compliant: bool,
+}
+
+/// Compliance of a member across all required services.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberCompliance {
+	/// The member
+	pub member: Member,
+	/// One entry per service the member must provide
+	pub services: Vec<ServiceCompliance>,
+	/// All required services are compliant
+	pub compliant: bool,
+}
+
+/// A change recorded in a member's audit log.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum AuditChange {
+	/// The member was registered
+	Registered { name: String },
+	/// The member was renamed
+	Renamed { old: String, new: String },
+	/// The member status changed
+	StatusChanged { old: MemberStatus, new: MemberStatus },
+	/// The membership level changed
+	LevelChanged { old: MembershipLevel, new: MembershipLevel },
+	/// The member was made a monitor
+	MonitorAssigned,
+	/// The member is no longer a monitor
+	MonitorRemoved,
+	/// The member was made a curator
+	CuratorAssigned,
+	/// The member is no longer a curator
+	CuratorRemoved,
+}
+
+/// An entry of a member's audit log, returned by `ibp_memberAuditLog`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+	/// Block the change was made in
+	pub block_number: BlockNumber,
+	/// Account that made the change, `None` for root
+	pub actor: Option<AccountId>,
+	/// The change
+	pub change: AuditChange,
+	/// Why the change was made, given for status changes
+	pub reason: Option<String>,
+}
+
+/// The payout a member can expect, returned by `ibp_nextPayout`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payout {
+	/// The era in progress
+	pub era: u32,
+	/// The payout for the era in progress if it ended now
+	pub payout: Balance,
+	/// Payouts of past eras not claimed yet
+	pub claimable: Balance,
+}
+
+/// A member status change, pushed by `ibp_subscribeMemberStatus`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberStatusNotification {
+	/// Finalized block that contains the change
+	pub block_hash: Hash,
+	/// The member
+	pub member_id: AccountId,
+	/// Status before the change, `None` for a new member
+	pub previous_status: Option<MemberStatus>,
+	/// Status after the change
+	pub status: MemberStatus,
+	/// Why the status changed, `None` for a new member
+	pub reason: Option<String>,
+}
+
+/// What happened to an alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertChange {
+	/// The alert was raised by a monitor
+	Registered,
+	/// The alert was cleared by its monitor or a curator
+	Cleared,
+}
+
+/// An alert change, pushed by `ibp_subscribeAlerts`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertNotification {
+	/// Finalized block that contains the change
+	pub block_hash: Hash,
+	/// What happened to the alert
+	pub change: AlertChange,
+	/// Member the alert applies to
+	pub member_id: AccountId,
+	/// Service the alert applies to
+	pub service_id: String,
+	/// Domain the alert applies to
+	pub domain_id: String,
+	/// Alert type
+	pub alert_type: String,
+}
+
+impl AlertQuery {
+	fn matches(&self, alert: &AlertNotification) -> bool {
+		self.member_id.as_ref().map_or(true, |id| *id == alert.member_id) &&
+			self.service_id.as_ref().map_or(true, |id| *id == alert.service_id) &&
+			self.domain_id.as_ref().map_or(true, |id| *id == alert.domain_id) &&
+			self.alert_type.as_ref().map_or(true, |t| *t == alert.alert_type)
+	}
+}
+
+/// A service status change, pushed by `ibp_subscribeServiceStatus`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatusNotification {
+	/// Finalized block that contains the change
+	pub block_hash: Hash,
+	/// The service
+	pub service_id: String,
+	/// Status after the change
+	pub status: ServiceStatus,
+	/// Why the status changed
+	pub reason: String,
+}
+
+fn readable(bytes: &[u8]) -> String {
+	String::from_utf8_lossy(bytes).into_owned()
+}
+
+impl From<MemberInfo<AccountId>> for Member {
+	fn from(info: MemberInfo<AccountId>) -> Self {
+		Self { id: info.id, name: readable(&info.name), status: info.status, level: info.level }
+	}
+}
+
+impl From<AuditEntry<AccountId, BlockNumber>> for AuditRecord {
+	fn from(entry: AuditEntry<AccountId, BlockNumber>) -> Self {
+		let change = match entry.action {
+			AuditAction::Registered { name } => AuditChange::Registered { name: readable(&name) },
+			AuditAction::Renamed { old, new } =>
+				AuditChange::Renamed { old: readable(&old), new: readable(&new) },
+			AuditAction::StatusChanged { old, new } => AuditChange::StatusChanged { old, new },
+			AuditAction::LevelChanged { old, new } => AuditChange::LevelChanged { old, new },
+			AuditAction::MonitorAssigned => AuditChange::MonitorAssigned,
+			AuditAction::MonitorRemoved => AuditChange::MonitorRemoved,
+			AuditAction::CuratorAssigned => AuditChange::CuratorAssigned,
+			AuditAction::CuratorRemoved => AuditChange::CuratorRemoved,
+		};
+		Self {
+			block_number: entry.block_number,
+			actor: entry.actor,
+			change,
+			reason: entry.reason.map(|reason| readable(&reason)),
+		}
+	}
+}
+
+impl From<PayoutPreview<Balance>> for Payout {
+	fn from(preview: PayoutPreview<Balance>) -> Self {
+		Self { era: preview.era, payout: preview.payout, claimable: preview.claimable }
+	}
+}
+
+impl From<RollupInfo> for EpochUptime {
+	fn from(info: RollupInfo) -> Self {
+		Self {
+			epoch: info.epoch,
+			up: info.up,
+			down: info.down,
+			total: info.total,
+			uptime_percent: info.uptime.deconstruct() as f64 * 100.0 / Perbill::ACCURACY as f64,
+			latency_p50: info.latency_p50,
+			latency_p90: info.latency_p90,
+			latency_p99: info.latency_p99,
+		}
+	}
+}
+
+impl From<ServiceInfo> for Service {
+	fn from(info: ServiceInfo) -> Self {
+		Self {
+			id: readable(&info.id),
+			chain_id: readable(&info.chain_id),
+			service_type: info.service_type,
+			level: info.level,
+			status: info.status,
+		}
+	}
+}
+
+impl From<AlertInfo<AccountId>> for Alert {
+	fn from(info: AlertInfo<AccountId>) -> Self {
+		Self {
+			monitor_id: info.monitor_id,
+			alert_id: info.alert_id,
+			member_id: info.member_id,
+			domain_id: readable(&info.domain_id),
+			service_id: readable(&info.service_id),
+			alert_type: readable(&info.alert_type),
+			severity: info.severity,
+			raised_at: info.raised_at_time,
+			acknowledged_by: info.acknowledged_by,
+			acknowledged_at: info.acknowledged_at_time,
+		}
+	}
+}
+
+/// Runtimes before `IbpApi` version 5 did not record severity, raising time or acknowledgement.
+impl From<AlertInfoBeforeV5<AccountId>> for Alert {
+	fn from(info: AlertInfoBeforeV5<AccountId>) -> Self {
+		Self {
+			monitor_id: info.monitor_id,
+			alert_id: info.alert_id,
+			member_id: info.member_id,
+			domain_id: readable(&info.domain_id),
+			service_id: readable(&info.service_id),
+			alert_type: readable(&info.alert_type),
+			severity: AlertSeverity::default(),
+			raised_at: 0,
+			acknowledged_by: None,
+			acknowledged_at: None,
+		}
+	}
+}
+
+impl From<(AlertType, AlertTypeInfo<BlockNumber>)> for AlertKind {
+	fn from((code, info): (AlertType, AlertTypeInfo<BlockNumber>)) -> Self {
+		Self {
+			code: readable(&code),
+			severity: info.severity,
+			description: readable(&info.description),
+			expires_after: info.expires_after,
+			escalation: info.escalation,
+			status: info.status,
+		}
+	}
+}
+
+impl From<AlertStats> for ResponseTimes {
+	fn from(stats: AlertStats) -> Self {
+		Self {
+			resolved: stats.resolved,
+			acknowledged: stats.acknowledged,
+			mtta_ms: stats.mean_time_to_acknowledge,
+			mttr_ms: stats.mean_time_to_resolve,
+		}
+	}
+}
+
+impl From<PendingAlertInfo<AccountId, BlockNumber>> for PendingAlert {
+	fn from(info: PendingAlertInfo<AccountId, BlockNumber>) -> Self {
+		Self {
+			member_id: info.member_id,
+			service_id: readable(&info.service_id),
+			alert_type: readable(&info.alert_type),
+			expires_at: info.expires_at,
+			monitors: info.monitors,
+			required: info.required,
+		}
+	}
+}
+
+impl From<CheckInfo<AccountId, BlockNumber>> for Check {
+	fn from(info: CheckInfo<AccountId, BlockNumber>) -> Self {
+		Self {
+			monitor_id: info.monitor_id,
+			block_number: info.block_number,
+			domain_id: readable(&info.domain_id),
+			status: info.status,
+		}
+	}
+}
+
+/// IBP RPC methods.
+#[rpc(server)]
+pub trait IbpApi<BlockHash> {
+	/// The services a member must provide.
+	#[method(name = "ibp_memberServices")]
+	fn member_services(&self, member: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<Service>>;
+
+	/// The most recent changes made to a member, oldest first.
+	#[method(name = "ibp_memberAuditLog")]
+	fn member_audit_log(&self, member: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<AuditRecord>>;
+
+	/// The payout a member can expect for the era in progress, and its unclaimed payouts.
+	#[method(name = "ibp_nextPayout")]
+	fn next_payout(&self, member: AccountId, at: Option<BlockHash>) -> RpcResult<Payout>;
+
+	/// Current alerts matching the filter.
+	#[method(name = "ibp_activeAlerts")]
+	fn active_alerts(&self, filter: AlertQuery, at: Option<BlockHash>) -> RpcResult<Vec<Alert>>;
+
+	/// Alerts waiting for quorum matching the filter, `monitorId` matches any reporting monitor.
+	#[method(name = "ibp_pendingAlerts")]
+	fn pending_alerts(&self, filter: AlertQuery, at: Option<BlockHash>) -> RpcResult<Vec<PendingAlert>>;
+
+	/// The alert types monitors may raise.
+	#[method(name = "ibp_alertTypes")]
+	fn alert_types(&self, at: Option<BlockHash>) -> RpcResult<Vec<AlertKind>>;
+
+	/// Mean time to acknowledge and to resolve over the recently resolved alerts of a member.
+	#[method(name = "ibp_alertStats")]
+	fn alert_stats(&self, member: AccountId, at: Option<BlockHash>) -> RpcResult<ResponseTimes>;
+
+	/// Last health check and alerts for every member that must provide a service.
+	#[method(name = "ibp_serviceHealth")]
+	fn service_health(&self, service: String, at: Option<BlockHash>) -> RpcResult<ServiceHealth>;
+
+	/// Uptime and latency of a member on a service per epoch, oldest epoch first.
+	#[method(name = "ibp_uptimeRollups")]
+	fn uptime_rollups(
+		&self,
+		member: AccountId,
+		service: String,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<EpochUptime>>;
+
+	/// Whether a member is healthy on all the services it must provide.
+	#[method(name = "ibp_memberCompliance")]
+	fn member_compliance(
+		&self,
+		member: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<MemberCompliance>;
+
+	/// Member status changes in finalized blocks, for one member or all members.
+	#[subscription(
+		name = "ibp_subscribeMemberStatus" => "ibp_memberStatus",
+		unsubscribe = "ibp_unsubscribeMemberStatus",
+		item = MemberStatusNotification
+	)]
+	async fn subscribe_member_status(&self, member: Option<AccountId>) -> SubscriptionResult;
+
+	/// Alerts registered or cleared in finalized blocks, matching the filter.
+	/// `monitorId` is not supported, alert events do not carry the monitor.
+	#[subscription(
+		name = "ibp_subscribeAlerts" => "ibp_alerts",
+		unsubscribe = "ibp_unsubscribeAlerts",
+		item = AlertNotification
+	)]
+	async fn subscribe_alerts(&self, filter: AlertQuery) -> SubscriptionResult;
+
+	/// Service status changes in finalized blocks, for one service or all services.
+	#[subscription(
+		name = "ibp_subscribeServiceStatus" => "ibp_serviceStatus",
+		unsubscribe = "ibp_unsubscribeServiceStatus",
+		item = ServiceStatusNotification
+	)]
+	async fn subscribe_service_status(&self, service: Option<String>) -> SubscriptionResult;
+}
+
+/// Provides the `ibp_*` RPC methods.
+pub struct Ibp<C, B> {
+	client: Arc<C>,
+	backend: Arc<B>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Ibp<C, B> {
+	/// Create new `Ibp` with the given reference to the client and backend.
+	pub fn new(client: Arc<C>, backend: Arc<B>) -> Self {
+		Self { client, backend, _marker: PhantomData }
+	}
+}
+
+/// Error codes of the `ibp_*` RPC methods.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+	/// A parameter is invalid or unknown.
+	InvalidParams,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidParams => 2,
+		}
+	}
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the IBP runtime API.",
+		Some(format!("{:?}", err)),
+	)
+}
+
+fn invalid_params(message: &str) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::InvalidParams.into(), message.to_string(), None::<()>)
+}
+
+/// Convert a readable id into a bounded id.
+fn bounded_id<B: TryFrom<Vec<u8>>>(id: &str) -> RpcResult<B> {
+	B::try_from(id.as_bytes().to_vec()).map_err(|_| invalid_params("Id is too long."))
+}
+
+/// The runtime filter for an alert query.
+fn alert_filter(filter: AlertQuery) -> RpcResult<AlertFilter<AccountId>> {
+	Ok(AlertFilter {
+		member_id: filter.member_id,
+		monitor_id: filter.monitor_id,
+		service_id: filter.service_id.as_deref().map(bounded_id).transpose()?,
+		domain_id: filter.domain_id.as_deref().map(bounded_id).transpose()?,
+		alert_type: filter.alert_type.as_deref().map(bounded_id).transpose()?,
+	})
+}
+
+/// Storage key of `frame_system::Events`.
+fn system_events_key() -> Vec<u8> {
+	[sp_core::twox_128(b"System"), sp_core::twox_128(b"Events")].concat()
+}
+
+/// Decode the runtime events of a block. The events are only read when the block runs the
+/// runtime version the node was built with, `RuntimeEvent` may not match any other version.
+fn block_events<C, B>(client: &C, backend: &B, hash: Hash) -> Result<Vec<RuntimeEvent>, String>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: IbpRuntimeApi<Block, AccountId, BlockNumber>,
+	B: Backend<Block>,
+{
+	let version = client
+		.runtime_api()
+		.version(hash)
+		.map_err(|e| format!("Unable to read the runtime version of block {:?}: {:?}", hash, e))?;
+	if version.spec_name != VERSION.spec_name || version.spec_version != VERSION.spec_version {
+		return Err(format!(
+			"Block {:?} runs {} version {}, the node decodes events of {} version {}.",
+			hash, version.spec_name, version.spec_version, VERSION.spec_name, VERSION.spec_version,
+		));
+	}
+	let raw = backend
+		.state_at(hash)
+		.and_then(|state| state.storage(&system_events_key()))
+		.map_err(|e| format!("Unable to read the events of block {:?}: {:?}", hash, e))?;
+	let Some(raw) = raw else { return Ok(Vec::new()) };
+	let records = Vec::<frame_system::EventRecord<RuntimeEvent, Hash>>::decode(&mut &raw[..])
+		.map_err(|e| format!("Unable to decode the events of block {:?}: {}", hash, e))?;
+	Ok(records.into_iter().map(|record| record.event).collect())
+}
+
+impl<C, B> Ibp<C, B>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: IbpRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: IbpRewardApi<Block, AccountId, Balance>,
+	C: BlockchainEvents<Block>,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn at(&self, at: Option<Hash>) -> Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+
+	/// Last check and alerts of a (member, service) pair.
+	fn pair_health(
+		&self,
+		at: Hash,
+		member: &AccountId,
+		service: &ServiceInfo,
+	) -> RpcResult<(Option<Check>, Vec<Alert>)> {
+		let api = self.client.runtime_api();
+		let last_check = api
+			.recent_checks(at, member.clone(), service.id.clone())
+			.map_err(runtime_error)?
+			.pop()
+			.map(Into::into);
+		let filter = AlertFilter {
+			member_id: Some(member.clone()),
+			monitor_id: None,
+			service_id: Some(service.id.clone()),
+			domain_id: None,
+			alert_type: None,
+		};
+		let alerts = api
+			.active_alerts(at, filter)
+			.map_err(runtime_error)?
+			.into_iter()
+			.map(Into::into)
+			.collect();
+		Ok((last_check, alerts))
+	}
+
+	/// Events of every finalized block, including blocks finalized implicitly. A block whose
+	/// events cannot be decoded yields an error, and no events of the blocks after it.
+	fn finalized_events(&self) -> impl futures::Stream<Item = Result<(Hash, RuntimeEvent), String>> + Send {
+		let client = self.client.clone();
+		let backend = self.backend.clone();
+		self.client.finality_notification_stream().flat_map(move |notification| {
+			let hashes = notification.tree_route.iter().copied().chain(std::iter::once(notification.hash));
+			let mut events = Vec::new();
+			for hash in hashes {
+				match block_events(&*client, &*backend, hash) {
+					Ok(block) => events.extend(block.into_iter().map(|event| Ok((hash, event)))),
+					Err(error) => {
+						events.push(Err(error));
+						break
+					},
+				}
+			}
+			futures::stream::iter(events)
+		})
+	}
+
+	/// Push the notifications produced by `select` to the subscriber, until it unsubscribes or
+	/// the events of a block cannot be decoded. The subscription is then closed with the error.
+	async fn subscribe<T, F>(&self, pending: PendingSubscriptionSink, select: F) -> SubscriptionResult
+	where
+		T: Serialize + Send + 'static,
+		F: Fn(Hash, RuntimeEvent) -> Option<T> + Send + 'static,
+	{
+		let mut events = self.finalized_events().boxed();
+		let sink = pending.accept().await?;
+		while let Some(event) = events.next().await {
+			let (hash, event) = event.map_err(|error| {
+				log::warn!(target: "rpc", "Closing the {} subscription: {}", sink.method_name(), error);
+				error
+			})?;
+			let Some(notification) = select(hash, event) else { continue };
+			if sink.send(SubscriptionMessage::from_json(&notification)?).await.is_err() {
+				// the subscriber is gone
+				break
+			}
+		}
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl<C, B> IbpApiServer<Hash> for Ibp<C, B>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: IbpRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: IbpRewardApi<Block, AccountId, Balance>,
+	C: BlockchainEvents<Block>,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn member_services(&self, member: AccountId, at: Option<Hash>) -> RpcResult<Vec<Service>> {
+		let api = self.client.runtime_api();
+		let services = api.services_for_member(self.at(at), member).map_err(runtime_error)?;
+		Ok(services.into_iter().map(Into::into).collect())
+	}
+
+	fn member_audit_log(&self, member: AccountId, at: Option<Hash>) -> RpcResult<Vec<AuditRecord>> {
+		let api = self.client.runtime_api();
+		let log = api.member_audit_log(self.at(at), member).map_err(runtime_error)?;
+		Ok(log.into_iter().map(Into::into).collect())
+	}
+
+	fn next_payout(&self, member: AccountId, at: Option<Hash>) -> RpcResult<Payout> {
+		let api = self.client.runtime_api();
+		let preview = api.next_payout(self.at(at), member).map_err(runtime_error)?;
+		Ok(preview.into())
+	}
+
+	fn active_alerts(&self, filter: AlertQuery, at: Option<Hash>) -> RpcResult<Vec<Alert>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		let filter = alert_filter(filter)?;
+		let version = api
+			.api_version::<dyn IbpRuntimeApi<Block, AccountId, BlockNumber>>(at)
+			.map_err(runtime_error)?
+			.unwrap_or(1);
+		if version < 5 {
+			#[allow(deprecated)]
+			let alerts = api.active_alerts_before_version_5(at, filter).map_err(runtime_error)?;
+			return Ok(alerts.into_iter().map(Into::into).collect());
+		}
+		let alerts = api.active_alerts(at, filter).map_err(runtime_error)?;
+		Ok(alerts.into_iter().map(Into::into).collect())
+	}
+
+	fn pending_alerts(&self, filter: AlertQuery, at: Option<Hash>) -> RpcResult<Vec<PendingAlert>> {
+		let api = self.client.runtime_api();
+		let alerts = api.pending_alerts(self.at(at), alert_filter(filter)?).map_err(runtime_error)?;
+		Ok(alerts.into_iter().map(Into::into).collect())
+	}
+
+	fn alert_types(&self, at: Option<Hash>) -> RpcResult<Vec<AlertKind>> {
+		let api = self.client.runtime_api();
+		let alert_types = api.alert_types(self.at(at)).map_err(runtime_error)?;
+		Ok(alert_types.into_iter().map(Into::into).collect())
+	}
+
+	fn alert_stats(&self, member: AccountId, at: Option<Hash>) -> RpcResult<ResponseTimes> {
+		let api = self.client.runtime_api();
+		let stats = api.alert_stats(self.at(at), member).map_err(runtime_error)?;
+		Ok(stats.into())
+	}
+
+	fn service_health(&self, service: String, at: Option<Hash>) -> RpcResult<ServiceHealth> {
+		let at = self.at(at);
+		let api = self.client.runtime_api();
+		let service_id = bounded_id(&service)?;
+		let service = api
+			.service(at, service_id)
+			.map_err(runtime_error)?
+			.ok_or_else(|| invalid_params("Service not found."))?;
+		let members = api.members_for_service(at, service.id.clone()).map_err(runtime_error)?;
+		let mut health = Vec::with_capacity(members.len());
+		for member in members {
+			let Some(member_id) = member.id.clone() else { continue };
+			let (last_check, alerts) = self.pair_health(at, &member_id, &service)?;
+			health.push(MemberHealth { member: member.into(), last_check, alerts });
+		}
+		Ok(ServiceHealth { service: service.into(), members: health })
+	}
+
+	fn uptime_rollups(
+		&self,
+		member: AccountId,
+		service: String,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<EpochUptime>> {
+		let api = self.client.runtime_api();
+		let rollups =
+			api.uptime_rollups(self.at(at), member, bounded_id(&service)?).map_err(runtime_error)?;
+		Ok(rollups.into_iter().map(Into::into).collect())
+	}
+
+	fn member_compliance(
+		&self,
+		member: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<MemberCompliance> {
+		let at = self.at(at);
+		let api = self.client.runtime_api();
+		let member_info = api
+			.member(at, member.clone())
+			.map_err(runtime_error)?
+			.ok_or_else(|| invalid_params("Member not found."))?;
+		let services = api.services_for_member(at, member.clone()).map_err(runtime_error)?;
+		let mut compliance = Vec::with_capacity(services.len());
+		for service in services {
+			let (last_check, alerts) = self.pair_health(at, &member, &service)?;
+			let compliant = alerts.is_empty() &&
+				last_check.as_ref().map_or(false, |check| check.status == HealthCheckStatus::Active);
+			compliance.push(ServiceCompliance { service: service.into(), last_check, alerts, compliant });
+		}
+		let compliant = compliance.iter().all(|service| service.compliant);
+		Ok(MemberCompliance { member: member_info.into(), services: compliance, compliant })
+	}
+
+	async fn subscribe_member_status(
+		&self,
+		pending: PendingSubscriptionSink,
+		member: Option<AccountId>,
+	) -> SubscriptionResult {
+		use pallet_ibp_member::Event;
+		self.subscribe(pending, move |block_hash, event| {
+			let (member_id, previous_status, status, reason) = match event {
+				RuntimeEvent::IbpMember(Event::MemberRegistered(member_id)) =>
+					(member_id, None, MemberStatus::Locked, None),
+				RuntimeEvent::IbpMember(Event::MemberStatusUpdated(member_id, previous, status, reason)) =>
+					(member_id, Some(previous), status, Some(readable(&reason))),
+				_ => return None,
+			};
+			if member.as_ref().map_or(false, |id| *id != member_id) {
+				return None
+			}
+			Some(MemberStatusNotification { block_hash, member_id, previous_status, status, reason })
+		})
+		.await
+	}
+
+	async fn subscribe_alerts(&self, pending: PendingSubscriptionSink, filter: AlertQuery) -> SubscriptionResult {
+		use pallet_ibp_alert::Event;
+		if filter.monitor_id.is_some() {
+			pending.reject(invalid_params("monitorId is not supported for subscriptions.")).await;
+			return Ok(())
+		}
+		self.subscribe(pending, move |block_hash, event| {
+			let (change, member_id, service_id, domain_id, alert_type) = match event {
+				RuntimeEvent::IbpAlert(Event::AlertRegistered(member_id, service_id, domain_id, alert_type)) =>
+					(AlertChange::Registered, member_id, service_id, domain_id, alert_type),
+				RuntimeEvent::IbpAlert(Event::AlertCleared(member_id, service_id, domain_id, alert_type)) =>
+					(AlertChange::Cleared, member_id, service_id, domain_id, alert_type),
+				_ => return None,
+			};
+			let notification = AlertNotification {
+				block_hash,
+				change,
+				member_id,
+				service_id: readable(&service_id),
+				domain_id: readable(&domain_id),
+				alert_type: readable(&alert_type),
+			};
+			filter.matches(&notification).then_some(notification)
+		})
+		.await
+	}
+
+	async fn subscribe_service_status(
+		&self,
+		pending: PendingSubscriptionSink,
+		service: Option<String>,
+	) -> SubscriptionResult {
+		use pallet_ibp_service::Event;
+		self.subscribe(pending, move |block_hash, event| {
+			let (service_id, status, reason) = match event {
+				RuntimeEvent::IbpService(Event::ServiceStatusUpdated(service_id, _, status, reason)) =>
+					(service_id, status, reason),
+				_ => return None,
+			};
+			let service_id = readable(&service_id);
+			if service.as_ref().map_or(false, |id| *id != service_id) {
+				return None
+			}
+			Some(ServiceStatusNotification { block_hash, service_id, status, reason: readable(&reason) })
+		})
+		.await
+	}
+}
@@ -1,35 +1,401 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-ibp-alert
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as IbpAlert;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{
+	sp_runtime::{traits::One, Percent},
+	weights::Weight,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_ibp_member::{MemberData, MemberStatus, MembershipLevel, Members};
+use pallet_ibp_service::{ChainRelation, DomainOwner, ServiceId, ServiceStatus, ServiceType};
+extern crate alloc;
+use alloc::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn bounded<B: TryFrom<Vec<u8>>>(value: &[u8]) -> B {
+	B::try_from(value.to_vec()).ok().expect("value fits in 32 bytes")
+}
+
+fn add_member<T: Config>(account_id: &T::AccountId, name: &[u8]) {
+	let member_data = MemberData::<T> {
+		id: Some(account_id.clone()),
+		name: bounded(name),
+		status: MemberStatus::Active,
+		level: MembershipLevel::One,
+	};
+	Members::<T>::insert(account_id, member_data);
+}
+
+/// A curator, a monitor, an active member, an active service and the `ServiceDown` alert type,
+/// whose alerts lock the member when unresolved for a block
+fn setup<T: Config>() -> (T::AccountId, T::AccountId, T::AccountId, ServiceId) {
+	let curator: T::AccountId = account("curator", 0, SEED);
+	MemberPallet::<T>::assign_curator(RawOrigin::Root.into(), curator.clone()).expect("curator is assigned");
+
+	let monitor: T::AccountId = whitelisted_caller();
+	add_member::<T>(&monitor, b"monitor");
+	MemberPallet::<T>::assign_monitor(RawOrigin::Signed(curator.clone()).into(), monitor.clone())
+		.expect("monitor is assigned");
+
+	let member_id: T::AccountId = account("member", 0, SEED);
+	add_member::<T>(&member_id, b"member");
+
+	ServicePallet::<T>::register_chain(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"polkadot"),
+		[1u8; 32],
+		ChainRelation::Relay,
+		0,
+		bounded(b"DOT"),
+	)
+	.expect("chain is registered");
+	let service_id: ServiceId = bounded(b"polkadot-rpc");
+	ServicePallet::<T>::register_service(
+		RawOrigin::Signed(curator.clone()).into(),
+		service_id.clone(),
+		bounded(b"polkadot"),
+		ServiceType::RPC,
+		MembershipLevel::One,
+		ServiceStatus::Active,
+	)
+	.expect("service is registered");
+	ServicePallet::<T>::register_domain(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"example.com"),
+		DomainOwner::Programme,
+	)
+	.expect("domain is registered");
+	ServicePallet::<T>::set_domain_service(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"example.com"),
+		service_id.clone(),
+		bounded(b"rpc.example.com"),
+	)
+	.expect("domain service is set");
+	IbpAlert::<T>::register_alert_type(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"ServiceDown"),
+		AlertSeverity::Critical,
+		description(),
+		None,
+		EscalationPolicy::LockMember { after: One::one() },
+	)
+	.expect("alert type is registered");
+	(curator, monitor, member_id, service_id)
+}
+
+fn add_alert<T: Config>(monitor: &T::AccountId, member_id: &T::AccountId, service_id: &ServiceId) {
+	IbpAlert::<T>::register_alert(
+		RawOrigin::Signed(monitor.clone()).into(),
+		1,
+		member_id.clone(),
+		service_id.clone(),
+		bounded(b"example.com"),
+		bounded(b"ServiceDown"),
+	)
+	.expect("alert is registered");
+}
+
+/// An alert type description of the maximum length
+fn description() -> AlertDescription {
+	alloc::vec![b'd'; 128].try_into().expect("description fits in 128 bytes")
+}
+
+/// A resolution note of the maximum length
+fn note() -> ResolutionNote {
+	alloc::vec![b'n'; 128].try_into().expect("note fits in 128 bytes")
+}
+
+/// Fill the member's history with resolved copies of its alert
+fn fill_history<T: Config>(monitor: &T::AccountId, member_id: &T::AccountId) {
+	let key = AlertKey::<T> { monitor_id: monitor.clone(), alert_id: 1 };
+	let mut alert = Alerts::<T>::get(key).expect("alert is registered");
+	alert.resolved_at_time = Some(1);
+	alert.resolution_note = Some(note());
+	AlertHistory::<T>::mutate(member_id, |history| {
+		while !history.is_full() {
+			let _ = history.try_push(alert.clone());
+		}
+	});
+}
+
+/// Assign `count` more monitors, each reporting the alert of `add_alert`
+fn add_reports<T: Config>(curator: &T::AccountId, member_id: &T::AccountId, service_id: &ServiceId, count: u32) {
+	for index in 0..count {
+		let monitor: T::AccountId = account("monitor", index, SEED);
+		let mut name = b"monitor".to_vec();
+		name.extend_from_slice(&index.to_be_bytes());
+		add_member::<T>(&monitor, &name);
+		MemberPallet::<T>::assign_monitor(RawOrigin::Signed(curator.clone()).into(), monitor.clone())
+			.expect("monitor is assigned");
+		add_alert::<T>(&monitor, member_id, service_id);
+	}
+}
+
+/// Fill the pending alert expiries of the blocks a quorum window of one block opened now ends in,
+/// but the last one
+fn fill_pending_expiries<T: Config>(member_id: &T::AccountId, service_id: &ServiceId) {
+	let key = (member_id.clone(), service_id.clone(), bounded::<AlertType>(b"Other"));
+	let mut expires_at = frame_system::Pallet::<T>::block_number() + One::one();
+	for _ in 0..MAX_PENDING_EXPIRY_DELAY {
+		PendingAlertExpiries::<T>::mutate(expires_at, |expiring| {
+			while !expiring.is_full() {
+				let _ = expiring.try_push(key.clone());
+			}
+		});
+		expires_at += One::one();
+	}
+}
+
+/// Fill the escalation checks of the blocks an alert raised now falls due in, but the last one
+fn fill_due_escalations<T: Config>(monitor: &T::AccountId, member_id: &T::AccountId, service_id: &ServiceId) {
+	let raised_at = frame_system::Pallet::<T>::block_number();
+	let due = DueEscalation::<T> {
+		key: AlertKey { monitor_id: monitor.clone(), alert_id: 0 },
+		member_id: member_id.clone(),
+		service_id: service_id.clone(),
+		alert_type: bounded(b"ServiceDown"),
+		raised_at,
+	};
+	let mut due_at = raised_at + One::one();
+	for _ in 0..MAX_ESCALATION_DELAY {
+		DueEscalations::<T>::mutate(due_at, |escalations| {
+			while !escalations.is_full() {
+				let _ = escalations.try_push(due.clone());
+			}
+		});
+		due_at += One::one();
+	}
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
+	/// Worst case: every one of `m` active monitors must report, the last report confirms the alert,
+	/// and the pending alert expiry and escalation check go in the last blocks with room
+	#[benchmark]
+	fn register_alert(m: Linear<1, { T::MaxAlertReports::get() }>) {
+		let (curator, monitor, member_id, service_id) = setup::<T>();
+		IbpAlert::<T>::set_quorum(RawOrigin::Root.into(), AlertQuorum::Fraction(Percent::one()), One::one())
+			.expect("quorum is set");
+		fill_pending_expiries::<T>(&member_id, &service_id);
+		add_reports::<T>(&curator, &member_id, &service_id, m - 1);
+		fill_due_escalations::<T>(&monitor, &member_id, &service_id);
+		let alert_type: AlertType = bounded(b"ServiceDown");
+		#[extrinsic_call]
+		register_alert(
+			RawOrigin::Signed(monitor),
+			1,
+			member_id.clone(),
+			service_id.clone(),
+			bounded(b"example.com"),
+			alert_type.clone(),
+		);
+
+		assert!(AlertIndex::<T>::contains_key((member_id, service_id, alert_type)));
+	}
+
+	#[benchmark]
+	fn set_quorum() {
+		let window: BlockNumberFor<T> = 10u32.into();
+		#[extrinsic_call]
+		set_quorum(RawOrigin::Root, AlertQuorum::Count(2), window);
+
+		assert_eq!(Quorum::<T>::get(), AlertQuorum::Count(2));
+	}
+
+	#[benchmark]
+	fn set_escalation_dry_run() {
+		#[extrinsic_call]
+		set_escalation_dry_run(RawOrigin::Root, true);
+
+		assert!(EscalationDryRun::<T>::get());
+	}
+
+	#[benchmark]
+	fn register_alert_type() {
+		let (curator, ..) = setup::<T>();
+		let code: AlertType = bounded(b"HostDown");
+		#[extrinsic_call]
+		register_alert_type(
+			RawOrigin::Signed(curator),
+			code.clone(),
+			AlertSeverity::Critical,
+			description(),
+			Some(One::one()),
+			EscalationPolicy::LockMember { after: One::one() },
+		);
+
+		assert!(AlertTypes::<T>::contains_key(code));
+	}
+
+	#[benchmark]
+	fn update_alert_type() {
+		let (curator, ..) = setup::<T>();
+		let code: AlertType = bounded(b"ServiceDown");
+		#[extrinsic_call]
+		update_alert_type(
+			RawOrigin::Signed(curator),
+			code.clone(),
+			AlertSeverity::Warning,
+			description(),
+			Some(One::one()),
+			EscalationPolicy::ChillAssignment { count: 3, within: One::one() },
+		);
+
+		assert_eq!(AlertTypes::<T>::get(code).expect("type is kept").severity, AlertSeverity::Warning);
+	}
+
+	#[benchmark]
+	fn deprecate_alert_type() {
+		let (curator, ..) = setup::<T>();
+		let code: AlertType = bounded(b"ServiceDown");
+		#[extrinsic_call]
+		deprecate_alert_type(RawOrigin::Signed(curator), code.clone());
+
+		assert_eq!(AlertTypes::<T>::get(code).expect("type is kept").status, AlertTypeStatus::Deprecated);
+	}
+
+	/// `n` pending alerts of distinct members expire in the same block
+	#[benchmark]
+	fn expire_pending_alerts(n: Linear<0, { T::MaxPendingExpiries::get() }>) {
+		let (_, monitor, _, service_id) = setup::<T>();
+		let expires_at: BlockNumberFor<T> = 10u32.into();
+		let alert_type: AlertType = bounded(b"ServiceDown");
+		for index in 0..n {
+			let member_id: T::AccountId = account("member", index + 1, SEED);
+			let key = (member_id, service_id.clone(), alert_type.clone());
+			let report = AlertReport::<T> {
+				monitor_id: monitor.clone(),
+				alert_id: index as u64,
+				domain_id: bounded(b"example.com"),
+				reported_at: One::one(),
+			};
+			let reports = alloc::vec![report].try_into().ok().expect("one report fits");
+			PendingAlerts::<T>::insert(&key, PendingAlert::<T> { expires_at, reports });
+			PendingAlertExpiries::<T>::try_mutate(expires_at, |expiring| expiring.try_push(key))
+				.expect("expiry fits");
+		}
+		#[block]
+		{
+			IbpAlert::<T>::expire_pending_alerts(expires_at);
+		}
+
+		assert_eq!(PendingAlerts::<T>::iter().count(), 0);
+	}
+
+	/// `n` alerts of distinct members with a full history fall due in the same block, each counts
+	/// as repeated. A dry run: the enforcement is charged by `lock_weight` and `chill_weight`.
+	#[benchmark]
+	fn escalate_alerts(n: Linear<0, { T::MaxDueEscalations::get() }>) {
+		let (curator, monitor, _, service_id) = setup::<T>();
+		IbpAlert::<T>::update_alert_type(
+			RawOrigin::Signed(curator).into(),
+			bounded(b"ServiceDown"),
+			AlertSeverity::Critical,
+			description(),
+			None,
+			EscalationPolicy::ChillAssignment {
+				count: T::MaxAlertHistory::get().saturating_add(1),
+				within: 100u32.into(),
+			},
+		)
+		.expect("alert type is updated");
+		EscalationDryRun::<T>::put(true);
+		for index in 0..n {
+			let member_id: T::AccountId = account("member", index + 1, SEED);
+			let mut name = b"member".to_vec();
+			name.extend_from_slice(&index.to_be_bytes());
+			add_member::<T>(&member_id, &name);
+			IbpAlert::<T>::register_alert(
+				RawOrigin::Signed(monitor.clone()).into(),
+				index as u64,
+				member_id.clone(),
+				service_id.clone(),
+				bounded(b"example.com"),
+				bounded(b"ServiceDown"),
+			)
+			.expect("alert is registered");
+			let key = AlertKey::<T> { monitor_id: monitor.clone(), alert_id: index as u64 };
+			let alert = Alerts::<T>::get(key).expect("alert is registered");
+			AlertHistory::<T>::mutate(&member_id, |history| {
+				while !history.is_full() {
+					let _ = history.try_push(alert.clone());
+				}
+			});
+		}
+		let due_at = frame_system::Pallet::<T>::block_number() + One::one();
+		#[block]
+		{
+			IbpAlert::<T>::escalate_alerts(due_at);
+		}
+
+		assert!(DueEscalations::<T>::get(due_at).is_empty());
+		let locked = Members::<T>::iter_values().filter(|member| member.status == MemberStatus::Locked).count();
+		assert_eq!(locked, 0);
+	}
+
+	/// `n` alerts past their time to live are expired into a full history
+	#[benchmark]
+	fn sweep_alerts(n: Linear<1, 100>) {
+		let (_, monitor, member_id, service_id) = setup::<T>();
+		add_alert::<T>(&monitor, &member_id, &service_id);
+		fill_history::<T>(&monitor, &member_id);
+		let key = AlertKey::<T> { monitor_id: monitor.clone(), alert_id: 1 };
+		let alert = Alerts::<T>::get(key).expect("alert is registered");
+		for alert_id in 2..=n as u64 {
+			let key = AlertKey::<T> { monitor_id: monitor.clone(), alert_id };
+			Alerts::<T>::insert(key, AlertData { alert_id, ..alert.clone() });
+		}
+		let now = IbpAlert::<T>::expires_at(&alert);
+		#[block]
+		{
+			IbpAlert::<T>::sweep_alerts(now, Weight::MAX);
+		}
+
+		assert_eq!(Alerts::<T>::iter().count(), 0);
+	}
+
+	/// Worst case: the history is full, the oldest alert is dropped
+	#[benchmark]
+	fn clear_alert() {
+		let (_, monitor, member_id, service_id) = setup::<T>();
+		add_alert::<T>(&monitor, &member_id, &service_id);
+		fill_history::<T>(&monitor, &member_id);
+		#[extrinsic_call]
+		clear_alert(RawOrigin::Signed(monitor), 1, note());
+
+		assert_eq!(Alerts::<T>::iter().count(), 0);
+	}
+
+	/// Worst case: the history is full, the oldest alert is dropped
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn clear_monitor_alert() {
+		let (curator, monitor, member_id, service_id) = setup::<T>();
+		add_alert::<T>(&monitor, &member_id, &service_id);
+		fill_history::<T>(&monitor, &member_id);
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		clear_monitor_alert(RawOrigin::Signed(curator), monitor, 1, note());
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(Alerts::<T>::iter().count(), 0);
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn acknowledge_alert() {
+		let (_, monitor, member_id, service_id) = setup::<T>();
+		add_alert::<T>(&monitor, &member_id, &service_id);
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		acknowledge_alert(RawOrigin::Signed(member_id.clone()), monitor.clone(), 1);
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		let alert = Alerts::<T>::get(AlertKey::<T> { monitor_id: monitor, alert_id: 1 }).expect("alert is kept");
+		assert_eq!(alert.acknowledged_by, Some(member_id));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(IbpAlert, crate::mock::new_test_ext(), crate::mock::Test);
 }
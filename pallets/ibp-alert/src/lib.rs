@@ -5,14 +5,13 @@
 
 use codec::{ Decode, Encode, MaxEncodedLen };
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use frame_support::{
+    sp_runtime::Percent,
     traits::ConstU32,
     BoundedVec,
 };
 
-/// implement jobs at the end of each session
-use pallet_session::{SessionManager, ShouldEndSession};
-
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -33,32 +32,106 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 pub use weights::*;
+pub mod migrations;
 
 use pallet_ibp_member::Pallet as MemberPallet;
 use pallet_ibp_service::Pallet as ServicePallet;
+/// Escalations lock members in `pallet-ibp-member` and chill assignments in `pallet-ibp-service`
+pub use pallet_ibp_member::MemberEnforcement;
+pub use pallet_ibp_service::AssignmentEnforcement;
 
-pub type DomainId = BoundedVec<u8, ConstU32<32>>;
+/// IBP domains are registered in `pallet-ibp-service`
+pub use pallet_ibp_service::DomainId;
+/// Code of an alert type registered in `AlertTypes`, e.g. `ServiceDown`
 pub type AlertType = BoundedVec<u8, ConstU32<32>>;
+pub type AlertDescription = BoundedVec<u8, ConstU32<128>>;
+/// How an alert was resolved, free text
+pub type ResolutionNote = BoundedVec<u8, ConstU32<128>>;
+
+/// Blocks an escalation check may be pushed back when its block is full, see `MaxDueEscalations`
+pub const MAX_ESCALATION_DELAY: u32 = 16;
+
+/// How urgently an alert needs attention
+#[derive(
+    Clone, Copy, Debug, Default, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen, Serialize,
+    Deserialize,
+)]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+/// What happens when alerts of a type are not dealt with
+#[derive(Clone, Debug, Default, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum EscalationPolicy<BlockNumber> {
+    /// Alerts of the type never escalate
+    #[default]
+    None,
+    /// `count` alerts of the type for the same (member, service) raised within `within` blocks
+    /// chill the service assignment
+    ChillAssignment { count: u32, within: BlockNumber },
+    /// An alert unresolved for `after` blocks locks the member
+    LockMember { after: BlockNumber },
+}
 
-// #[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
-// pub enum AlertType {
-//     HostDown,
-//     ServiceDown,
-//     InstanceDown,
-//     BlackboxProbeFailed,
-//     BlackboxProbeHttpFailure,
-//     NoIncomingConnection,
-//     BlockFinalizationLaggingBehind,
-//     BlackboxSslCertificateWillExpireSoon,
-//     SubstrateServiceTooManyRestarts,
-//     Other,
-// }
-
-// impl Default for AlertType {
-//     fn default() -> Self {
-//       AlertType::Other
-//     }
-// }
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum AlertTypeStatus {
+    Active,
+    /// No new alerts of the type are accepted, existing alerts are kept
+    Deprecated,
+}
+
+/// An alert type monitors may raise, managed by the curators
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub struct AlertTypeInfo<BlockNumber> {
+    /// Severity of every alert of the type
+    pub severity: AlertSeverity,
+    pub description: AlertDescription,
+    /// Blocks after which alerts of the type expire unless resolved, `None` for the pallet default
+    pub expires_after: Option<BlockNumber>,
+    pub escalation: EscalationPolicy<BlockNumber>,
+    pub status: AlertTypeStatus,
+}
+
+/// Response times over the resolved alerts of a member, in milliseconds
+#[derive(Clone, Debug, Default, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct AlertStats {
+    /// Resolved alerts in the history
+    pub resolved: u32,
+    /// Resolved alerts the member acknowledged
+    pub acknowledged: u32,
+    /// Mean time from raising to acknowledging, over the acknowledged alerts
+    pub mean_time_to_acknowledge: Option<u64>,
+    /// Mean time from raising to resolving
+    pub mean_time_to_resolve: Option<u64>,
+}
+
+/// Blocks a pending alert expiry may be pushed back when its block is full, see `MaxPendingExpiries`
+pub const MAX_PENDING_EXPIRY_DELAY: u32 = 16;
+
+/// Filter for querying alerts, `None` fields match any alert
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct AlertFilter<AccountId> {
+    pub member_id: Option<AccountId>,
+    pub monitor_id: Option<AccountId>,
+    pub service_id: Option<pallet_ibp_service::ServiceId>,
+    pub domain_id: Option<DomainId>,
+    pub alert_type: Option<AlertType>,
+}
+
+/// How many monitors must report the same (member, service, alert type) before the alert takes effect
+#[derive(Clone, Copy, Debug, Default, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum AlertQuorum {
+    /// The first report takes effect, no quorum
+    #[default]
+    Single,
+    /// A number of distinct monitors
+    Count(u32),
+    /// A share of the active monitors, rounded up
+    Fraction(Percent),
+}
 
 
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
@@ -67,16 +140,29 @@ pub mod pallet {
     // Import various useful types required by all FRAME pallets.
     use super::*;
     use frame_support::pallet_prelude::*;
+    use frame_support::sp_runtime::{
+        traits::{One, Saturating, Zero},
+        SaturatedConversion,
+    };
+    use frame_support::traits::UnixTime;
+    use frame_support::weights::WeightMeter;
     use frame_system::pallet_prelude::*;
 
     use pallet_ibp_service::{ServiceId, ServiceStatus};
     use pallet_ibp_service::Error::ServiceNotFound;
-    use pallet_ibp_member::MemberStatus;
+    use pallet_ibp_member::{MemberStatus, StatusReason};
     use pallet_ibp_member::Error::MemberNotFound;
 
+    /// The in-code storage version.
+    /// v1: alerts carry their severity, acknowledgement and resolution
+    /// v2: alert types are registered in `AlertTypes`
+    /// v3: alerts and history record `expired_at_time`
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
     // (`Call`s) in this pallet.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -90,15 +176,38 @@ pub mod pallet {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// A type representing the weights required by the dispatchables of this pallet.
-        // type WeightInfo: WeightInfo;
+        type WeightInfo: WeightInfo;
         type AlertKey: Parameter + Member + TypeInfo + MaxEncodedLen;
+        /// Maximum number of monitors reporting the same pending alert,
+        /// at least the number of monitors for a `Fraction` quorum to be reachable
+        #[pallet::constant]
+        type MaxAlertReports: Get<u32>;
+        /// Pending alerts allowed to expire in the same block
+        #[pallet::constant]
+        type MaxPendingExpiries: Get<u32>;
+        /// Wall clock time, alerts record when they were raised, acknowledged and resolved
+        type UnixTime: UnixTime;
+        /// Maximum number of resolved alerts kept per member.
+        /// When the history is full the oldest alert is dropped.
+        #[pallet::constant]
+        type MaxAlertHistory: Get<u32>;
+        /// Locks members when an alert escalates
+        type Members: MemberEnforcement<Self::AccountId>;
+        /// Chills service assignments when an alert escalates
+        type Assignments: AssignmentEnforcement<Self::AccountId>;
+        /// Alert escalations allowed to fall due in the same block
+        #[pallet::constant]
+        type MaxDueEscalations: Get<u32>;
+        /// Blocks an alert lives unless resolved, when its type sets no `expires_after`
+        #[pallet::constant]
+        type DefaultAlertTtl: Get<BlockNumberFor<Self>>;
     }
 
     #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
     #[scale_info(skip_type_params(T))]
     pub struct AlertKey<T: Config> {
-        monitor_id: T::AccountId,
-        alert_id: u64
+        pub monitor_id: T::AccountId,
+        pub alert_id: u64
     }
 
     #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -115,7 +224,62 @@ pub mod pallet {
         // service Id: unique identifier for the service, string
         pub service_id: ServiceId,
         // service type; enum ['RPC', 'BOOT']
-        pub alert_type: AlertType
+        pub alert_type: AlertType,
+        pub severity: AlertSeverity,
+        // block and unix time in milliseconds at which the alert took effect
+        pub raised_at: BlockNumberFor<T>,
+        pub raised_at_time: u64,
+        // the member that acknowledged the alert, and when
+        pub acknowledged_by: Option<T::AccountId>,
+        pub acknowledged_at_time: Option<u64>,
+        // set once the alert is resolved and moved into `AlertHistory`
+        pub resolved_at_time: Option<u64>,
+        pub resolution_note: Option<ResolutionNote>,
+        // set instead when the alert expired unresolved and was moved into `AlertHistory`
+        pub expired_at_time: Option<u64>,
+    }
+
+    impl<T: Config> AlertData<T> {
+        /// Whether this alert matches all the fields set in the filter
+        pub fn matches(&self, filter: &AlertFilter<T::AccountId>) -> bool {
+            filter.member_id.as_ref().map_or(true, |id| *id == self.member_id)
+                && filter.monitor_id.as_ref().map_or(true, |id| *id == self.monitor_id)
+                && filter.service_id.as_ref().map_or(true, |id| *id == self.service_id)
+                && filter.domain_id.as_ref().map_or(true, |id| *id == self.domain_id)
+                && filter.alert_type.as_ref().map_or(true, |t| *t == self.alert_type)
+        }
+    }
+
+    /// A monitor's report of an alert waiting for quorum
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct AlertReport<T: Config> {
+        pub monitor_id: T::AccountId,
+        // the monitor's own id for the alert
+        pub alert_id: u64,
+        pub domain_id: DomainId,
+        pub reported_at: BlockNumberFor<T>,
+    }
+
+    /// An alert reported by fewer monitors than the quorum requires
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct PendingAlert<T: Config> {
+        /// The alert expires at this block unless the quorum is reached
+        pub expires_at: BlockNumberFor<T>,
+        /// Reports from distinct monitors, oldest first
+        pub reports: BoundedVec<AlertReport<T>, T::MaxAlertReports>,
+    }
+
+    /// An alert to check against the escalation policy of its type
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct DueEscalation<T: Config> {
+        pub key: AlertKey<T>,
+        pub member_id: T::AccountId,
+        pub service_id: ServiceId,
+        pub alert_type: AlertType,
+        pub raised_at: BlockNumberFor<T>,
     }
 
     /// storage for this pallet. 
@@ -128,6 +292,80 @@ pub mod pallet {
     #[pallet::getter(fn alert_index)]
     pub type AlertIndex<T: Config> = StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId, AlertType), u64, OptionQuery>;
 
+    /// Resolved and expired alerts per member, oldest first, bounded by `MaxAlertHistory`
+    #[pallet::storage]
+    #[pallet::getter(fn alert_history)]
+    pub type AlertHistory<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<AlertData<T>, T::MaxAlertHistory>, ValueQuery>;
+
+    /// The alert types monitors may raise, by code
+    #[pallet::storage]
+    #[pallet::getter(fn alert_types)]
+    pub type AlertTypes<T: Config> =
+        StorageMap<_, Blake2_128Concat, AlertType, AlertTypeInfo<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Reports needed before an alert takes effect
+    #[pallet::storage]
+    #[pallet::getter(fn quorum)]
+    pub type Quorum<T: Config> = StorageValue<_, AlertQuorum, ValueQuery>;
+
+    /// Number of blocks after the first report within which the quorum must be reached
+    #[pallet::storage]
+    #[pallet::getter(fn quorum_window)]
+    pub type QuorumWindow<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Alerts waiting for quorum, by (member, service, alert type)
+    #[pallet::storage]
+    #[pallet::getter(fn pending_alert)]
+    pub type PendingAlerts<T: Config> =
+        StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId, AlertType), PendingAlert<T>, OptionQuery>;
+
+    /// The pending alerts expiring at each block, cleared by `on_initialize`
+    #[pallet::storage]
+    pub type PendingAlertExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<(T::AccountId, ServiceId, AlertType), T::MaxPendingExpiries>,
+        ValueQuery,
+    >;
+
+    /// The alerts due for an escalation check at each block, processed by `on_initialize`
+    #[pallet::storage]
+    pub type DueEscalations<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<DueEscalation<T>, T::MaxDueEscalations>, ValueQuery>;
+
+    /// Escalations only emit `AlertEscalationDryRun` when set, members and assignments are left alone
+    #[pallet::storage]
+    #[pallet::getter(fn escalation_dry_run)]
+    pub type EscalationDryRun<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Raw key of the last alert visited by the `on_idle` sweep, it resumes after it
+    #[pallet::storage]
+    pub type AlertSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// GenesisConfig - the initial alert types
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Initial alert types: (code, type)
+        pub alert_types: Vec<(AlertType, AlertTypeInfo<BlockNumberFor<T>>)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (code, alert_type) in &self.alert_types {
+                assert!(!AlertTypes::<T>::contains_key(code), "duplicate alert type in genesis");
+                assert!(
+                    Pallet::<T>::ensure_valid_escalation(&alert_type.escalation, alert_type.expires_after).is_ok(),
+                    "invalid escalation policy in genesis"
+                );
+                AlertTypes::<T>::insert(code, alert_type);
+            }
+        }
+    }
+
     /// Events that functions in this pallet can emit.
     ///
     /// Events are a simple means of indicating to the outside world (such as dApps, chain explorers
@@ -142,8 +380,35 @@ pub mod pallet {
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         AlertRegistered(T::AccountId, ServiceId, DomainId, AlertType),
+        // (member, service, domain, alert type)
         AlertCleared(T::AccountId, ServiceId, DomainId, AlertType),
         AlertIndexGenerated(),
+        /// (quorum, window)
+        QuorumSet(AlertQuorum, BlockNumberFor<T>),
+        /// (member, service, alert type, reports, reports required)
+        AlertPending(T::AccountId, ServiceId, AlertType, u32, u32),
+        /// (member, service, alert type, reports), the alert takes effect
+        AlertConfirmed(T::AccountId, ServiceId, AlertType, u32),
+        /// (member, service, alert type, reports), the quorum was not reached in time
+        PendingAlertExpired(T::AccountId, ServiceId, AlertType, u32),
+        /// (member, service, alert type)
+        AlertAcknowledged(T::AccountId, ServiceId, AlertType),
+        AlertTypeRegistered(AlertType),
+        AlertTypeUpdated(AlertType),
+        AlertTypeDeprecated(AlertType),
+        /// (member, service, alert type, policy), the member or assignment was acted on and the
+        /// curators should follow up
+        AlertEscalated(T::AccountId, ServiceId, AlertType, EscalationPolicy<BlockNumberFor<T>>),
+        /// (member, service, alert type, policy), the alert would have escalated
+        AlertEscalationDryRun(T::AccountId, ServiceId, AlertType, EscalationPolicy<BlockNumberFor<T>>),
+        /// (member, service, alert type, error), e.g. the member was locked already
+        AlertEscalationFailed(T::AccountId, ServiceId, AlertType, DispatchError),
+        /// (dry run)
+        EscalationDryRunSet(bool),
+        /// (member, service, domain, alert type), the alert was not resolved within its time to live
+        AlertExpired(T::AccountId, ServiceId, DomainId, AlertType),
+        /// (monitor, member, service, alert type), the monitor that raised the alert was removed
+        MonitorAlertRemoved(T::AccountId, T::AccountId, ServiceId, AlertType),
     }
 
     /// Errors that can be returned by this pallet.
@@ -170,6 +435,48 @@ pub mod pallet {
         // check for service membership level
         ServiceMembershipLevelMismatch,
         MemberServiceAlertExists,
+        // the domain is not registered for the service, or retired
+        UnknownDomainService,
+        /// The quorum can never be reached, or the window is zero
+        InvalidQuorum,
+        /// The monitor already reported this pending alert
+        AlertAlreadyReported,
+        /// `MaxAlertReports` monitors already reported this pending alert
+        TooManyAlertReports,
+        /// `MaxPendingExpiries` pending alerts already expire at the end of the quorum window and
+        /// the `MAX_PENDING_EXPIRY_DELAY` blocks after it
+        TooManyPendingAlerts,
+        /// Only the member the alert applies to can acknowledge it
+        NotAlertMember,
+        AlertAlreadyAcknowledged,
+        AlertTypeAlreadyExists,
+        /// Alerts must be of a type registered in `AlertTypes`
+        AlertTypeNotFound,
+        /// No new alerts of a deprecated type are accepted
+        AlertTypeDeprecated,
+        /// A `ChillAssignment` count of zero or above what `MaxAlertHistory` can track, or a zero period
+        InvalidEscalationPolicy,
+        /// `MaxDueEscalations` escalations already fall due at the block the alert escalates and
+        /// the `MAX_ESCALATION_DELAY` blocks after it, or before the alert expires
+        TooManyDueEscalations,
+        /// Alerts of the type would expire before a `LockMember` policy locks the member
+        AlertExpiresBeforeEscalation,
+        /// The monitor already has an alert under this id
+        AlertIdInUse,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Drop the pending alerts whose quorum window ends at this block, and escalate the
+        /// alerts due.
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::expire_pending_alerts(block_number).saturating_add(Self::escalate_alerts(block_number))
+        }
+
+        /// Sweep stale alerts with the weight left in the block.
+        fn on_idle(block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::sweep_alerts(block_number, remaining_weight)
+        }
     }
 
     /// The pallet's dispatchable functions ([`Call`]s).
@@ -187,19 +494,23 @@ pub mod pallet {
     #[pallet::call]
     impl<T: Config> Pallet<T> {
 
+        /// Register an alert. With a quorum set the alert stays pending until enough monitors
+        /// report the same (member, service, alert type) within the quorum window.
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::register_alert(T::MaxAlertReports::get()))]
         pub fn register_alert(
             origin: OriginFor<T>, 
             alert_id: u64,
             member_id: T::AccountId,
             service_id: ServiceId,
             domain_id: DomainId,
-            alert_type: AlertType
+            alert_type: AlertType,
         ) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             // only monitors can register health checks
             ensure!(MemberPallet::<T>::monitors(&sender), Error::<T>::NotAMonitor);
+            let type_info = AlertTypes::<T>::get(&alert_type).ok_or(Error::<T>::AlertTypeNotFound)?;
+            ensure!(type_info.status == AlertTypeStatus::Active, Error::<T>::AlertTypeDeprecated);
             // ensure member exists
             let member = MemberPallet::<T>::members(&member_id).ok_or(MemberNotFound)?;
             if member.status != MemberStatus::Active {
@@ -210,8 +521,11 @@ pub mod pallet {
             if service.status != ServiceStatus::Active {
                 return Err(Error::<T>::ServiceNotActive.into());
             }
+            ensure!(ServicePallet::<T>::is_domain_service(&domain_id, &service_id), Error::<T>::UnknownDomainService);
             let alert_index = (member_id.clone(), service_id.clone(), alert_type.clone());
             ensure!(!AlertIndex::<T>::contains_key(alert_index.clone()), Error::<T>::MemberServiceAlertExists);
+            let key: AlertKey<T> = AlertKey { monitor_id: sender.clone(), alert_id };
+            ensure!(!Alerts::<T>::contains_key(&key), Error::<T>::AlertIdInUse);
             // check member.level GE service.membershipLevel
             // ensure!(member.level >= service.level, Error::<T>::ServiceMembershipLevelMismatch);
             // let level = ServiceMembershipLevel::Zero;
@@ -223,23 +537,29 @@ pub mod pallet {
                 member_id: member_id.clone(),
                 service_id: service_id.clone(),
                 domain_id: domain_id.clone(),
-                alert_type: alert_type.clone()
+                alert_type: alert_type.clone(),
+                severity: type_info.severity,
+                raised_at: frame_system::Pallet::<T>::block_number(),
+                raised_at_time: Self::now(),
+                acknowledged_by: None,
+                acknowledged_at_time: None,
+                resolved_at_time: None,
+                resolution_note: None,
+                expired_at_time: None,
             };
-            let key: AlertKey<T> = AlertKey { monitor_id: sender.clone(), alert_id };
-            // let key = ( sender.clone(), alert_id );
-            // store the alert
-            Alerts::<T>::insert(key, alert_data);
-            // update alert index
-            AlertIndex::<T>::insert(alert_index, 1);
-            Self::deposit_event(Event::AlertRegistered(member_id, service_id, domain_id, alert_type));
-            Ok(())
+            match Quorum::<T>::get() {
+                AlertQuorum::Single => Self::store_alert(alert_data),
+                quorum => Self::report_alert(alert_data, quorum),
+            }
         }
 
+        /// Resolve an alert, it moves into the member's `AlertHistory`
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::clear_alert())]
         pub fn clear_alert(
             origin: OriginFor<T>, 
-            alert_id: u64
+            alert_id: u64,
+            resolution_note: ResolutionNote,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // get the alert by (monitor, Id)
@@ -251,21 +571,42 @@ pub mod pallet {
                 alert.monitor_id == sender || MemberPallet::<T>::curators(&sender), 
                 Error::<T>::BadOriginOrNotACurator
             );
-            // delete the alert
-            Alerts::<T>::remove(key);
-            // delete alert index
-            let alert_index = (sender.clone(), alert.service_id.clone(), alert.alert_type.clone());
-            AlertIndex::<T>::remove(alert_index);
-            Self::deposit_event(Event::AlertCleared(sender, alert.service_id, alert.domain_id, alert.alert_type));
+            Self::resolve_alert(key, alert, resolution_note);
             Ok(())
         }
 
+        /// Acknowledge an alert, only the member it applies to can
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::acknowledge_alert())]
+        pub fn acknowledge_alert(
+            origin: OriginFor<T>,
+            monitor_id: T::AccountId,
+            alert_id: u64,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let key: AlertKey<T> = AlertKey { monitor_id, alert_id };
+            Alerts::<T>::try_mutate(key, |maybe_alert| {
+                let alert = maybe_alert.as_mut().ok_or(Error::<T>::AlertNotFound)?;
+                ensure!(alert.member_id == sender, Error::<T>::NotAlertMember);
+                ensure!(alert.acknowledged_by.is_none(), Error::<T>::AlertAlreadyAcknowledged);
+                alert.acknowledged_by = Some(sender);
+                alert.acknowledged_at_time = Some(Self::now());
+                Self::deposit_event(Event::AlertAcknowledged(
+                    alert.member_id.clone(),
+                    alert.service_id.clone(),
+                    alert.alert_type.clone(),
+                ));
+                Ok(())
+            })
+        }
+
         #[pallet::call_index(20)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::clear_monitor_alert())]
         pub fn clear_monitor_alert(
             origin: OriginFor<T>, 
             monitor_id: T::AccountId,
-            alert_id: u64
+            alert_id: u64,
+            resolution_note: ResolutionNote,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only curators can force clear alerts
@@ -274,47 +615,481 @@ pub mod pallet {
             let key: AlertKey<T> = AlertKey { monitor_id: monitor_id.clone(), alert_id };
             // let key = ( monitor_id, alert_id );
             let alert: AlertData<T> = Alerts::<T>::get(key.clone()).ok_or(Error::<T>::AlertNotFound)?;
-            // delete the alert
-            Alerts::<T>::remove(key);
-            // delete alert index
-            let alert_index = (monitor_id.clone(), alert.service_id.clone(), alert.alert_type.clone());
-            AlertIndex::<T>::remove(alert_index);
-            Self::deposit_event(Event::AlertCleared(sender, alert.service_id, alert.domain_id, alert.alert_type));
+            Self::resolve_alert(key, alert, resolution_note);
+            Ok(())
+        }
+
+        /// Set the number of monitors that must report an alert within `window` blocks
+        /// before it takes effect. Pending alerts keep the window they started with.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::set_quorum())]
+        pub fn set_quorum(origin: OriginFor<T>, quorum: AlertQuorum, window: BlockNumberFor<T>) -> DispatchResult {
+            // TODO do this via governance
+            ensure_root(origin)?;
+            let valid = match quorum {
+                AlertQuorum::Single => true,
+                AlertQuorum::Count(count) => count > 0 && count <= T::MaxAlertReports::get() && !window.is_zero(),
+                AlertQuorum::Fraction(share) => !share.is_zero() && !window.is_zero(),
+            };
+            ensure!(valid, Error::<T>::InvalidQuorum);
+            Quorum::<T>::put(quorum);
+            QuorumWindow::<T>::put(window);
+            Self::deposit_event(Event::QuorumSet(quorum, window));
+            Ok(())
+        }
+
+        /// Only report the escalations that would happen, e.g. while tuning the policies
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::set_escalation_dry_run())]
+        pub fn set_escalation_dry_run(origin: OriginFor<T>, dry_run: bool) -> DispatchResult {
+            // TODO do this via governance
+            ensure_root(origin)?;
+            EscalationDryRun::<T>::put(dry_run);
+            Self::deposit_event(Event::EscalationDryRunSet(dry_run));
+            Ok(())
+        }
+
+        /// Register an alert type monitors may raise.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::register_alert_type())]
+        pub fn register_alert_type(
+            origin: OriginFor<T>,
+            code: AlertType,
+            severity: AlertSeverity,
+            description: AlertDescription,
+            expires_after: Option<BlockNumberFor<T>>,
+            escalation: EscalationPolicy<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can register alert types
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            ensure!(!AlertTypes::<T>::contains_key(&code), Error::<T>::AlertTypeAlreadyExists);
+            Self::ensure_valid_escalation(&escalation, expires_after)?;
+            let alert_type =
+                AlertTypeInfo { severity, description, expires_after, escalation, status: AlertTypeStatus::Active };
+            AlertTypes::<T>::insert(&code, alert_type);
+            Self::deposit_event(Event::AlertTypeRegistered(code));
+            Ok(())
+        }
+
+        /// Change an active alert type. Alerts already raised keep the severity they were raised with.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::WeightInfo::update_alert_type())]
+        pub fn update_alert_type(
+            origin: OriginFor<T>,
+            code: AlertType,
+            severity: AlertSeverity,
+            description: AlertDescription,
+            expires_after: Option<BlockNumberFor<T>>,
+            escalation: EscalationPolicy<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can update alert types
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            Self::ensure_valid_escalation(&escalation, expires_after)?;
+            AlertTypes::<T>::try_mutate(&code, |type_opt| -> DispatchResult {
+                let alert_type = type_opt.as_mut().ok_or(Error::<T>::AlertTypeNotFound)?;
+                ensure!(alert_type.status == AlertTypeStatus::Active, Error::<T>::AlertTypeDeprecated);
+                *alert_type =
+                    AlertTypeInfo { severity, description, expires_after, escalation, status: AlertTypeStatus::Active };
+                Ok(())
+            })?;
+            Self::deposit_event(Event::AlertTypeUpdated(code));
+            Ok(())
+        }
+
+        /// Stop accepting alerts of a type. Alerts already raised are kept until they are resolved.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::WeightInfo::deprecate_alert_type())]
+        pub fn deprecate_alert_type(origin: OriginFor<T>, code: AlertType) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can deprecate alert types
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            AlertTypes::<T>::try_mutate(&code, |type_opt| -> DispatchResult {
+                let alert_type = type_opt.as_mut().ok_or(Error::<T>::AlertTypeNotFound)?;
+                ensure!(alert_type.status == AlertTypeStatus::Active, Error::<T>::AlertTypeDeprecated);
+                alert_type.status = AlertTypeStatus::Deprecated;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::AlertTypeDeprecated(code));
             Ok(())
         }
 
     } // impl<T: Config> Pallet<T>
 
-    use pallet_session::SessionManager;
-    extern crate alloc;
-    use log::info;
-    use alloc::vec::Vec;
-    use frame_support::pallet_prelude::PhantomData;
+    impl<T: Config> Pallet<T> {
+        /// Unix time in milliseconds
+        pub(crate) fn now() -> u64 {
+            T::UnixTime::now().as_millis().saturated_into()
+        }
 
-    /// Session Manager
-    pub struct IbpSessionManager<T>(PhantomData<T>);
+        /// Remove an alert and move it into the member's history
+        fn resolve_alert(key: AlertKey<T>, mut alert: AlertData<T>, resolution_note: ResolutionNote) {
+            Self::remove_alert(&key, &alert);
+            Self::deposit_event(Event::AlertCleared(
+                alert.member_id.clone(),
+                alert.service_id.clone(),
+                alert.domain_id.clone(),
+                alert.alert_type.clone(),
+            ));
+            alert.resolved_at_time = Some(Self::now());
+            alert.resolution_note = Some(resolution_note);
+            Self::record_history(alert);
+        }
 
-    impl<T: Config> SessionManager<T::AccountId> for IbpSessionManager<T> {
+        /// Keep an alert in the member's history, dropping the oldest when it is full
+        fn record_history(alert: AlertData<T>) {
+            AlertHistory::<T>::mutate(alert.member_id.clone(), |history| {
+                if history.is_full() {
+                    history.remove(0);
+                }
+                // cannot fail, there is room for at least one alert
+                let _ = history.try_push(alert);
+            });
+        }
 
-        fn new_session(_new_index: u32) -> Option<Vec<T::AccountId>> {
-            None
+        fn remove_alert(key: &AlertKey<T>, alert: &AlertData<T>) {
+            Alerts::<T>::remove(key);
+            AlertIndex::<T>::remove((alert.member_id.clone(), alert.service_id.clone(), alert.alert_type.clone()));
         }
 
-        fn end_session(_end_index: u32) {
-            // Insert your logic here
+        /// Block at which an unresolved alert expires, by the time to live of its type
+        pub fn expires_at(alert: &AlertData<T>) -> BlockNumberFor<T> {
+            let ttl = AlertTypes::<T>::get(&alert.alert_type)
+                .and_then(|alert_type| alert_type.expires_after)
+                .unwrap_or_else(T::DefaultAlertTtl::get);
+            alert.raised_at.saturating_add(ttl)
+        }
+
+        /// Remove the alerts of monitors no longer assigned, and expire the alerts past their time
+        /// to live, as far as `limit` allows. Expired alerts move into the history marked expired,
+        /// where they still count as repeated alerts. The sweep resumes where it stopped at the next
+        /// call. Returns the weight used.
+        pub(crate) fn sweep_alerts(block_number: BlockNumberFor<T>, limit: Weight) -> Weight {
+            let mut meter = WeightMeter::with_limit(limit);
+            let base = T::WeightInfo::sweep_alerts(0);
+            let per_alert = T::WeightInfo::sweep_alerts(1).saturating_sub(base);
+            if meter.try_consume(base).is_err() {
+                return Weight::zero();
+            }
+            let mut alerts = match AlertSweepCursor::<T>::take() {
+                Some(cursor) => Alerts::<T>::iter_from(cursor),
+                None => Alerts::<T>::iter(),
+            };
+            loop {
+                if meter.try_consume(per_alert).is_err() {
+                    AlertSweepCursor::<T>::put(alerts.last_raw_key().to_vec());
+                    break;
+                }
+                // a full pass is done, the next sweep starts over
+                let Some((key, mut alert)) = alerts.next() else { break };
+                if !MemberPallet::<T>::monitors(&alert.monitor_id) {
+                    Self::remove_alert(&key, &alert);
+                    Self::deposit_event(Event::MonitorAlertRemoved(
+                        alert.monitor_id,
+                        alert.member_id,
+                        alert.service_id,
+                        alert.alert_type,
+                    ));
+                } else if block_number >= Self::expires_at(&alert) {
+                    Self::remove_alert(&key, &alert);
+                    Self::deposit_event(Event::AlertExpired(
+                        alert.member_id.clone(),
+                        alert.service_id.clone(),
+                        alert.domain_id.clone(),
+                        alert.alert_type.clone(),
+                    ));
+                    alert.expired_at_time = Some(Self::now());
+                    Self::record_history(alert);
+                }
+            }
+            meter.consumed()
         }
 
-        fn start_session(_start_index: u32) {
-            info!("New session, recalculating AlertIndex");
+        /// Mean time to acknowledge and to resolve over the resolved alerts of a member
+        pub fn alert_stats(member_id: &T::AccountId) -> AlertStats {
+            let history = AlertHistory::<T>::get(member_id);
+            let mut stats = AlertStats::default();
+            let (mut to_acknowledge, mut to_resolve) = (0u64, 0u64);
+            for alert in history.iter() {
+                // expired alerts are left out, acknowledged or not
+                let Some(resolved_at) = alert.resolved_at_time else { continue };
+                stats.resolved.saturating_inc();
+                to_resolve = to_resolve.saturating_add(resolved_at.saturating_sub(alert.raised_at_time));
+                if let Some(acknowledged_at) = alert.acknowledged_at_time {
+                    stats.acknowledged.saturating_inc();
+                    to_acknowledge =
+                        to_acknowledge.saturating_add(acknowledged_at.saturating_sub(alert.raised_at_time));
+                }
+            }
+            stats.mean_time_to_acknowledge = (stats.acknowledged > 0).then(|| to_acknowledge / stats.acknowledged as u64);
+            stats.mean_time_to_resolve = (stats.resolved > 0).then(|| to_resolve / stats.resolved as u64);
+            stats
+        }
+
+        /// Store an alert that takes effect, under an id its monitor is not using
+        fn store_alert(alert: AlertData<T>) -> DispatchResult {
+            let key: AlertKey<T> = AlertKey { monitor_id: alert.monitor_id.clone(), alert_id: alert.alert_id };
+            ensure!(!Alerts::<T>::contains_key(&key), Error::<T>::AlertIdInUse);
+            Self::schedule_escalation(&key, &alert)?;
+            AlertIndex::<T>::insert((alert.member_id.clone(), alert.service_id.clone(), alert.alert_type.clone()), 1);
+            Self::deposit_event(Event::AlertRegistered(
+                alert.member_id.clone(),
+                alert.service_id.clone(),
+                alert.domain_id.clone(),
+                alert.alert_type.clone(),
+            ));
+            Alerts::<T>::insert(key, alert);
+            Ok(())
+        }
+
+        /// Add a monitor's report to the pending alert, and store the alert once the quorum is
+        /// reached. The alert is kept under the key of the first monitor that reported it.
+        fn report_alert(alert: AlertData<T>, quorum: AlertQuorum) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+            let index = (alert.member_id.clone(), alert.service_id.clone(), alert.alert_type.clone());
+            let mut pending = match PendingAlerts::<T>::get(&index) {
+                Some(pending) => pending,
+                None => {
+                    let expires_at = Self::schedule_pending_expiry(&index, now.saturating_add(QuorumWindow::<T>::get()))?;
+                    PendingAlert { expires_at, reports: BoundedVec::default() }
+                },
+            };
+            ensure!(
+                !pending.reports.iter().any(|report| report.monitor_id == alert.monitor_id),
+                Error::<T>::AlertAlreadyReported
+            );
+            let report = AlertReport {
+                monitor_id: alert.monitor_id.clone(),
+                alert_id: alert.alert_id,
+                domain_id: alert.domain_id.clone(),
+                reported_at: now,
+            };
+            pending.reports.try_push(report).map_err(|_| Error::<T>::TooManyAlertReports)?;
+
+            let reports = pending.reports.len() as u32;
+            let required = Self::required_reports(quorum);
+            if reports < required {
+                PendingAlerts::<T>::insert(&index, pending);
+                Self::deposit_event(Event::AlertPending(index.0, index.1, index.2, reports, required));
+                return Ok(());
+            }
+            PendingAlerts::<T>::remove(&index);
+            PendingAlertExpiries::<T>::mutate(pending.expires_at, |expiring| expiring.retain(|key| *key != index));
+            Self::deposit_event(Event::AlertConfirmed(index.0, index.1, index.2, reports));
+            let first = &pending.reports[0];
+            Self::store_alert(AlertData {
+                monitor_id: first.monitor_id.clone(),
+                alert_id: first.alert_id,
+                domain_id: first.domain_id.clone(),
+                ..alert
+            })
+        }
+
+        /// Queue the expiry of a pending alert in the first block from `from` with room left, at most
+        /// `MAX_PENDING_EXPIRY_DELAY` blocks later. Returns the block it expires in.
+        fn schedule_pending_expiry(
+            index: &(T::AccountId, ServiceId, AlertType),
+            from: BlockNumberFor<T>,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            let max_expiries = T::MaxPendingExpiries::get() as usize;
+            let mut block_number = from;
+            for _ in 0..=MAX_PENDING_EXPIRY_DELAY {
+                if PendingAlertExpiries::<T>::decode_len(block_number).unwrap_or(0) < max_expiries {
+                    PendingAlertExpiries::<T>::try_mutate(block_number, |expiring| expiring.try_push(index.clone()))
+                        .map_err(|_| Error::<T>::TooManyPendingAlerts)?;
+                    return Ok(block_number);
+                }
+                block_number = block_number.saturating_add(One::one());
+            }
+            Err(Error::<T>::TooManyPendingAlerts.into())
+        }
+
+        /// Number of distinct monitors that must report an alert under `quorum`
+        pub fn required_reports(quorum: AlertQuorum) -> u32 {
+            match quorum {
+                AlertQuorum::Single => 1,
+                AlertQuorum::Count(count) => count.max(1),
+                AlertQuorum::Fraction(share) => share.mul_ceil(MemberPallet::<T>::active_monitor_count()).max(1),
+            }
+        }
+
+        /// Drop the pending alerts expiring at `block_number`, returns the weight used
+        pub(crate) fn expire_pending_alerts(block_number: BlockNumberFor<T>) -> Weight {
+            let expiring = PendingAlertExpiries::<T>::take(block_number);
+            let count = expiring.len() as u32;
+            for index in expiring {
+                if let Some(pending) = PendingAlerts::<T>::take(&index) {
+                    let reports = pending.reports.len() as u32;
+                    Self::deposit_event(Event::PendingAlertExpired(index.0, index.1, index.2, reports));
+                }
+            }
+            T::WeightInfo::expire_pending_alerts(count)
+        }
+
+        /// A `ChillAssignment` policy must be reachable with the history kept per member, and the
+        /// alerts of a `LockMember` policy must not expire before they are overdue
+        fn ensure_valid_escalation(
+            escalation: &EscalationPolicy<BlockNumberFor<T>>,
+            expires_after: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let valid = match escalation {
+                EscalationPolicy::None => true,
+                EscalationPolicy::ChillAssignment { count, within } => {
+                    *count > 0 && *count <= T::MaxAlertHistory::get().saturating_add(1) && !within.is_zero()
+                },
+                EscalationPolicy::LockMember { after } => !after.is_zero(),
+            };
+            ensure!(valid, Error::<T>::InvalidEscalationPolicy);
+            // overdue alerts escalate in `on_initialize`, before the sweep of the same block
+            if let EscalationPolicy::LockMember { after } = escalation {
+                let ttl = expires_after.unwrap_or_else(T::DefaultAlertTtl::get);
+                ensure!(ttl >= *after, Error::<T>::AlertExpiresBeforeEscalation);
+            }
+            Ok(())
+        }
+
+        /// Queue the escalation check of an alert taking effect, if its type escalates.
+        /// Repeated alerts are counted from the next block, unresolved alerts once they are overdue.
+        /// The check goes in the first block with room, at most `MAX_ESCALATION_DELAY` blocks later,
+        /// and an unresolved alert is checked before it expires.
+        fn schedule_escalation(key: &AlertKey<T>, alert: &AlertData<T>) -> DispatchResult {
+            let max_delay: BlockNumberFor<T> = MAX_ESCALATION_DELAY.into();
+            let escalation = AlertTypes::<T>::get(&alert.alert_type).map(|alert_type| alert_type.escalation);
+            let (due_at, latest) = match escalation {
+                Some(EscalationPolicy::ChillAssignment { .. }) => {
+                    let due_at = alert.raised_at.saturating_add(One::one());
+                    (due_at, due_at.saturating_add(max_delay))
+                },
+                Some(EscalationPolicy::LockMember { after }) => {
+                    let due_at = alert.raised_at.saturating_add(after);
+                    (due_at, due_at.saturating_add(max_delay).min(Self::expires_at(alert)))
+                },
+                _ => return Ok(()),
+            };
+            let due = DueEscalation {
+                key: key.clone(),
+                member_id: alert.member_id.clone(),
+                service_id: alert.service_id.clone(),
+                alert_type: alert.alert_type.clone(),
+                raised_at: alert.raised_at,
+            };
+            let max_due = T::MaxDueEscalations::get() as usize;
+            let mut block_number = due_at;
+            for _ in 0..=MAX_ESCALATION_DELAY {
+                if block_number > latest {
+                    break;
+                }
+                if DueEscalations::<T>::decode_len(block_number).unwrap_or(0) < max_due {
+                    return DueEscalations::<T>::try_mutate(block_number, |escalations| escalations.try_push(due))
+                        .map_err(|_| Error::<T>::TooManyDueEscalations.into());
+                }
+                block_number = block_number.saturating_add(One::one());
+            }
+            Err(Error::<T>::TooManyDueEscalations.into())
+        }
+
+        /// Check the alerts due at `block_number` against the current policy of their type, and
+        /// lock the member or chill the assignment when they escalate. Returns the weight used,
+        /// the enforcement charged by its upper bound.
+        pub(crate) fn escalate_alerts(block_number: BlockNumberFor<T>) -> Weight {
+            let escalations = DueEscalations::<T>::take(block_number);
+            let mut weight = T::WeightInfo::escalate_alerts(escalations.len() as u32);
+            for due in escalations {
+                let Some(alert_type) = AlertTypes::<T>::get(&due.alert_type) else { continue };
+                let escalates = match alert_type.escalation {
+                    EscalationPolicy::None => false,
+                    EscalationPolicy::ChillAssignment { count, within } => {
+                        Self::repeated_alerts(&due, within) >= count
+                    },
+                    EscalationPolicy::LockMember { after } => {
+                        block_number >= due.raised_at.saturating_add(after)
+                            && Alerts::<T>::get(&due.key).map_or(false, |alert| alert.raised_at == due.raised_at)
+                    },
+                };
+                if escalates {
+                    weight.saturating_accrue(Self::escalate(due, alert_type.escalation));
+                }
+            }
+            weight
+        }
+
+        /// Alerts of the same (member, service, alert type) raised within `within` blocks up to
+        /// the alert due, the alert itself included whether it is open, resolved or expired
+        fn repeated_alerts(due: &DueEscalation<T>, within: BlockNumberFor<T>) -> u32 {
+            let since = due.raised_at.saturating_sub(within);
+            let recorded = AlertHistory::<T>::get(&due.member_id)
+                .iter()
+                .filter(|alert| {
+                    alert.service_id == due.service_id
+                        && alert.alert_type == due.alert_type
+                        && alert.raised_at > since
+                        && alert.raised_at <= due.raised_at
+                })
+                .count() as u32;
+            recorded.saturating_add(Alerts::<T>::contains_key(&due.key) as u32)
+        }
+
+        /// Act on an escalated alert, or only report it in a dry run. Returns the weight of the
+        /// enforcement.
+        fn escalate(due: DueEscalation<T>, policy: EscalationPolicy<BlockNumberFor<T>>) -> Weight {
+            let DueEscalation { member_id, service_id, alert_type, .. } = due;
+            if EscalationDryRun::<T>::get() {
+                Self::deposit_event(Event::AlertEscalationDryRun(member_id, service_id, alert_type, policy));
+                return Weight::zero();
+            }
+            let reason = StatusReason::truncate_from(alert_type.to_vec());
+            let (result, weight) = match policy {
+                EscalationPolicy::None => (Ok(()), Weight::zero()),
+                EscalationPolicy::ChillAssignment { .. } => {
+                    (T::Assignments::chill(&member_id, &service_id, reason), T::Assignments::chill_weight())
+                },
+                EscalationPolicy::LockMember { .. } => {
+                    (T::Members::lock(&member_id, reason), T::Members::lock_weight())
+                },
+            };
+            match result {
+                Ok(()) => Self::deposit_event(Event::AlertEscalated(member_id, service_id, alert_type, policy)),
+                Err(error) => Self::deposit_event(Event::AlertEscalationFailed(member_id, service_id, alert_type, error)),
+            }
+            weight
+        }
+
+        /// Alerts waiting for quorum matching the filter, `monitor_id` matches any reporting monitor
+        pub fn pending_alerts(
+            filter: &AlertFilter<T::AccountId>,
+        ) -> Vec<((T::AccountId, ServiceId, AlertType), PendingAlert<T>)> {
+            PendingAlerts::<T>::iter()
+                .filter(|((member_id, service_id, alert_type), pending)| {
+                    filter.member_id.as_ref().map_or(true, |id| id == member_id)
+                        && filter.service_id.as_ref().map_or(true, |id| id == service_id)
+                        && filter.alert_type.as_ref().map_or(true, |t| t == alert_type)
+                        && filter.monitor_id.as_ref().map_or(true, |id| {
+                            pending.reports.iter().any(|report| report.monitor_id == *id)
+                        })
+                        && filter.domain_id.as_ref().map_or(true, |id| {
+                            pending.reports.iter().any(|report| report.domain_id == *id)
+                        })
+                })
+                .collect()
+        }
+
+        /// All current alerts matching the filter
+        pub fn active_alerts(filter: &AlertFilter<T::AccountId>) -> Vec<AlertData<T>> {
+            Alerts::<T>::iter_values().filter(|alert| alert.matches(filter)).collect()
+        }
+
+        /// Rebuild the `AlertIndex` from the open alerts, run by the runtime at each session start
+        pub fn rebuild_alert_index() {
+            log::info!("New session, recalculating AlertIndex");
             // clear the AlertIndex
-            AlertIndex::<T>::remove_all(None);
+            let _ = AlertIndex::<T>::clear(u32::MAX, None);
             // recalculate the AlertIndex (to be sure...)
             for (_, alert) in Alerts::<T>::iter() {
                 let key = (alert.member_id.clone(), alert.service_id.clone(), alert.alert_type);
                 AlertIndex::<T>::insert(key, 1);
-            };
-            Pallet::<T>::deposit_event(Event::AlertIndexGenerated());
+            }
+            Self::deposit_event(Event::AlertIndexGenerated());
         }
     }
-
 }
@@ -0,0 +1,221 @@
+//! Storage migrations for the alert pallet.
+
+use super::*;
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion, UnixTime},
+    weights::Weight,
+};
+use frame_support::sp_runtime::SaturatedConversion;
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_ibp_service::ServiceId;
+#[cfg(feature = "try-runtime")]
+use frame_support::{ensure, sp_runtime::TryRuntimeError};
+extern crate alloc;
+use alloc::vec::Vec;
+
+pub mod v1 {
+    use super::*;
+
+    /// `AlertData` before v1, without severity, timestamps or resolution
+    #[derive(Encode, Decode)]
+    pub struct OldAlertData<AccountId> {
+        pub monitor_id: AccountId,
+        pub alert_id: u64,
+        pub member_id: AccountId,
+        pub domain_id: DomainId,
+        pub service_id: ServiceId,
+        pub alert_type: AlertType,
+    }
+
+    /// Give the existing alerts the default severity. They are taken as raised at the upgrade,
+    /// the block and time they were raised at were not recorded.
+    pub struct AddAlertLifecycle<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for AddAlertLifecycle<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+            let raised_at: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+            let raised_at_time: u64 = T::UnixTime::now().as_millis().saturated_into();
+            let mut translated = 0u64;
+            Alerts::<T>::translate::<OldAlertData<T::AccountId>, _>(|_key, old| {
+                translated += 1;
+                Some(AlertData {
+                    monitor_id: old.monitor_id,
+                    alert_id: old.alert_id,
+                    member_id: old.member_id,
+                    domain_id: old.domain_id,
+                    service_id: old.service_id,
+                    alert_type: old.alert_type,
+                    severity: AlertSeverity::default(),
+                    raised_at,
+                    raised_at_time,
+                    acknowledged_by: None,
+                    acknowledged_at_time: None,
+                    resolved_at_time: None,
+                    resolution_note: None,
+                    expired_at_time: None,
+                })
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(3 + translated, 1 + translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok((Alerts::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            ensure!(Alerts::<T>::iter_values().count() as u32 == before, "alerts were lost");
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+
+    /// Register every alert type in use, so alerts raised before the registry keep a valid type.
+    /// The types get the default severity and no expiry or escalation, curators review them with
+    /// `update_alert_type`.
+    pub struct RegisterAlertTypes<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for RegisterAlertTypes<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1u64;
+            let mut registered = 0u64;
+            let alert_types = Alerts::<T>::iter_values()
+                .map(|alert| alert.alert_type)
+                .chain(PendingAlerts::<T>::iter_keys().map(|(_, _, alert_type)| alert_type));
+            for code in alert_types {
+                reads += 2;
+                if AlertTypes::<T>::contains_key(&code) {
+                    continue;
+                }
+                let alert_type = AlertTypeInfo {
+                    severity: AlertSeverity::default(),
+                    description: AlertDescription::truncate_from(code.to_vec()),
+                    expires_after: None,
+                    escalation: EscalationPolicy::None,
+                    status: AlertTypeStatus::Active,
+                };
+                AlertTypes::<T>::insert(&code, alert_type);
+                registered += 1;
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, 1 + registered)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "storage version not updated");
+            ensure!(
+                Alerts::<T>::iter_values().all(|alert| AlertTypes::<T>::contains_key(&alert.alert_type)),
+                "alert of an unregistered type"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+
+    /// `AlertData` before v3, without the expiry of the alerts kept in the history
+    #[derive(Encode, Decode)]
+    pub struct OldAlertData<AccountId, BlockNumber> {
+        pub monitor_id: AccountId,
+        pub alert_id: u64,
+        pub member_id: AccountId,
+        pub domain_id: DomainId,
+        pub service_id: ServiceId,
+        pub alert_type: AlertType,
+        pub severity: AlertSeverity,
+        pub raised_at: BlockNumber,
+        pub raised_at_time: u64,
+        pub acknowledged_by: Option<AccountId>,
+        pub acknowledged_at_time: Option<u64>,
+        pub resolved_at_time: Option<u64>,
+        pub resolution_note: Option<ResolutionNote>,
+    }
+
+    type OldAlertDataOf<T> = OldAlertData<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    impl<T: Config> From<OldAlertDataOf<T>> for AlertData<T> {
+        fn from(old: OldAlertDataOf<T>) -> Self {
+            AlertData {
+                monitor_id: old.monitor_id,
+                alert_id: old.alert_id,
+                member_id: old.member_id,
+                domain_id: old.domain_id,
+                service_id: old.service_id,
+                alert_type: old.alert_type,
+                severity: old.severity,
+                raised_at: old.raised_at,
+                raised_at_time: old.raised_at_time,
+                acknowledged_by: old.acknowledged_by,
+                acknowledged_at_time: old.acknowledged_at_time,
+                resolved_at_time: old.resolved_at_time,
+                resolution_note: old.resolution_note,
+                expired_at_time: None,
+            }
+        }
+    }
+
+    /// Add the expiry to the open alerts and the alert histories. The histories only held resolved
+    /// alerts before v3, none of them expired.
+    pub struct AddAlertExpiry<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for AddAlertExpiry<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut translated = 0u64;
+            Alerts::<T>::translate::<OldAlertDataOf<T>, _>(|_key, old| {
+                translated += 1;
+                Some(old.into())
+            });
+            AlertHistory::<T>::translate::<Vec<OldAlertDataOf<T>>, _>(|_member_id, old| {
+                translated += 1;
+                Some(BoundedVec::truncate_from(old.into_iter().map(Into::into).collect()))
+            });
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1 + translated, 1 + translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            let alerts = Alerts::<T>::iter_keys().count() as u32;
+            let histories = AlertHistory::<T>::iter_keys().count() as u32;
+            Ok((alerts, histories).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "storage version not updated");
+            let (alerts, histories) =
+                <(u32, u32)>::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            ensure!(Alerts::<T>::iter_values().count() as u32 == alerts, "alerts were lost");
+            ensure!(AlertHistory::<T>::iter_values().count() as u32 == histories, "alert histories were lost");
+            Ok(())
+        }
+    }
+}
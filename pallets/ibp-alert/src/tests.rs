@@ -1,27 +1,989 @@
-use crate::{mock::*, Error, Event, Something};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, AlertDescription, AlertFilter, AlertHistory, AlertIndex, AlertKey, AlertQuorum,
+	AlertSeverity, AlertStats, AlertSweepCursor, AlertType, AlertTypeInfo, AlertTypeStatus, AlertTypes, Alerts,
+	DueEscalation, DueEscalations, EscalationPolicy, Error, Event, PendingAlertExpiries, PendingAlerts,
+	ResolutionNote, WeightInfo, MAX_ESCALATION_DELAY, MAX_PENDING_EXPIRY_DELAY,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::{DispatchError, Percent};
+use pallet_ibp_member::{MemberStatus, Members};
+use pallet_ibp_service::ChilledAssignments;
+use pallet_ibp_test_utils::{add_member, bounded, domain_id, service_id, setup_service};
+
+const CURATOR: u64 = 1;
+const MEMBER: u64 = 2;
+const MONITOR: u64 = 3;
+const MONITOR_2: u64 = 4;
+const MONITOR_3: u64 = 5;
+
+fn alert_type() -> AlertType {
+	bounded("ServiceDown")
+}
+
+fn description() -> AlertDescription {
+	bounded("The service does not respond")
+}
+
+/// A curator, an active member, a monitor, one active service and the critical `ServiceDown` alert type
+fn new_test_ext_with_service() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		setup_service::<Test>(&CURATOR, &MEMBER, &MONITOR);
+		assert_ok!(IbpAlert::register_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			None,
+			EscalationPolicy::None,
+		));
+	});
+	ext
+}
+
+fn register_alert(alert_id: u64) -> frame_support::dispatch::DispatchResult {
+	IbpAlert::register_alert(
+		RuntimeOrigin::signed(MONITOR),
+		alert_id,
+		MEMBER,
+		service_id(),
+		domain_id(),
+		alert_type(),
+	)
+}
+
+/// Two more monitors, three in all
+fn add_monitors() {
+	for (account_id, name) in [(MONITOR_2, "monitor2"), (MONITOR_3, "monitor3")] {
+		add_member::<Test>(&CURATOR, &account_id, name);
+		assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(CURATOR), account_id));
+	}
+}
+
+fn report_alert(monitor: u64, alert_id: u64) -> frame_support::dispatch::DispatchResult {
+	IbpAlert::register_alert(
+		RuntimeOrigin::signed(monitor),
+		alert_id,
+		MEMBER,
+		service_id(),
+		domain_id(),
+		alert_type(),
+	)
+}
+
+fn note() -> ResolutionNote {
+	bounded("restarted the node")
+}
+
+fn no_filter() -> AlertFilter<u64> {
+	AlertFilter { member_id: None, monitor_id: None, service_id: None, domain_id: None, alert_type: None }
+}
 
 #[test]
-fn it_works_for_default_value() {
-	new_test_ext().execute_with(|| {
+fn register_alert_works() {
+	new_test_ext_with_service().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(Something::<Test>::get(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		assert_ok!(register_alert(1));
+		assert!(AlertIndex::<Test>::contains_key((MEMBER, service_id(), alert_type())));
+		assert_eq!(IbpAlert::active_alerts(&no_filter()).len(), 1);
+		System::assert_last_event(Event::AlertRegistered(MEMBER, service_id(), domain_id(), alert_type()).into());
+	});
+}
+
+#[test]
+fn duplicate_member_service_alert_is_rejected() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(register_alert(1));
+		assert_noop!(register_alert(2), Error::<Test>::MemberServiceAlertExists);
+	});
+}
+
+#[test]
+fn monitor_can_clear_own_alert() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+		assert!(!AlertIndex::<Test>::contains_key((MEMBER, service_id(), alert_type())));
+		System::assert_last_event(Event::AlertCleared(MEMBER, service_id(), domain_id(), alert_type()).into());
+	});
+}
+
+#[test]
+fn only_curators_can_clear_monitor_alerts() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(register_alert(1));
+		assert_noop!(
+			IbpAlert::clear_monitor_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 1, note()),
+			Error::<Test>::NotACurator
+		);
+		assert_ok!(IbpAlert::clear_monitor_alert(RuntimeOrigin::signed(CURATOR), MONITOR, 1, note()));
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+	});
+}
+
+#[test]
+fn rebuild_alert_index_restores_open_alerts() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		let index_key = (MEMBER, service_id(), alert_type());
+		AlertIndex::<Test>::remove(&index_key);
+		AlertIndex::<Test>::insert((MONITOR, service_id(), alert_type()), 1);
+
+		IbpAlert::rebuild_alert_index();
+
+		assert!(AlertIndex::<Test>::contains_key(&index_key));
+		assert_eq!(AlertIndex::<Test>::iter().count(), 1);
+		System::assert_last_event(Event::AlertIndexGenerated().into());
+	});
+}
+
+#[test]
+fn alerts_need_a_registered_domain_service() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_noop!(
+			IbpAlert::register_alert(
+				RuntimeOrigin::signed(MONITOR),
+				1,
+				MEMBER,
+				service_id(),
+				bounded("unknown.network"),
+				alert_type(),
+			),
+			Error::<Test>::UnknownDomainService
+		);
+		assert_ok!(IbpService::retire_domain(RuntimeOrigin::signed(CURATOR), domain_id()));
+		assert_noop!(register_alert(1), Error::<Test>::UnknownDomainService);
+	});
+}
+
+#[test]
+fn quorum_confirms_alert_reported_by_enough_monitors() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		add_monitors();
+		assert_ok!(IbpAlert::set_quorum(RuntimeOrigin::root(), AlertQuorum::Count(2), 5));
+
+		assert_ok!(report_alert(MONITOR, 7));
+		System::assert_last_event(Event::AlertPending(MEMBER, service_id(), alert_type(), 1, 2).into());
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+		assert_eq!(IbpAlert::pending_alerts(&no_filter()).len(), 1);
+		assert_noop!(report_alert(MONITOR, 8), Error::<Test>::AlertAlreadyReported);
+
+		assert_ok!(report_alert(MONITOR_2, 9));
+		System::assert_has_event(Event::AlertConfirmed(MEMBER, service_id(), alert_type(), 2).into());
+		System::assert_last_event(Event::AlertRegistered(MEMBER, service_id(), domain_id(), alert_type()).into());
+		assert!(PendingAlerts::<Test>::iter().next().is_none());
+		// kept under the key of the first monitor
+		let alerts = IbpAlert::active_alerts(&no_filter());
+		assert_eq!((alerts[0].monitor_id, alerts[0].alert_id), (MONITOR, 7));
+		assert_noop!(report_alert(MONITOR_3, 10), Error::<Test>::MemberServiceAlertExists);
+	});
+}
+
+/// Register the `HostDown` alert type and have `monitor` report it under `alert_id`
+fn report_host_down(monitor: u64, alert_id: u64) -> frame_support::dispatch::DispatchResult {
+	if !AlertTypes::<Test>::contains_key(bounded::<AlertType>("HostDown")) {
+		assert_ok!(IbpAlert::register_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			bounded("HostDown"),
+			AlertSeverity::Warning,
+			description(),
+			None,
+			EscalationPolicy::None,
+		));
+	}
+	IbpAlert::register_alert(RuntimeOrigin::signed(monitor), alert_id, MEMBER, service_id(), domain_id(), bounded("HostDown"))
+}
+
+#[test]
+fn alert_ids_are_not_reused() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		assert_noop!(report_host_down(MONITOR, 1), Error::<Test>::AlertIdInUse);
+		assert_ok!(report_host_down(MONITOR, 2));
+		assert_eq!(Alerts::<Test>::get(AlertKey { monitor_id: MONITOR, alert_id: 1 }).unwrap().alert_type, alert_type());
+	});
+}
+
+#[test]
+fn confirmed_alert_needs_a_free_id_of_the_first_monitor() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		add_monitors();
+		assert_ok!(IbpAlert::set_quorum(RuntimeOrigin::root(), AlertQuorum::Count(2), 5));
+		assert_ok!(report_alert(MONITOR, 1));
+		assert_ok!(report_host_down(MONITOR, 1));
+		assert_ok!(report_host_down(MONITOR_2, 2));
+		// the confirmed alert would replace the `HostDown` alert kept under the same key
+		assert_noop!(report_alert(MONITOR_2, 3), Error::<Test>::AlertIdInUse);
+		assert_eq!(
+			Alerts::<Test>::get(AlertKey { monitor_id: MONITOR, alert_id: 1 }).unwrap().alert_type,
+			bounded::<AlertType>("HostDown")
+		);
+	});
+}
+
+#[test]
+fn fraction_quorum_counts_active_monitors() {
+	new_test_ext_with_service().execute_with(|| {
+		add_monitors();
+		let half = AlertQuorum::Fraction(Percent::from_percent(50));
+		// half of three monitors, rounded up
+		assert_eq!(IbpAlert::required_reports(half), 2);
+		assert_ok!(IbpMember::chill_member(RuntimeOrigin::signed(MONITOR_3), Default::default()));
+		assert_eq!(IbpAlert::required_reports(half), 1);
+		assert_ok!(IbpMember::remove_monitor(RuntimeOrigin::signed(CURATOR), MONITOR_2));
+		assert_eq!(IbpAlert::required_reports(AlertQuorum::Fraction(Percent::from_percent(100))), 1);
+	});
+}
+
+#[test]
+fn pending_alert_expires_after_window() {
+	new_test_ext_with_service().execute_with(|| {
+		add_monitors();
+		assert_ok!(IbpAlert::set_quorum(RuntimeOrigin::root(), AlertQuorum::Count(2), 5));
+		System::set_block_number(1);
+		assert_ok!(report_alert(MONITOR, 1));
+
+		System::set_block_number(5);
+		IbpAlert::on_initialize(5);
+		assert_eq!(IbpAlert::pending_alerts(&no_filter()).len(), 1);
+		System::set_block_number(6);
+		IbpAlert::on_initialize(6);
+		assert!(IbpAlert::pending_alerts(&no_filter()).is_empty());
+		System::assert_last_event(Event::PendingAlertExpired(MEMBER, service_id(), alert_type(), 1).into());
+
+		// a late report starts a new window
+		assert_ok!(report_alert(MONITOR_2, 1));
+		System::assert_last_event(Event::AlertPending(MEMBER, service_id(), alert_type(), 1, 2).into());
+	});
+}
+
+/// Fill the pending alert expiries of the blocks `from..=to`
+fn fill_pending_expiries(from: u64, to: u64) {
+	let key = (MONITOR_3, service_id(), alert_type());
+	for expires_at in from..=to {
+		PendingAlertExpiries::<Test>::mutate(expires_at, |expiring| {
+			while !expiring.is_full() {
+				let _ = expiring.try_push(key.clone());
+			}
+		});
+	}
+}
+
+#[test]
+fn pending_alert_expires_in_the_next_block_with_room() {
+	new_test_ext_with_service().execute_with(|| {
+		add_monitors();
+		assert_ok!(IbpAlert::set_quorum(RuntimeOrigin::root(), AlertQuorum::Count(2), 5));
+		System::set_block_number(1);
+		fill_pending_expiries(6, 6);
+		assert_ok!(report_alert(MONITOR, 1));
+		let key = (MEMBER, service_id(), alert_type());
+		assert_eq!(PendingAlerts::<Test>::get(&key).unwrap().expires_at, 7);
+		assert!(PendingAlertExpiries::<Test>::get(7).contains(&key));
+
+		System::set_block_number(7);
+		IbpAlert::on_initialize(7);
+		assert!(IbpAlert::pending_alerts(&no_filter()).is_empty());
+		System::assert_last_event(Event::PendingAlertExpired(MEMBER, service_id(), alert_type(), 1).into());
+
+		// every block up to `MAX_PENDING_EXPIRY_DELAY` later is full
+		fill_pending_expiries(12, 12 + MAX_PENDING_EXPIRY_DELAY as u64);
+		assert_noop!(report_alert(MONITOR, 1), Error::<Test>::TooManyPendingAlerts);
+	});
+}
+
+#[test]
+fn quorum_must_be_reachable() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_noop!(
+			IbpAlert::set_quorum(RuntimeOrigin::signed(CURATOR), AlertQuorum::Count(2), 5),
+			DispatchError::BadOrigin
+		);
+		// MaxAlertReports is 4 in the mock
+		for (quorum, window) in [
+			(AlertQuorum::Count(0), 5),
+			(AlertQuorum::Count(5), 5),
+			(AlertQuorum::Count(2), 0),
+			(AlertQuorum::Fraction(Percent::zero()), 5),
+		] {
+			assert_noop!(IbpAlert::set_quorum(RuntimeOrigin::root(), quorum, window), Error::<Test>::InvalidQuorum);
+		}
+		assert_ok!(IbpAlert::set_quorum(RuntimeOrigin::root(), AlertQuorum::Single, 0));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
-	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+fn member_acknowledges_own_alert() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(register_alert(1));
+		let key = || AlertKey::<Test> { monitor_id: MONITOR, alert_id: 1 };
+		let alert = Alerts::<Test>::get(key()).unwrap();
+		assert_eq!((alert.severity, alert.raised_at, alert.raised_at_time), (AlertSeverity::Critical, 2, 12_000));
+
+		System::set_block_number(5);
+		assert_noop!(IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MONITOR), MONITOR, 1), Error::<Test>::NotAlertMember);
+		assert_noop!(IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 2), Error::<Test>::AlertNotFound);
+		assert_ok!(IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 1));
+		System::assert_last_event(Event::AlertAcknowledged(MEMBER, service_id(), alert_type()).into());
+		let alert = Alerts::<Test>::get(key()).unwrap();
+		assert_eq!((alert.acknowledged_by, alert.acknowledged_at_time), (Some(MEMBER), Some(30_000)));
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 1),
+			Error::<Test>::AlertAlreadyAcknowledged
 		);
 	});
 }
+
+#[test]
+fn resolved_alerts_move_into_history() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_eq!(IbpAlert::alert_stats(&MEMBER), AlertStats::default());
+		// raised at 6s, acknowledged at 12s, resolved at 30s
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		System::set_block_number(2);
+		assert_ok!(IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 1));
+		System::set_block_number(5);
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+		// raised at 30s, never acknowledged, resolved at 90s
+		assert_ok!(register_alert(2));
+		System::set_block_number(15);
+		assert_ok!(IbpAlert::clear_monitor_alert(RuntimeOrigin::signed(CURATOR), MONITOR, 2, note()));
+
+		let history = AlertHistory::<Test>::get(MEMBER);
+		assert_eq!(history.len(), 2);
+		assert_eq!((history[0].resolved_at_time, history[0].resolution_note.clone()), (Some(30_000), Some(note())));
+		assert_eq!(
+			IbpAlert::alert_stats(&MEMBER),
+			AlertStats {
+				resolved: 2,
+				acknowledged: 1,
+				mean_time_to_acknowledge: Some(6_000),
+				mean_time_to_resolve: Some(42_000),
+			}
+		);
+
+		// MaxAlertHistory is 3 in the mock
+		for alert_id in 3..=4 {
+			assert_ok!(register_alert(alert_id));
+			assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), alert_id, note()));
+		}
+		let history = AlertHistory::<Test>::get(MEMBER);
+		assert_eq!(history.iter().map(|alert| alert.alert_id).collect::<Vec<_>>(), vec![2, 3, 4]);
+	});
+}
+
+#[test]
+fn migration_adds_alert_lifecycle() {
+	new_test_ext_with_service().execute_with(|| {
+		StorageVersion::new(0).put::<IbpAlert>();
+		System::set_block_number(3);
+		let key = AlertKey::<Test> { monitor_id: MONITOR, alert_id: 1 };
+		let old = migrations::v1::OldAlertData {
+			monitor_id: MONITOR,
+			alert_id: 1,
+			member_id: MEMBER,
+			domain_id: domain_id(),
+			service_id: service_id(),
+			alert_type: alert_type(),
+		};
+		frame_support::storage::unhashed::put_raw(&Alerts::<Test>::hashed_key_for(&key), &old.encode());
+
+		migrations::v1::AddAlertLifecycle::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpAlert::on_chain_storage_version(), 1);
+		let alert = Alerts::<Test>::get(key).unwrap();
+		assert_eq!((alert.member_id, alert.alert_type), (MEMBER, alert_type()));
+		assert_eq!((alert.severity, alert.raised_at, alert.raised_at_time), (AlertSeverity::Warning, 3, 18_000));
+		assert_eq!(alert.acknowledged_by, None);
+	});
+}
+
+#[test]
+fn migration_adds_alert_expiry() {
+	new_test_ext_with_service().execute_with(|| {
+		StorageVersion::new(2).put::<IbpAlert>();
+		System::set_block_number(3);
+		let old = migrations::v3::OldAlertData {
+			monitor_id: MONITOR,
+			alert_id: 1,
+			member_id: MEMBER,
+			domain_id: domain_id(),
+			service_id: service_id(),
+			alert_type: alert_type(),
+			severity: AlertSeverity::Critical,
+			raised_at: 3,
+			raised_at_time: 18_000,
+			acknowledged_by: None,
+			acknowledged_at_time: None,
+			resolved_at_time: None,
+			resolution_note: None,
+		};
+		let key = AlertKey::<Test> { monitor_id: MONITOR, alert_id: 1 };
+		frame_support::storage::unhashed::put_raw(&Alerts::<Test>::hashed_key_for(&key), &old.encode());
+		let resolved = migrations::v3::OldAlertData {
+			alert_id: 2,
+			resolved_at_time: Some(24_000),
+			resolution_note: Some(note()),
+			..old
+		};
+		frame_support::storage::unhashed::put_raw(
+			&AlertHistory::<Test>::hashed_key_for(MEMBER),
+			&vec![resolved].encode(),
+		);
+
+		migrations::v3::AddAlertExpiry::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpAlert::on_chain_storage_version(), 3);
+		let alert = Alerts::<Test>::get(key).unwrap();
+		assert_eq!((alert.raised_at, alert.expired_at_time), (3, None));
+		let history = AlertHistory::<Test>::get(MEMBER);
+		assert_eq!(history.len(), 1);
+		assert_eq!((history[0].alert_id, history[0].resolved_at_time), (2, Some(24_000)));
+		assert_eq!((history[0].resolution_note.clone(), history[0].expired_at_time), (Some(note()), None));
+	});
+}
+
+#[test]
+fn curators_manage_alert_types() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		let code: AlertType = bounded("HostDown");
+		let escalation = EscalationPolicy::LockMember { after: 100 };
+		assert_noop!(
+			IbpAlert::register_alert_type(
+				RuntimeOrigin::signed(MONITOR),
+				code.clone(),
+				AlertSeverity::Critical,
+				description(),
+				Some(50),
+				escalation.clone(),
+			),
+			Error::<Test>::NotACurator
+		);
+		assert_noop!(
+			IbpAlert::register_alert_type(
+				RuntimeOrigin::signed(CURATOR),
+				alert_type(),
+				AlertSeverity::Info,
+				description(),
+				None,
+				EscalationPolicy::None,
+			),
+			Error::<Test>::AlertTypeAlreadyExists
+		);
+		assert_ok!(IbpAlert::register_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			code.clone(),
+			AlertSeverity::Critical,
+			description(),
+			Some(50),
+			escalation.clone(),
+		));
+		System::assert_last_event(Event::AlertTypeRegistered(code.clone()).into());
+
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			code.clone(),
+			AlertSeverity::Warning,
+			description(),
+			None,
+			escalation.clone(),
+		));
+		assert_eq!(
+			AlertTypes::<Test>::get(&code),
+			Some(AlertTypeInfo {
+				severity: AlertSeverity::Warning,
+				description: description(),
+				expires_after: None,
+				escalation,
+				status: AlertTypeStatus::Active,
+			})
+		);
+
+		assert_ok!(IbpAlert::deprecate_alert_type(RuntimeOrigin::signed(CURATOR), code.clone()));
+		System::assert_last_event(Event::AlertTypeDeprecated(code.clone()).into());
+		assert_noop!(
+			IbpAlert::deprecate_alert_type(RuntimeOrigin::signed(CURATOR), code),
+			Error::<Test>::AlertTypeDeprecated
+		);
+	});
+}
+
+#[test]
+fn alerts_need_a_registered_active_type() {
+	new_test_ext_with_service().execute_with(|| {
+		let register = |alert_id, alert_type: &str| {
+			IbpAlert::register_alert(
+				RuntimeOrigin::signed(MONITOR),
+				alert_id,
+				MEMBER,
+				service_id(),
+				domain_id(),
+				bounded(alert_type),
+			)
+		};
+		// typos do not start a separate alert stream
+		assert_noop!(register(1, "ServiceDwon"), Error::<Test>::AlertTypeNotFound);
+
+		// the alert takes the severity of its type
+		assert_ok!(register(1, "ServiceDown"));
+		let alert = Alerts::<Test>::get(AlertKey::<Test> { monitor_id: MONITOR, alert_id: 1 }).unwrap();
+		assert_eq!(alert.severity, AlertSeverity::Critical);
+
+		// alerts of a deprecated type are kept, no new ones are accepted
+		assert_ok!(IbpAlert::deprecate_alert_type(RuntimeOrigin::signed(CURATOR), alert_type()));
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+		assert_noop!(register(2, "ServiceDown"), Error::<Test>::AlertTypeDeprecated);
+	});
+}
+
+#[test]
+fn migration_registers_alert_types_in_use() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(register_alert(1));
+		let in_use: AlertType = bounded("BlackboxProbeFailed");
+		let mut alert = Alerts::<Test>::get(AlertKey::<Test> { monitor_id: MONITOR, alert_id: 1 }).unwrap();
+		alert.alert_id = 2;
+		alert.alert_type = in_use.clone();
+		Alerts::<Test>::insert(AlertKey::<Test> { monitor_id: MONITOR, alert_id: 2 }, alert);
+		StorageVersion::new(1).put::<IbpAlert>();
+
+		migrations::v2::RegisterAlertTypes::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpAlert::on_chain_storage_version(), 2);
+		// registered types are kept as they are
+		assert_eq!(AlertTypes::<Test>::get(alert_type()).unwrap().severity, AlertSeverity::Critical);
+		assert_eq!(
+			AlertTypes::<Test>::get(&in_use),
+			Some(AlertTypeInfo {
+				severity: AlertSeverity::Warning,
+				description: bounded("BlackboxProbeFailed"),
+				expires_after: None,
+				escalation: EscalationPolicy::None,
+				status: AlertTypeStatus::Active,
+			})
+		);
+	});
+}
+
+fn set_escalation(escalation: EscalationPolicy<u64>) {
+	assert_ok!(IbpAlert::update_alert_type(
+		RuntimeOrigin::signed(CURATOR),
+		alert_type(),
+		AlertSeverity::Critical,
+		description(),
+		None,
+		escalation,
+	));
+}
+
+fn run_to_block(block_number: u64) {
+	System::set_block_number(block_number);
+	IbpAlert::on_initialize(block_number);
+}
+
+#[test]
+fn repeated_alerts_chill_the_assignment() {
+	new_test_ext_with_service().execute_with(|| {
+		let policy = EscalationPolicy::ChillAssignment { count: 3, within: 10 };
+		set_escalation(policy.clone());
+		// two alerts resolved right away, then one left open
+		for alert_id in 1..=2 {
+			run_to_block(alert_id);
+			assert_ok!(register_alert(alert_id));
+			assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), alert_id, note()));
+		}
+		run_to_block(3);
+		assert_eq!(IbpService::assigned_services(&MEMBER), vec![service_id()]);
+		assert_ok!(register_alert(3));
+
+		run_to_block(4);
+		assert!(IbpService::assigned_services(&MEMBER).is_empty());
+		assert!(ChilledAssignments::<Test>::contains_key(MEMBER, service_id()));
+		System::assert_last_event(Event::AlertEscalated(MEMBER, service_id(), alert_type(), policy).into());
+	});
+}
+
+#[test]
+fn alerts_outside_the_window_are_not_counted() {
+	new_test_ext_with_service().execute_with(|| {
+		set_escalation(EscalationPolicy::ChillAssignment { count: 2, within: 3 });
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+		run_to_block(4);
+		assert_ok!(register_alert(2));
+
+		run_to_block(5);
+		assert_eq!(IbpService::assigned_services(&MEMBER), vec![service_id()]);
+	});
+}
+
+#[test]
+fn expired_alerts_are_counted() {
+	new_test_ext_with_service().execute_with(|| {
+		let policy = EscalationPolicy::ChillAssignment { count: 2, within: 10 };
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			Some(2),
+			policy.clone(),
+		));
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+		run_to_block(2);
+		IbpAlert::on_idle(3, Weight::MAX);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+
+		run_to_block(4);
+		assert_ok!(register_alert(2));
+		run_to_block(5);
+		assert!(ChilledAssignments::<Test>::contains_key(MEMBER, service_id()));
+		System::assert_last_event(Event::AlertEscalated(MEMBER, service_id(), alert_type(), policy).into());
+	});
+}
+
+#[test]
+fn unresolved_alert_locks_the_member() {
+	new_test_ext_with_service().execute_with(|| {
+		let policy = EscalationPolicy::LockMember { after: 10 };
+		set_escalation(policy.clone());
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+
+		run_to_block(10);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Active);
+		run_to_block(11);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Locked);
+		System::assert_last_event(Event::AlertEscalated(MEMBER, service_id(), alert_type(), policy).into());
+	});
+}
+
+/// Fill the escalation checks of the blocks `from..=to`
+fn fill_due_escalations(from: u64, to: u64) {
+	let due = DueEscalation::<Test> {
+		key: AlertKey { monitor_id: MONITOR_2, alert_id: 0 },
+		member_id: MONITOR_3,
+		service_id: service_id(),
+		alert_type: alert_type(),
+		raised_at: 0,
+	};
+	for due_at in from..=to {
+		DueEscalations::<Test>::mutate(due_at, |escalations| {
+			while !escalations.is_full() {
+				let _ = escalations.try_push(due.clone());
+			}
+		});
+	}
+}
+
+#[test]
+fn escalation_goes_in_the_next_block_with_room() {
+	new_test_ext_with_service().execute_with(|| {
+		set_escalation(EscalationPolicy::LockMember { after: 10 });
+		run_to_block(1);
+		fill_due_escalations(11, 11);
+		assert_ok!(register_alert(1));
+		assert_eq!(DueEscalations::<Test>::get(12)[0].key, AlertKey { monitor_id: MONITOR, alert_id: 1 });
+
+		run_to_block(11);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Active);
+		run_to_block(12);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Locked);
+	});
+}
+
+#[test]
+fn escalation_needs_room_within_the_delay() {
+	new_test_ext_with_service().execute_with(|| {
+		set_escalation(EscalationPolicy::LockMember { after: 10 });
+		run_to_block(1);
+		fill_due_escalations(11, 11 + MAX_ESCALATION_DELAY as u64);
+		assert_noop!(register_alert(1), Error::<Test>::TooManyDueEscalations);
+	});
+}
+
+#[test]
+fn escalation_is_not_pushed_past_the_alert_expiry() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			Some(11),
+			EscalationPolicy::LockMember { after: 10 },
+		));
+		run_to_block(1);
+		// the alert would expire at block 12 before it is checked
+		fill_due_escalations(11, 12);
+		assert_noop!(register_alert(1), Error::<Test>::TooManyDueEscalations);
+	});
+}
+
+#[test]
+fn resolved_alert_does_not_escalate() {
+	new_test_ext_with_service().execute_with(|| {
+		set_escalation(EscalationPolicy::LockMember { after: 10 });
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+		run_to_block(5);
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+
+		run_to_block(11);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Active);
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::IbpAlert(Event::AlertEscalated(..)))));
+	});
+}
+
+#[test]
+fn escalation_dry_run_only_reports() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_noop!(IbpAlert::set_escalation_dry_run(RuntimeOrigin::signed(CURATOR), true), DispatchError::BadOrigin);
+		assert_ok!(IbpAlert::set_escalation_dry_run(RuntimeOrigin::root(), true));
+		assert!(IbpAlert::escalation_dry_run());
+
+		let lock = EscalationPolicy::LockMember { after: 1 };
+		set_escalation(lock.clone());
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+		run_to_block(2);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Active);
+		System::assert_last_event(Event::AlertEscalationDryRun(MEMBER, service_id(), alert_type(), lock).into());
+
+		let chill = EscalationPolicy::ChillAssignment { count: 1, within: 1 };
+		set_escalation(chill.clone());
+		assert_ok!(IbpAlert::clear_alert(RuntimeOrigin::signed(MONITOR), 1, note()));
+		assert_ok!(register_alert(2));
+		run_to_block(3);
+		assert_eq!(IbpService::assigned_services(&MEMBER), vec![service_id()]);
+		System::assert_last_event(Event::AlertEscalationDryRun(MEMBER, service_id(), alert_type(), chill).into());
+	});
+}
+
+#[test]
+fn failed_escalation_is_reported() {
+	new_test_ext_with_service().execute_with(|| {
+		set_escalation(EscalationPolicy::LockMember { after: 2 });
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+		assert_ok!(IbpMember::lock_member(RuntimeOrigin::signed(CURATOR), MEMBER, Default::default()));
+
+		run_to_block(3);
+		System::assert_last_event(
+			Event::AlertEscalationFailed(
+				MEMBER,
+				service_id(),
+				alert_type(),
+				pallet_ibp_member::Error::<Test>::InvalidStatusTransition.into(),
+			)
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn escalation_policies_are_validated() {
+	new_test_ext_with_service().execute_with(|| {
+		// `MaxAlertHistory` is 3, four alerts can be counted with the one raised
+		for escalation in [
+			EscalationPolicy::ChillAssignment { count: 0, within: 10 },
+			EscalationPolicy::ChillAssignment { count: 5, within: 10 },
+			EscalationPolicy::ChillAssignment { count: 2, within: 0 },
+			EscalationPolicy::LockMember { after: 0 },
+		] {
+			assert_noop!(
+				IbpAlert::update_alert_type(
+					RuntimeOrigin::signed(CURATOR),
+					alert_type(),
+					AlertSeverity::Critical,
+					description(),
+					None,
+					escalation.clone(),
+				),
+				Error::<Test>::InvalidEscalationPolicy
+			);
+			assert_noop!(
+				IbpAlert::register_alert_type(
+					RuntimeOrigin::signed(CURATOR),
+					bounded("HostDown"),
+					AlertSeverity::Critical,
+					description(),
+					None,
+					escalation,
+				),
+				Error::<Test>::InvalidEscalationPolicy
+			);
+		}
+		set_escalation(EscalationPolicy::ChillAssignment { count: 4, within: 10 });
+
+		// alerts must not expire before they lock the member, `DefaultAlertTtl` is 100
+		for (expires_after, after) in [(Some(5), 10), (None, 101)] {
+			let escalation = EscalationPolicy::LockMember { after };
+			assert_noop!(
+				IbpAlert::update_alert_type(
+					RuntimeOrigin::signed(CURATOR),
+					alert_type(),
+					AlertSeverity::Critical,
+					description(),
+					expires_after,
+					escalation.clone(),
+				),
+				Error::<Test>::AlertExpiresBeforeEscalation
+			);
+			assert_noop!(
+				IbpAlert::register_alert_type(
+					RuntimeOrigin::signed(CURATOR),
+					bounded("HostDown"),
+					AlertSeverity::Critical,
+					description(),
+					expires_after,
+					escalation,
+				),
+				Error::<Test>::AlertExpiresBeforeEscalation
+			);
+		}
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			Some(10),
+			EscalationPolicy::LockMember { after: 10 },
+		));
+		set_escalation(EscalationPolicy::LockMember { after: 100 });
+	});
+}
+
+#[test]
+fn alert_expiring_when_overdue_locks_the_member() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			Some(10),
+			EscalationPolicy::LockMember { after: 10 },
+		));
+		run_to_block(1);
+		assert_ok!(register_alert(1));
+
+		run_to_block(11);
+		IbpAlert::on_idle(11, Weight::MAX);
+		assert_eq!(Members::<Test>::get(MEMBER).status, MemberStatus::Locked);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+	});
+}
+
+#[test]
+fn alerts_expire_after_their_ttl() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(IbpAlert::update_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			alert_type(),
+			AlertSeverity::Critical,
+			description(),
+			Some(5),
+			EscalationPolicy::None,
+		));
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		assert_ok!(IbpAlert::acknowledge_alert(RuntimeOrigin::signed(MEMBER), MONITOR, 1));
+
+		IbpAlert::on_idle(5, Weight::MAX);
+		assert_eq!(IbpAlert::active_alerts(&no_filter()).len(), 1);
+		IbpAlert::on_idle(6, Weight::MAX);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+		assert!(!AlertIndex::<Test>::contains_key((MEMBER, service_id(), alert_type())));
+		// expired alerts move into the history unresolved, marked expired
+		let history = AlertHistory::<Test>::get(MEMBER);
+		assert_eq!(history.len(), 1);
+		assert_eq!((history[0].resolved_at_time, history[0].expired_at_time), (None, Some(6_000)));
+		// only resolved alerts count towards the response times, the acknowledgement included
+		assert_eq!(history[0].acknowledged_at_time, Some(6_000));
+		assert_eq!(IbpAlert::alert_stats(&MEMBER), AlertStats::default());
+		System::assert_last_event(Event::AlertExpired(MEMBER, service_id(), domain_id(), alert_type()).into());
+		assert_ok!(register_alert(2));
+	});
+}
+
+#[test]
+fn alerts_expire_after_default_ttl() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+
+		IbpAlert::on_idle(100, Weight::MAX);
+		assert_eq!(IbpAlert::active_alerts(&no_filter()).len(), 1);
+		IbpAlert::on_idle(101, Weight::MAX);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+	});
+}
+
+#[test]
+fn sweep_resumes_within_weight_limit() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_ok!(IbpAlert::register_alert_type(
+			RuntimeOrigin::signed(CURATOR),
+			bounded("HostDown"),
+			AlertSeverity::Critical,
+			description(),
+			None,
+			EscalationPolicy::None,
+		));
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		assert_ok!(IbpAlert::register_alert(
+			RuntimeOrigin::signed(MONITOR),
+			2,
+			MEMBER,
+			service_id(),
+			domain_id(),
+			bounded("HostDown"),
+		));
+
+		// room for a single alert per sweep
+		let limit = <() as WeightInfo>::sweep_alerts(1);
+		assert_eq!(IbpAlert::on_idle(101, Weight::zero()), Weight::zero());
+		assert!(IbpAlert::on_idle(101, limit).all_lte(limit));
+		assert_eq!(IbpAlert::active_alerts(&no_filter()).len(), 1);
+		assert!(AlertSweepCursor::<Test>::get().is_some());
+		IbpAlert::on_idle(102, limit);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+	});
+}
+
+#[test]
+fn alerts_of_removed_monitors_are_removed() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(register_alert(1));
+		IbpAlert::on_idle(2, Weight::MAX);
+		assert_eq!(IbpAlert::active_alerts(&no_filter()).len(), 1);
+
+		assert_ok!(IbpMember::remove_monitor(RuntimeOrigin::signed(CURATOR), MONITOR));
+		IbpAlert::on_idle(3, Weight::MAX);
+		assert!(IbpAlert::active_alerts(&no_filter()).is_empty());
+		assert!(!AlertIndex::<Test>::contains_key((MEMBER, service_id(), alert_type())));
+		System::assert_last_event(Event::MonitorAlertRemoved(MONITOR, MEMBER, service_id(), alert_type()).into());
+	});
+}
@@ -1,29 +1,28 @@
 
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_ibp_alert
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS.
+//! Storage access counts follow the dispatchables; execution times are conservative placeholders.
+//! Regenerate on reference hardware before relying on them, with `scripts/benchmark-weights.sh`,
+//! which builds with `--features runtime-benchmarks` and runs for each IBP pallet:
 
-// Executed Command:
-// ../../target/release/node-template
+// Command:
+// ./target/release/ibp-node
 // benchmark
 // pallet
 // --chain
 // dev
 // --pallet
-// pallet_template
+// pallet_ibp_alert
 // --extrinsic
 // *
 // --steps=50
 // --repeat=20
 // --wasm-execution=compiled
 // --output
-// pallets/template/src/weights.rs
+// pallets/ibp-alert/src/weights.rs
 // --template
-// ../../.maintain/frame-weight-template.hbs
+// ./.maintain/frame-weight-template.hbs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -32,67 +31,281 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_ibp_alert.
 pub trait WeightInfo {
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+	fn register_alert(m: u32, ) -> Weight;
+	fn clear_alert() -> Weight;
+	fn clear_monitor_alert() -> Weight;
+	fn acknowledge_alert() -> Weight;
+	fn set_quorum() -> Weight;
+	fn set_escalation_dry_run() -> Weight;
+	fn register_alert_type() -> Weight;
+	fn update_alert_type() -> Weight;
+	fn deprecate_alert_type() -> Weight;
+	fn expire_pending_alerts(n: u32, ) -> Weight;
+	fn escalate_alerts(n: u32, ) -> Weight;
+	fn sweep_alerts(n: u32, ) -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_ibp_alert using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// fn register_member() -> Weight {
-	// 	// Proof Size summary in bytes:
-	// 	//  Measured:  `0`
-	// 	//  Estimated: `0`
-	// 	// Minimum execution time: 8_000_000 picoseconds.
-	// 	Weight::from_parts(10_000, 0)
-	// 		.saturating_add(T::DbWeight::get().writes(1_u64))
-	// }
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:0)
+	/// Storage: IbpAlert AlertIndex (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert Quorum (r:1 w:0)
+	/// Storage: IbpAlert PendingAlerts (r:1 w:1)
+	/// Storage: IbpAlert PendingAlertExpiries (r:17 w:1)
+	/// Storage: IbpAlert DueEscalations (r:17 w:1)
+	/// Storage: IbpAlert Alerts (r:2 w:1)
+	/// Worst case: a `Fraction` quorum of `m` reports is reached
+	/// The range of component `m` is `[1, 16]`.
+	fn register_alert(m: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 3800)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(47_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(m.into()))
+	}
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert AlertHistory (r:1 w:1), worst case with a full history of `MaxAlertHistory` alerts
+	fn clear_alert() -> Weight {
+		Weight::from_parts(35_000_000, 60000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: IbpAlert AlertIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert AlertHistory (r:1 w:1), worst case with a full history of `MaxAlertHistory` alerts
+	fn clear_monitor_alert() -> Weight {
+		Weight::from_parts(35_000_000, 60000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn acknowledge_alert() -> Weight {
+		Weight::from_parts(20_000_000, 3800)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpAlert Quorum (r:0 w:1)
+	/// Storage: IbpAlert QuorumWindow (r:0 w:1)
+	fn set_quorum() -> Weight {
 		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpAlert EscalationDryRun (r:0 w:1)
+	fn set_escalation_dry_run() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn register_alert_type() -> Weight {
+		Weight::from_parts(16_000_000, 3700)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn update_alert_type() -> Weight {
+		Weight::from_parts(17_000_000, 3700)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn deprecate_alert_type() -> Weight {
+		Weight::from_parts(16_000_000, 3700)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpAlert PendingAlertExpiries (r:1 w:1)
+	/// Storage: IbpAlert PendingAlerts (r:n w:n)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_pending_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1600)
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(n.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3000).saturating_mul(n.into()))
+	}
+	/// Storage: IbpAlert DueEscalations (r:1 w:1)
+	/// Storage: IbpAlert EscalationDryRun (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:n w:0)
+	/// Storage: IbpAlert Alerts (r:n w:0)
+	/// Storage: IbpAlert AlertHistory (r:n w:0)
+	/// Checks only, the lock or chill of an escalation is charged by its enforcement
+	/// The range of component `n` is `[0, 16]`.
+	fn escalate_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 24000).saturating_mul(n.into()))
+	}
+	/// Storage: IbpAlert AlertSweepCursor (r:1 w:1)
+	/// Storage: IbpAlert Alerts (r:n w:n)
+	/// Storage: IbpMember Monitors (r:n w:0)
+	/// Storage: IbpAlert AlertTypes (r:n w:0)
+	/// Storage: IbpAlert AlertIndex (r:0 w:n)
+	/// Storage: IbpAlert AlertHistory (r:n w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Worst case: every alert visited has expired and the history is full
+	/// The range of component `n` is `[1, 100]`.
+	fn sweep_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1600)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 24000).saturating_mul(n.into()))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:0)
+	/// Storage: IbpAlert AlertIndex (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert Quorum (r:1 w:0)
+	/// Storage: IbpAlert PendingAlerts (r:1 w:1)
+	/// Storage: IbpAlert PendingAlertExpiries (r:17 w:1)
+	/// Storage: IbpAlert DueEscalations (r:17 w:1)
+	/// Storage: IbpAlert Alerts (r:2 w:1)
+	/// Worst case: a `Fraction` quorum of `m` reports is reached
+	/// The range of component `m` is `[1, 16]`.
+	fn register_alert(m: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 3800)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(47_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(m.into()))
+	}
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert AlertHistory (r:1 w:1), worst case with a full history of `MaxAlertHistory` alerts
+	fn clear_alert() -> Weight {
+		Weight::from_parts(35_000_000, 60000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: IbpAlert AlertIndex (r:0 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: IbpAlert AlertHistory (r:1 w:1), worst case with a full history of `MaxAlertHistory` alerts
+	fn clear_monitor_alert() -> Weight {
+		Weight::from_parts(35_000_000, 60000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpAlert Alerts (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	fn acknowledge_alert() -> Weight {
+		Weight::from_parts(20_000_000, 3800)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpAlert Quorum (r:0 w:1)
+	/// Storage: IbpAlert QuorumWindow (r:0 w:1)
+	fn set_quorum() -> Weight {
 		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpAlert EscalationDryRun (r:0 w:1)
+	fn set_escalation_dry_run() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn register_alert_type() -> Weight {
+		Weight::from_parts(16_000_000, 3700)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn update_alert_type() -> Weight {
+		Weight::from_parts(17_000_000, 3700)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:1 w:1)
+	fn deprecate_alert_type() -> Weight {
+		Weight::from_parts(16_000_000, 3700)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpAlert PendingAlertExpiries (r:1 w:1)
+	/// Storage: IbpAlert PendingAlerts (r:n w:n)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_pending_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1600)
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(n.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3000).saturating_mul(n.into()))
+	}
+	/// Storage: IbpAlert DueEscalations (r:1 w:1)
+	/// Storage: IbpAlert EscalationDryRun (r:1 w:0)
+	/// Storage: IbpAlert AlertTypes (r:n w:0)
+	/// Storage: IbpAlert Alerts (r:n w:0)
+	/// Storage: IbpAlert AlertHistory (r:n w:0)
+	/// Checks only, the lock or chill of an escalation is charged by its enforcement
+	/// The range of component `n` is `[0, 16]`.
+	fn escalate_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 24000).saturating_mul(n.into()))
+	}
+	/// Storage: IbpAlert AlertSweepCursor (r:1 w:1)
+	/// Storage: IbpAlert Alerts (r:n w:n)
+	/// Storage: IbpMember Monitors (r:n w:0)
+	/// Storage: IbpAlert AlertTypes (r:n w:0)
+	/// Storage: IbpAlert AlertIndex (r:0 w:n)
+	/// Storage: IbpAlert AlertHistory (r:n w:n)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Worst case: every alert visited has expired and the history is full
+	/// The range of component `n` is `[1, 100]`.
+	fn sweep_alerts(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1600)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 24000).saturating_mul(n.into()))
 	}
 }
@@ -1,35 +1,193 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-ibp-check
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as IbpCheck;
 use frame_benchmarking::v2::*;
+use frame_system::pallet_prelude::BlockNumberFor;
 use frame_system::RawOrigin;
+use pallet_ibp_member::{MemberData, MemberStatus, MembershipLevel, Members};
+use pallet_ibp_service::{ChainRelation, DomainOwner, ServiceId, ServiceStatus, ServiceType};
+use frame_support::{
+	sp_runtime::traits::{One, Saturating},
+	BoundedVec,
+};
+extern crate alloc;
+use alloc::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn bounded<B: TryFrom<Vec<u8>>>(value: &[u8]) -> B {
+	B::try_from(value.to_vec()).ok().expect("value fits in 32 bytes")
+}
+
+fn add_member<T: Config>(account_id: &T::AccountId, name: &[u8]) {
+	let member_data = MemberData::<T> {
+		id: Some(account_id.clone()),
+		name: bounded(name),
+		status: MemberStatus::Active,
+		level: MembershipLevel::One,
+	};
+	Members::<T>::insert(account_id, member_data);
+}
+
+/// A monitor, a member and a service the monitor can check
+fn setup<T: Config>() -> (T::AccountId, T::AccountId, ServiceId) {
+	let curator: T::AccountId = account("curator", 0, SEED);
+	MemberPallet::<T>::assign_curator(RawOrigin::Root.into(), curator.clone()).expect("curator is assigned");
+
+	let monitor: T::AccountId = whitelisted_caller();
+	add_member::<T>(&monitor, b"monitor");
+	MemberPallet::<T>::assign_monitor(RawOrigin::Signed(curator.clone()).into(), monitor.clone())
+		.expect("monitor is assigned");
+
+	let member_id: T::AccountId = account("member", 0, SEED);
+	add_member::<T>(&member_id, b"member");
+
+	ServicePallet::<T>::register_chain(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"polkadot"),
+		[1u8; 32],
+		ChainRelation::Relay,
+		0,
+		bounded(b"DOT"),
+	)
+	.expect("chain is registered");
+	let service_id: ServiceId = bounded(b"polkadot-rpc");
+	ServicePallet::<T>::register_service(
+		RawOrigin::Signed(curator.clone()).into(),
+		service_id.clone(),
+		bounded(b"polkadot"),
+		ServiceType::RPC,
+		MembershipLevel::One,
+		ServiceStatus::Active,
+	)
+	.expect("service is registered");
+	ServicePallet::<T>::register_domain(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"example.com"),
+		DomainOwner::Programme,
+	)
+	.expect("domain is registered");
+	ServicePallet::<T>::set_domain_service(
+		RawOrigin::Signed(curator.clone()).into(),
+		bounded(b"example.com"),
+		service_id.clone(),
+		bounded(b"rpc.example.com"),
+	)
+	.expect("domain service is set");
+	(monitor, member_id, service_id)
+}
+
+/// Fill the (member, service) history with checks made at `block_number`
+fn fill_history<T: Config>(
+	monitor: &T::AccountId,
+	member_id: &T::AccountId,
+	service_id: &ServiceId,
+	block_number: BlockNumberFor<T>,
+) {
+	let check = HealthCheckData::<T> {
+		monitor_id: monitor.clone(),
+		block_number,
+		member_id: member_id.clone(),
+		domain_id: bounded(b"example.com"),
+		service_id: service_id.clone(),
+		check_type: HealthCheckType::RPC,
+		level: MembershipLevel::One,
+		status: HealthCheckStatus::Active,
+	};
+	Checks::<T>::mutate((member_id.clone(), service_id.clone()), |history| {
+		while !history.is_full() {
+			let _ = history.try_push(check.clone());
+		}
+	});
+}
+
+/// Fill the (member, service) rollups with epochs before the current one
+fn fill_rollups<T: Config>(member_id: &T::AccountId, service_id: &ServiceId) {
+	Rollups::<T>::mutate((member_id.clone(), service_id.clone()), |rollups| {
+		while !rollups.is_full() {
+			let mut rollup = UptimeRollup::new(rollups.len() as EpochIndex);
+			rollup.record(&HealthCheckStatus::Active, Some(100));
+			let _ = rollups.try_push(rollup);
+		}
+	});
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
+	/// Worst case: the histories are full, the oldest check and epoch are dropped
+	#[benchmark]
+	fn register_health_check() {
+		let (monitor, member_id, service_id) = setup::<T>();
+		let now = T::EpochLength::get().saturating_mul(T::MaxRollupEpochs::get().into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		fill_history::<T>(&monitor, &member_id, &service_id, now);
+		fill_rollups::<T>(&member_id, &service_id);
+		#[extrinsic_call]
+		register_health_check(
+			RawOrigin::Signed(monitor),
+			member_id.clone(),
+			service_id.clone(),
+			bounded(b"example.com"),
+			HealthCheckType::RPC,
+			HealthCheckStatus::Error,
+			None,
+		);
+
+		let latest = IbpCheck::<T>::latest_check(&member_id, &service_id).expect("check is stored");
+		assert_eq!(latest.status, HealthCheckStatus::Error);
+	}
+
+	/// `n` checks of distinct members, worst case: the histories are full, the oldest check and
+	/// epoch are dropped
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn register_health_checks(n: Linear<1, { T::MaxChecksPerBatch::get() }>) {
+		let (monitor, _, service_id) = setup::<T>();
+		let now = T::EpochLength::get().saturating_mul(T::MaxRollupEpochs::get().into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let mut checks = Vec::new();
+		for index in 0..n {
+			let member_id: T::AccountId = account("member", index + 1, SEED);
+			let mut name = b"member".to_vec();
+			name.extend_from_slice(&index.to_be_bytes());
+			add_member::<T>(&member_id, &name);
+			fill_history::<T>(&monitor, &member_id, &service_id, now);
+			fill_rollups::<T>(&member_id, &service_id);
+			checks.push(HealthCheckReport::<T> {
+				member_id,
+				service_id: service_id.clone(),
+				domain_id: bounded(b"example.com"),
+				check_type: HealthCheckType::RPC,
+				status: HealthCheckStatus::Error,
+				latency_ms: None,
+			});
+		}
+		let checks: BoundedVec<_, T::MaxChecksPerBatch> = checks.try_into().ok().expect("checks fit in a batch");
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		register_health_checks(RawOrigin::Signed(monitor), checks);
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		let member_id: T::AccountId = account("member", n, SEED);
+		let latest = IbpCheck::<T>::latest_check(&member_id, &service_id).expect("check is stored");
+		assert_eq!(latest.status, HealthCheckStatus::Error);
 	}
 
+	/// Worst case: the history is full and every check has expired
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn prune_health_checks() {
+		let (monitor, member_id, service_id) = setup::<T>();
+		let start = frame_system::Pallet::<T>::block_number();
+		fill_history::<T>(&monitor, &member_id, &service_id, start);
+		let expired = start.saturating_add(T::CheckRetentionPeriod::get()).saturating_add(One::one());
+		frame_system::Pallet::<T>::set_block_number(expired);
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		prune_health_checks(RawOrigin::Signed(monitor), member_id.clone(), service_id.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!Checks::<T>::contains_key((member_id, service_id)));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(IbpCheck, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -5,10 +5,9 @@
 
 use codec::{ Decode, Encode, MaxEncodedLen };
 use scale_info::{self, TypeInfo};
-use frame_support::{
-    traits::ConstU32,
-    BoundedVec,
-};
+use serde::{Deserialize, Serialize};
+use frame_support::traits::Get;
+use sp_runtime::{traits::Saturating, Perbill, Percent};
 
 pub use pallet::*;
 
@@ -29,26 +28,111 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 pub use weights::*;
+pub mod offchain;
 
 use pallet_ibp_member::Pallet as MemberPallet;
 use pallet_ibp_service::Pallet as ServicePallet;
 
 // pub type ServiceId = BoundedVec<u8, ConstU32<32>>;
-pub type DomainId = BoundedVec<u8, ConstU32<32>>;
+/// IBP domains are registered in `pallet-ibp-service`
+pub use pallet_ibp_service::DomainId;
 
-#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum HealthCheckType {
     RPC = 0,
     BOOT = 1,
 }
 
-#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum HealthCheckStatus {
     Error = 0,
     Active = 1,
     Chilled = 2,
 }
 
+/// Index of a rollup epoch, the block number divided by `EpochLength`
+pub type EpochIndex = u32;
+
+/// Upper bounds of the latency buckets of a rollup, in milliseconds. A probe makes three
+/// requests of up to `offchain::PROBE_TIMEOUT_MS` each, slower probes count in the last bucket.
+pub const LATENCY_BUCKETS_MS: [u32; 8] = [50, 100, 250, 500, 1_000, 2_000, 4_000, 9_000];
+
+/// Health checks of a (member, service) pair folded into one epoch
+#[derive(Clone, Debug, Default, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct UptimeRollup {
+    pub epoch: EpochIndex,
+    /// Checks that found the service up
+    pub up: u32,
+    /// Checks that found the service down
+    pub down: u32,
+    /// All checks, including checks of chilled services that are neither up nor down
+    pub total: u32,
+    /// Number of checks per bucket of `LATENCY_BUCKETS_MS`, for checks that reported a latency
+    pub latency_histogram: [u32; 8],
+}
+
+impl UptimeRollup {
+    /// An empty rollup for `epoch`
+    pub fn new(epoch: EpochIndex) -> Self {
+        Self { epoch, ..Default::default() }
+    }
+
+    /// Fold a check into the rollup
+    pub fn record(&mut self, status: &HealthCheckStatus, latency_ms: Option<u32>) {
+        match status {
+            HealthCheckStatus::Active => self.up.saturating_inc(),
+            HealthCheckStatus::Error => self.down.saturating_inc(),
+            HealthCheckStatus::Chilled => {},
+        }
+        self.total.saturating_inc();
+        if let Some(latency_ms) = latency_ms {
+            let bucket = LATENCY_BUCKETS_MS
+                .iter()
+                .position(|bound| latency_ms <= *bound)
+                .unwrap_or(LATENCY_BUCKETS_MS.len() - 1);
+            self.latency_histogram[bucket].saturating_inc();
+        }
+    }
+
+    /// Share of the checks that found the service up, zero without checks
+    pub fn uptime(&self) -> Perbill {
+        if self.total == 0 {
+            return Perbill::zero();
+        }
+        Perbill::from_rational(self.up, self.total)
+    }
+
+    /// Upper bound of the latency bucket holding `percentile` of the checks that reported a
+    /// latency, `None` when no check did
+    pub fn latency_percentile(&self, percentile: Percent) -> Option<u32> {
+        let samples = self.latency_histogram.iter().fold(0u32, |sum, count| sum.saturating_add(*count));
+        if samples == 0 {
+            return None;
+        }
+        let rank = percentile.mul_ceil(samples).max(1);
+        let mut seen = 0u32;
+        self.latency_histogram.iter().zip(LATENCY_BUCKETS_MS).find_map(|(count, bound)| {
+            seen = seen.saturating_add(*count);
+            (seen >= rank).then_some(bound)
+        })
+    }
+}
+
+/// Uptime of members as measured by the monitors, e.g. to pay them for the services they run
+pub trait UptimeProvider<AccountId, BlockNumber> {
+    /// Blocks per epoch the uptime is measured in, epochs start at multiples of it
+    fn epoch_length() -> BlockNumber;
+
+    /// Share of the checks that found the service up in the epochs from the one `from` falls in
+    /// to the one `to` falls in, zero when no check was registered
+    fn uptime(
+        member_id: &AccountId,
+        service_id: &pallet_ibp_service::ServiceId,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Perbill;
+}
+
 impl Default for HealthCheckType {
   fn default() -> Self {
     HealthCheckType::RPC
@@ -68,6 +152,13 @@ pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer};
+	use sp_runtime::{
+		offchain::storage::StorageValueRef,
+		traits::{IdentifyAccount, Zero},
+		RuntimeAppPublic, SaturatedConversion,
+	};
+	use crate::offchain::{probe_endpoint, EndpointProvider};
 
     // use AccountId;
     // use pallet_ibp_member::MemberId;
@@ -77,6 +168,8 @@ pub mod pallet {
     
     use pallet_ibp_service::{ServiceId, ServiceType, ServiceStatus};
     use pallet_ibp_service::Error::{ServiceNotFound};
+    extern crate alloc;
+    use alloc::vec::Vec;
 
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
 	// (`Call`s) in this pallet.
@@ -90,16 +183,47 @@ pub mod pallet {
 	/// These types are defined generically and made concrete when the pallet is declared in the
 	/// `runtime/src/lib.rs` file of your chain.
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_ibp_member::Config + pallet_ibp_service::Config {
+	pub trait Config:
+		CreateSignedTransaction<Call<Self>> + frame_system::Config + pallet_ibp_member::Config + pallet_ibp_service::Config
+	{
 		/// The overarching runtime event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// A type representing the weights required by the dispatchables of this pallet.
 		type WeightInfo: WeightInfo;
+		/// Maximum number of health checks kept per (member, service) pair.
+		/// When the history is full the oldest check is dropped.
+		#[pallet::constant]
+		type MaxChecksPerPair: Get<u32>;
+		/// Number of blocks a health check is kept before it is pruned.
+		#[pallet::constant]
+		type CheckRetentionPeriod: Get<BlockNumberFor<Self>>;
+		/// The monitor key used by the offchain worker to sign health checks.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Where the offchain worker finds member endpoints.
+		type Endpoints: EndpointProvider<Self::AccountId, ServiceId>;
+		/// The offchain worker probes endpoints every `ProbeInterval` blocks.
+		#[pallet::constant]
+		type ProbeInterval: Get<BlockNumberFor<Self>>;
+		/// Maximum number of health checks registered at once by `register_health_checks`,
+		/// the offchain worker submits at most this many checks per probe round.
+		#[pallet::constant]
+		type MaxChecksPerBatch: Get<u32>;
+		/// Number of blocks health checks are rolled up over.
+		#[pallet::constant]
+		type EpochLength: Get<BlockNumberFor<Self>>;
+		/// Maximum number of epochs of rollups kept per (member, service) pair.
+		/// When the history is full the oldest epoch is dropped.
+		#[pallet::constant]
+		type MaxRollupEpochs: Get<u32>;
 	}
 
     #[derive(Default, Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
     #[scale_info(skip_type_params(T))]
     pub struct HealthCheckData<T: Config> {
+        // monitor Id: the monitor that submitted the check
+        pub monitor_id: T::AccountId,
+        // block number at which the check was registered
+        pub block_number: BlockNumberFor<T>,
         // member Id: unique identifier for the member, string
         pub member_id: T::AccountId,
         // domain Id: unique identifier for the domain, string
@@ -114,15 +238,48 @@ pub mod pallet {
         pub status: HealthCheckStatus,
     }
 
+    /// A health check as a monitor submits it, see `register_health_check`
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct HealthCheckReport<T: Config> {
+        pub member_id: T::AccountId,
+        pub service_id: ServiceId,
+        pub domain_id: DomainId,
+        pub check_type: HealthCheckType,
+        pub status: HealthCheckStatus,
+        // time the monitor took to probe the endpoint, when measured
+        pub latency_ms: Option<u32>,
+    }
+
     // impl HealthCheckData {
     //     fn as_mut(&mut self) -> &mut HealthCheckData {
     //         self
     //     }
     // }
 
-    // /// storage for this pallet.
-    // #[pallet::storage]
-    // pub type Checks<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, HealthCheckData<T>, ValueQuery>;
+    /// Recent health checks per (member, service), oldest first.
+    /// Bounded by `MaxChecksPerPair` and pruned by `CheckRetentionPeriod`.
+    #[pallet::storage]
+    #[pallet::getter(fn checks)]
+    pub type Checks<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        (T::AccountId, ServiceId),
+        BoundedVec<HealthCheckData<T>, T::MaxChecksPerPair>,
+        ValueQuery,
+    >;
+
+    /// Uptime rollups per (member, service), oldest epoch first.
+    /// Epochs without checks are skipped, bounded by `MaxRollupEpochs`.
+    #[pallet::storage]
+    #[pallet::getter(fn rollups)]
+    pub type Rollups<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        (T::AccountId, ServiceId),
+        BoundedVec<UptimeRollup, T::MaxRollupEpochs>,
+        ValueQuery,
+    >;
 
     /// Events that functions in this pallet can emit.
     ///
@@ -138,6 +295,8 @@ pub mod pallet {
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         HealthCheckRegistered(T::AccountId, ServiceId, HealthCheckType, HealthCheckStatus),
+        // (member, service, number of checks removed), only when checks were removed
+        HealthChecksPruned(T::AccountId, ServiceId, u32),
     }
 
     /// Errors that can be returned by this pallet.
@@ -156,8 +315,21 @@ pub mod pallet {
         NotACurator,
         // check for service membership level
         ServiceMembershipLevelMismatch,
+        // the domain is not registered for the service, or retired
+        UnknownDomainService,
 	}
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Probe the endpoints of all members and submit the results as health checks,
+        /// only on nodes holding a monitor key.
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            if let Err(e) = Self::probe_members(block_number) {
+                log::debug!(target: "ibp-check", "offchain worker skipped at {:?}: {}", block_number, e);
+            }
+        }
+    }
+
     /// The pallet's dispatchable functions ([`Call`]s).
     ///
     /// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -174,7 +346,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
 
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::register_health_check())]
         pub fn register_health_check(
             origin: OriginFor<T>, 
             member_id: T::AccountId,
@@ -182,23 +354,87 @@ pub mod pallet {
             domain_id: DomainId,
             check_type: HealthCheckType,
             // level: ServiceMembershipLevel,
-            status: HealthCheckStatus
+            status: HealthCheckStatus,
+            // time the monitor took to probe the endpoint, when measured
+            latency_ms: Option<u32>,
+        ) -> DispatchResult {
+            let sender: T::AccountId = ensure_signed(origin)?;
+            // only monitors can register health checks
+            ensure!(MemberPallet::<T>::monitors(&sender), Error::<T>::NotAMonitor);
+            Self::register_check(
+                sender,
+                HealthCheckReport { member_id, service_id, domain_id, check_type, status, latency_ms },
+            )
+        }
+
+        /// Register the health checks of a probe round at once, all or none of them. The offchain
+        /// worker submits one batch per round: the transactions of a round would all be signed
+        /// with the same nonce, only the first would make it into a block.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::register_health_checks(checks.len() as u32))]
+        pub fn register_health_checks(
+            origin: OriginFor<T>,
+            checks: BoundedVec<HealthCheckReport<T>, T::MaxChecksPerBatch>,
         ) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             // only monitors can register health checks
             ensure!(MemberPallet::<T>::monitors(&sender), Error::<T>::NotAMonitor);
+            for check in checks {
+                Self::register_check(sender.clone(), check)?;
+            }
+            Ok(())
+        }
+
+        /// Remove checks older than `CheckRetentionPeriod` for a (member, service) pair.
+        /// Anyone can call this to clean up histories that no longer receive checks.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::prune_health_checks())]
+        pub fn prune_health_checks(
+            origin: OriginFor<T>,
+            member_id: T::AccountId,
+            service_id: ServiceId,
+        ) -> DispatchResult {
+            let _sender = ensure_signed(origin)?;
+            let key = (member_id.clone(), service_id.clone());
+            let now = frame_system::Pallet::<T>::block_number();
+            let removed = Checks::<T>::mutate_exists(&key, |maybe_history| {
+                let history = match maybe_history {
+                    Some(history) => history,
+                    None => return 0,
+                };
+                let removed = Self::prune_expired(history, now);
+                if history.is_empty() {
+                    *maybe_history = None;
+                }
+                removed
+            });
+            if removed > 0 {
+                Self::deposit_event(Event::HealthChecksPruned(member_id, service_id, removed));
+            }
+            Ok(())
+        }
+
+	}
+
+    impl<T: Config> Pallet<T> {
+        /// Store a check of a monitor and fold it into the rollups
+        fn register_check(monitor_id: T::AccountId, report: HealthCheckReport<T>) -> DispatchResult {
+            let HealthCheckReport { member_id, service_id, domain_id, check_type, status, latency_ms } = report;
             // ensure member exists
             // ensure!(MemberPallet::<T>::members(&member_id), Error::<T>::MemberNotFound);
             let member = MemberPallet::<T>::members(&member_id).ok_or(MemberNotFound)?;
             // ensure service exists
             // ensure!(ServicePallet::<T>::services(&service_id), ServiceNotFound);
             let service = ServicePallet::<T>::services(&service_id).ok_or(ServiceNotFound)?;
+            ensure!(ServicePallet::<T>::is_domain_service(&domain_id, &service_id), Error::<T>::UnknownDomainService);
             // check member.level GE service.membershipLevel
             // ensure!(member.level >= service.level, Error::<T>::ServiceMembershipLevelMismatch);
             // let level = ServiceMembershipLevel::Zero;
             // let status = ServiceStatus::Locked;
             // let service_id = id.clone();
-            let _check_data: HealthCheckData<T> = HealthCheckData {
+            let check_data: HealthCheckData<T> = HealthCheckData {
+                monitor_id,
+                block_number: frame_system::Pallet::<T>::block_number(),
                 member_id: member_id.clone(),
                 service_id: service_id.clone(),
                 domain_id,
@@ -206,10 +442,212 @@ pub mod pallet {
                 level: service.level,
                 status: status.clone(),
             };
-            // Services::<T>::insert(id.clone(), service_data);
+            Self::record_rollup(&check_data, latency_ms);
+            Self::store_check(check_data);
             Self::deposit_event(Event::HealthCheckRegistered(member_id, service_id, check_type, status));
             Ok(())
         }
 
-	}
+        /// Append a check to its (member, service) history.
+        /// Expired checks are pruned first, then the oldest check is dropped if the history is full.
+        fn store_check(check: HealthCheckData<T>) {
+            let key = (check.member_id.clone(), check.service_id.clone());
+            let now = check.block_number;
+            Checks::<T>::mutate(key, |history| {
+                Self::prune_expired(history, now);
+                if history.is_full() {
+                    history.remove(0);
+                }
+                // cannot fail, there is room for at least one check
+                let _ = history.try_push(check);
+            });
+        }
+
+        /// Fold a check into the rollup of its epoch.
+        /// A new epoch drops the oldest epoch if the history is full.
+        fn record_rollup(check: &HealthCheckData<T>, latency_ms: Option<u32>) {
+            let epoch = Self::epoch_index(check.block_number);
+            Rollups::<T>::mutate((&check.member_id, &check.service_id), |rollups| {
+                if rollups.last().map(|rollup| rollup.epoch) != Some(epoch) {
+                    if rollups.is_full() {
+                        rollups.remove(0);
+                    }
+                    // cannot fail, there is room for at least one epoch
+                    let _ = rollups.try_push(UptimeRollup::new(epoch));
+                }
+                if let Some(rollup) = rollups.last_mut() {
+                    rollup.record(&check.status, latency_ms);
+                }
+            });
+        }
+
+        /// The epoch `block_number` falls in
+        pub fn epoch_index(block_number: BlockNumberFor<T>) -> EpochIndex {
+            let epoch_length = T::EpochLength::get();
+            if epoch_length.is_zero() {
+                return 0;
+            }
+            (block_number / epoch_length).saturated_into()
+        }
+
+        /// Retained rollups for a (member, service) pair, oldest epoch first
+        pub fn uptime_rollups(member_id: &T::AccountId, service_id: &ServiceId) -> Vec<UptimeRollup> {
+            Rollups::<T>::get((member_id, service_id)).into_inner()
+        }
+
+        /// Drop checks older than `CheckRetentionPeriod`, returns the number of checks removed
+        fn prune_expired(
+            history: &mut BoundedVec<HealthCheckData<T>, T::MaxChecksPerPair>,
+            now: BlockNumberFor<T>,
+        ) -> u32 {
+            let cutoff = now.saturating_sub(T::CheckRetentionPeriod::get());
+            let before = history.len();
+            history.retain(|check| check.block_number >= cutoff);
+            (before - history.len()) as u32
+        }
+
+        /// All retained checks for a (member, service) pair, oldest first
+        pub fn recent_checks(member_id: &T::AccountId, service_id: &ServiceId) -> Vec<HealthCheckData<T>> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let cutoff = now.saturating_sub(T::CheckRetentionPeriod::get());
+            Checks::<T>::get((member_id, service_id))
+                .into_iter()
+                .filter(|check| check.block_number >= cutoff)
+                .collect()
+        }
+
+        /// Checks for a (member, service) pair registered at or after `since`
+        pub fn checks_since(
+            member_id: &T::AccountId,
+            service_id: &ServiceId,
+            since: BlockNumberFor<T>,
+        ) -> Vec<HealthCheckData<T>> {
+            Checks::<T>::get((member_id, service_id))
+                .into_iter()
+                .filter(|check| check.block_number >= since)
+                .collect()
+        }
+
+        /// The most recent check for a (member, service) pair, if any
+        pub fn latest_check(member_id: &T::AccountId, service_id: &ServiceId) -> Option<HealthCheckData<T>> {
+            Checks::<T>::get((member_id, service_id)).into_iter().last()
+        }
+
+        /// Probe every endpoint of every RPC service a member must provide, then submit the results
+        /// in one signed `register_health_checks`. A round probes at most `MaxChecksPerBatch`
+        /// endpoints, the next round resumes with the member that did not fit.
+        pub fn probe_members(block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+            let interval = T::ProbeInterval::get();
+            if interval.is_zero() || !(block_number % interval).is_zero() {
+                return Err("not a probe block");
+            }
+            let signer = Self::monitor_signer().ok_or("no monitor key in the keystore")?;
+            // only one probe round per block, even when the worker runs again after a re-org
+            let last_probe = StorageValueRef::persistent(b"ibp-check::last-probe");
+            last_probe
+                .mutate(|last: Result<Option<BlockNumberFor<T>>, _>| match last {
+                    Ok(Some(last)) if last >= block_number => Err(()),
+                    _ => Ok(block_number),
+                })
+                .map_err(|_| "already probed")?;
+
+            let cursor = StorageValueRef::persistent(b"ibp-check::probe-cursor");
+            let mut members = match cursor.get::<Vec<u8>>().ok().flatten() {
+                Some(raw_key) => pallet_ibp_member::Members::<T>::iter_keys_from(raw_key),
+                None => pallet_ibp_member::Members::<T>::iter_keys(),
+            };
+            let mut checks = BoundedVec::<HealthCheckReport<T>, T::MaxChecksPerBatch>::new();
+            loop {
+                let probed = members.last_raw_key().to_vec();
+                let Some(member_id) = members.next() else {
+                    // all members were probed, the next round starts over
+                    cursor.clear();
+                    break;
+                };
+                if checks.is_full() {
+                    cursor.set(&probed);
+                    break;
+                }
+                for service in ServicePallet::<T>::services_for_member(&member_id) {
+                    if service.service_type != ServiceType::RPC {
+                        continue;
+                    }
+                    for endpoint in T::Endpoints::endpoints(&member_id, &service.id) {
+                        // the round ends once the batch is full
+                        if checks.is_full() {
+                            break;
+                        }
+                        let started = sp_io::offchain::timestamp();
+                        let result = probe_endpoint(&endpoint.url, endpoint.genesis_hash.as_ref());
+                        let elapsed = sp_io::offchain::timestamp().diff(&started).millis();
+                        // failed probes often wait for the deadline, only successful probes report a latency
+                        let (status, latency_ms) = match result {
+                            Ok(()) => (HealthCheckStatus::Active, Some(elapsed.saturated_into::<u32>())),
+                            Err(e) => {
+                                log::debug!(target: "ibp-check", "probe of {:?} failed: {:?}", endpoint.url, e);
+                                (HealthCheckStatus::Error, None)
+                            },
+                        };
+                        let _ = checks.try_push(HealthCheckReport {
+                            member_id: member_id.clone(),
+                            service_id: service.id.clone(),
+                            domain_id: endpoint.domain_id,
+                            check_type: HealthCheckType::RPC,
+                            status,
+                            latency_ms,
+                        });
+                    }
+                }
+            }
+            if checks.is_empty() {
+                return Ok(());
+            }
+            let result =
+                signer.send_signed_transaction(|_account| Call::register_health_checks { checks: checks.clone() });
+            if let Some((_, Err(()))) = result {
+                log::warn!(target: "ibp-check", "failed to submit health checks");
+            }
+            Ok(())
+        }
+
+        /// A signer for the first local key that belongs to a monitor
+        fn monitor_signer() -> Option<Signer<T, T::AuthorityId, frame_system::offchain::ForAny>> {
+            let monitor_key = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+                .into_iter()
+                .map(|key| {
+                    let generic: <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic = key.into();
+                    let public: T::Public = generic.into();
+                    public
+                })
+                .find(|public| {
+                    let account_id = public.clone().into_account();
+                    MemberPallet::<T>::monitors(&account_id)
+                })?;
+            Some(Signer::<T, T::AuthorityId>::any_account().with_filter(alloc::vec![monitor_key]))
+        }
+    }
+}
+
+impl<T: Config> UptimeProvider<T::AccountId, frame_system::pallet_prelude::BlockNumberFor<T>> for Pallet<T> {
+    fn epoch_length() -> frame_system::pallet_prelude::BlockNumberFor<T> {
+        T::EpochLength::get()
+    }
+
+    fn uptime(
+        member_id: &T::AccountId,
+        service_id: &pallet_ibp_service::ServiceId,
+        from: frame_system::pallet_prelude::BlockNumberFor<T>,
+        to: frame_system::pallet_prelude::BlockNumberFor<T>,
+    ) -> Perbill {
+        // the rollups cover `MaxRollupEpochs` epochs, the raw checks only `MaxChecksPerPair`
+        let (first, last) = (Self::epoch_index(from), Self::epoch_index(to));
+        let mut epochs = UptimeRollup::default();
+        for rollup in Rollups::<T>::get((member_id, service_id)) {
+            if rollup.epoch >= first && rollup.epoch <= last {
+                epochs.up.saturating_accrue(rollup.up);
+                epochs.total.saturating_accrue(rollup.total);
+            }
+        }
+        epochs.uptime()
+    }
 }
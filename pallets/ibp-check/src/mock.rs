@@ -1,22 +1,30 @@
-use crate::{self as pallet_template, MemberStatus};
+use crate as pallet_ibp_check;
+use crate::offchain::{Endpoint, EndpointProvider};
 use frame_support::{
-	derive_impl,
-	traits::{ConstU16, ConstU64},
+	derive_impl, parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
 };
-use sp_core::H256;
+use pallet_ibp_service::ServiceId;
+use sp_core::{sr25519::Signature, H256};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::TestXt,
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
 	BuildStorage,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Balances: pallet_balances,
+		IbpMember: pallet_ibp_member,
+		IbpService: pallet_ibp_service,
+		IbpCheck: pallet_ibp_check,
 	}
 );
 
@@ -31,14 +39,14 @@ impl frame_system::Config for Test {
 	type Nonce = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Block = Block;
 	type RuntimeEvent = RuntimeEvent;
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -47,13 +55,109 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
 	type WeightInfo = ();
-  // type AccountId = u64;
-  type MemberStatus = u64;
-  type MembershipLevel = u64;
-  type MemberData = (MemberStatus, MembershipLevel);
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl pallet_ibp_member::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type DefaultSessionLength = ConstU64<10>;
+	type OnMemberChanged = IbpService;
+	type MaxAuditEntries = ConstU32<3>;
+	type Currency = Balances;
+	type UnbondingPeriod = ConstU64<10>;
+	type Slashed = ();
+	type MaxMembers = ConstU32<16>;
+}
+
+impl pallet_ibp_service::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxOverrideExpiries = ConstU32<4>;
+	type MaxServices = ConstU32<16>;
+}
+
+/// The URL the probe tests expect requests on
+pub const ENDPOINT_URL: &str = "http://localhost:9944";
+
+parameter_types! {
+	/// The URLs every member serves every service on
+	pub static EndpointUrls: Vec<String> = vec![ENDPOINT_URL.into()];
+	pub static MaxChecksPerBatch: u32 = 4;
+}
+
+/// Every member serves every service on each of `EndpointUrls`
+pub struct LocalEndpoint;
+
+impl EndpointProvider<AccountId, ServiceId> for LocalEndpoint {
+	fn endpoints(_member_id: &AccountId, _service_id: &ServiceId) -> Vec<Endpoint> {
+		EndpointUrls::get()
+			.into_iter()
+			.map(|url| Endpoint {
+				domain_id: b"localhost".to_vec().try_into().unwrap(),
+				url: url.into_bytes(),
+				genesis_hash: None,
+			})
+			.collect()
+	}
+}
+
+impl pallet_ibp_check::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxChecksPerPair = ConstU32<10>;
+	type CheckRetentionPeriod = ConstU64<100>;
+	type AuthorityId = pallet_ibp_check::offchain::crypto::MonitorAuthId;
+	type Endpoints = LocalEndpoint;
+	type ProbeInterval = ConstU64<5>;
+	type MaxChecksPerBatch = MaxChecksPerBatch;
+	type EpochLength = ConstU64<10>;
+	type MaxRollupEpochs = ConstU32<3>;
+}
+
+/// A test account
+pub fn account(seed: u8) -> AccountId {
+	sp_core::sr25519::Public::from_raw([seed; 32])
 }
 
 // Build genesis storage according to the mock runtime.
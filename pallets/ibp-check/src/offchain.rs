@@ -0,0 +1,211 @@
+//! Offchain probing of member RPC endpoints.
+//!
+//! The offchain worker calls `system_health`, `chain_getBlockHash(0)` and `chain_getHeader`
+//! on every endpoint a member must serve, then submits the result as a health check signed
+//! with a monitor key from the local keystore (key type [`KEY_TYPE`]).
+
+use crate::DomainId;
+use pallet_ibp_service::{EndpointProtocol, ServiceId};
+use lite_json::json::JsonValue;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::offchain::{http, Duration};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Key type of the monitor keys used to sign health checks, insert with
+/// `ibp-node key insert --key-type ibpm --scheme sr25519`
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ibpm");
+
+/// Time allowed for each JSON-RPC request of a probe
+pub const PROBE_TIMEOUT_MS: u64 = 3_000;
+
+/// Monitor key crypto, the keys are sr25519 so the monitor account id is the public key
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct MonitorAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for MonitorAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	// implemented for mock runtimes that sign with sr25519 directly
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for MonitorAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// An RPC endpoint a member serves a service on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+	/// Domain reported with the health check
+	pub domain_id: DomainId,
+	/// Full URL of the JSON-RPC endpoint, e.g. `https://rpc.example.com/polkadot`
+	pub url: Vec<u8>,
+	/// Genesis hash of the chain the endpoint must serve, when known
+	pub genesis_hash: Option<[u8; 32]>,
+}
+
+/// Source of the endpoints probed by the offchain worker
+pub trait EndpointProvider<AccountId, ServiceId> {
+	/// The endpoints `member_id` serves `service_id` on
+	fn endpoints(member_id: &AccountId, service_id: &ServiceId) -> Vec<Endpoint>;
+}
+
+/// No endpoints, the offchain worker probes nothing
+impl<AccountId, ServiceId> EndpointProvider<AccountId, ServiceId> for () {
+	fn endpoints(_member_id: &AccountId, _service_id: &ServiceId) -> Vec<Endpoint> {
+		Vec::new()
+	}
+}
+
+/// Probes the endpoints members declare in `pallet-ibp-service` whose hostname is registered
+/// for the service under an active domain, against the genesis hash of the service's chain. `wss://` endpoints are probed over `https://`, nodes
+/// serve both on the same port.
+impl<T: pallet_ibp_service::Config> EndpointProvider<T::AccountId, ServiceId> for pallet_ibp_service::Pallet<T> {
+	fn endpoints(member_id: &T::AccountId, service_id: &ServiceId) -> Vec<Endpoint> {
+		let Some(endpoint) = pallet_ibp_service::MemberEndpoints::<T>::get((member_id, service_id)) else {
+			return Vec::new();
+		};
+		let url = match endpoint.protocol {
+			EndpointProtocol::Https => endpoint.url.into_inner(),
+			EndpointProtocol::Wss => [b"https://".as_slice(), &endpoint.url[b"wss://".len()..]].concat(),
+			EndpointProtocol::P2p => return Vec::new(),
+		};
+		let Some(domain_id) = url_host(&url).and_then(|host| Self::domain_for_host(service_id, host)) else {
+			return Vec::new();
+		};
+		alloc::vec![Endpoint { domain_id, url, genesis_hash: Self::genesis_hash(service_id) }]
+	}
+}
+
+/// The host of a URL, e.g. `rpc.example.com` for `https://rpc.example.com:443/polkadot`
+fn url_host(url: &[u8]) -> Option<&[u8]> {
+	let scheme_end = url.windows(3).position(|w| w == b"://")? + 3;
+	let authority = &url[scheme_end..];
+	let host_end = authority.iter().position(|c| *c == b'/' || *c == b':').unwrap_or(authority.len());
+	Some(&authority[..host_end])
+}
+
+/// Why an endpoint failed its probe
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProbeError {
+	/// The URL is not valid UTF-8
+	InvalidUrl,
+	/// The request could not be sent or the connection failed
+	Http,
+	/// No response before the deadline
+	DeadlineReached,
+	/// The response had a non-200 status code
+	Status(u16),
+	/// The body was not a JSON-RPC result of the expected shape
+	InvalidResponse,
+	/// The JSON-RPC response was an error
+	RpcError,
+	/// The node reports it is still syncing
+	Syncing,
+	/// The node serves another chain
+	WrongGenesis,
+}
+
+/// Probe an endpoint: the node must be synced, return its genesis hash (`genesis_hash` when
+/// given) and its best header
+pub fn probe_endpoint(url: &[u8], genesis_hash: Option<&[u8; 32]>) -> Result<(), ProbeError> {
+	let url = core::str::from_utf8(url).map_err(|_| ProbeError::InvalidUrl)?;
+
+	let health = rpc_call(url, "system_health", "[]")?;
+	match object_field(&health, "isSyncing") {
+		Some(JsonValue::Boolean(false)) => {},
+		Some(JsonValue::Boolean(true)) => return Err(ProbeError::Syncing),
+		_ => return Err(ProbeError::InvalidResponse),
+	}
+
+	let served_hash = rpc_call(url, "chain_getBlockHash", "[0]")?;
+	let served_hash = match served_hash {
+		// 0x followed by 32 bytes in hex
+		JsonValue::String(hash) if hash.len() == 66 && hash.starts_with(&['0', 'x']) => hash,
+		_ => return Err(ProbeError::InvalidResponse),
+	};
+	if let Some(expected) = genesis_hash {
+		if !hex_matches(&served_hash[2..], expected) {
+			return Err(ProbeError::WrongGenesis);
+		}
+	}
+
+	let header = rpc_call(url, "chain_getHeader", "[]")?;
+	match object_field(&header, "number") {
+		Some(JsonValue::String(_)) => Ok(()),
+		_ => Err(ProbeError::InvalidResponse),
+	}
+}
+
+/// The JSON-RPC request body for `method` with already encoded `params`
+pub fn rpc_request_body(method: &str, params: &str) -> Vec<u8> {
+	[
+		r#"{"jsonrpc":"2.0","id":1,"method":""#.as_bytes(),
+		method.as_bytes(),
+		r#"","params":"#.as_bytes(),
+		params.as_bytes(),
+		b"}",
+	]
+	.concat()
+}
+
+/// POST a JSON-RPC request and return its `result`
+fn rpc_call(url: &str, method: &str, params: &str) -> Result<JsonValue, ProbeError> {
+	let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PROBE_TIMEOUT_MS));
+	let pending = http::Request::post(url, alloc::vec![rpc_request_body(method, params)])
+		.add_header("Content-Type", "application/json")
+		.deadline(deadline)
+		.send()
+		.map_err(|_| ProbeError::Http)?;
+	let response = pending
+		.try_wait(deadline)
+		.map_err(|_| ProbeError::DeadlineReached)?
+		.map_err(|_| ProbeError::Http)?;
+	if response.code != 200 {
+		return Err(ProbeError::Status(response.code));
+	}
+	let body = response.body().collect::<Vec<u8>>();
+	let body = core::str::from_utf8(&body).map_err(|_| ProbeError::InvalidResponse)?;
+	let json = lite_json::parse_json(body).map_err(|_| ProbeError::InvalidResponse)?;
+	if object_field(&json, "error").is_some() {
+		return Err(ProbeError::RpcError);
+	}
+	object_field(&json, "result").cloned().ok_or(ProbeError::InvalidResponse)
+}
+
+/// Whether hex digits, either case, encode `bytes`
+fn hex_matches(hex: &[char], bytes: &[u8]) -> bool {
+	hex.len() == bytes.len() * 2 &&
+		hex.chunks(2).zip(bytes).all(|(digits, byte)| {
+			match (digits[0].to_digit(16), digits[1].to_digit(16)) {
+				(Some(high), Some(low)) => (high * 16 + low) as u8 == *byte,
+				_ => false,
+			}
+		})
+}
+
+/// The value of `key` when `value` is a JSON object
+fn object_field<'a>(value: &'a JsonValue, key: &str) -> Option<&'a JsonValue> {
+	match value {
+		JsonValue::Object(fields) => fields
+			.iter()
+			.find(|(name, _)| name.iter().copied().eq(key.chars()))
+			.map(|(_, value)| value),
+		_ => None,
+	}
+}
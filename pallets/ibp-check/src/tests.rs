@@ -1,27 +1,689 @@
-use crate::{mock::*, Error, Event, Something};
+use crate::{
+	mock::*,
+	offchain::{probe_endpoint, rpc_request_body, Endpoint, EndpointProvider, ProbeError, KEY_TYPE},
+	Checks, DomainId, Error, Event, HealthCheckReport, HealthCheckStatus, HealthCheckType, Rollups, UptimeProvider,
+	UptimeRollup,
+};
+use codec::Decode;
+use parking_lot::RwLock;
 use frame_support::{assert_noop, assert_ok};
+use pallet_ibp_member::MembershipLevel;
+use pallet_ibp_service::EndpointProtocol;
+use pallet_ibp_test_utils::{bounded, domain_id, service_id, setup_service};
+use sp_core::offchain::{
+	testing, Externalities, HttpError, HttpRequestId, HttpRequestStatus, OffchainDbExt, OffchainWorkerExt,
+	OpaqueNetworkState, OpaquePeerId, Timestamp, TransactionPoolExt,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{Perbill, Percent};
+use std::{
+	collections::BTreeMap,
+	io::{Read, Write},
+	net::{TcpListener, TcpStream},
+	sync::Arc,
+};
+
+fn curator() -> AccountId {
+	account(1)
+}
+
+fn member() -> AccountId {
+	account(2)
+}
+
+fn monitor() -> AccountId {
+	account(3)
+}
+
+/// A curator, a member, a monitor, and one active service served under a domain
+fn new_test_ext_with_service() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| setup_service::<Test>(&curator(), &member(), &monitor()));
+	ext
+}
+
+fn register_check(status: HealthCheckStatus) {
+	register_check_with_latency(status, None);
+}
+
+fn register_check_with_latency(status: HealthCheckStatus, latency_ms: Option<u32>) {
+	assert_ok!(IbpCheck::register_health_check(
+		RuntimeOrigin::signed(monitor()),
+		member(),
+		service_id(),
+		domain_id(),
+		HealthCheckType::RPC,
+		status,
+		latency_ms,
+	));
+}
 
 #[test]
-fn it_works_for_default_value() {
-	new_test_ext().execute_with(|| {
+fn register_health_check_works() {
+	new_test_ext_with_service().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(Something::<Test>::get(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		register_check(HealthCheckStatus::Active);
+		let latest = IbpCheck::latest_check(&member(), &service_id()).unwrap();
+		assert_eq!(latest.monitor_id, monitor());
+		assert_eq!(latest.block_number, 1);
+		System::assert_last_event(
+			Event::HealthCheckRegistered(member(), service_id(), HealthCheckType::RPC, HealthCheckStatus::Active).into(),
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
-	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+fn only_monitors_can_register_health_checks() {
+	new_test_ext_with_service().execute_with(|| {
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			IbpCheck::register_health_check(
+				RuntimeOrigin::signed(member()),
+				member(),
+				service_id(),
+				domain_id(),
+				HealthCheckType::RPC,
+				HealthCheckStatus::Active,
+				None,
+			),
+			Error::<Test>::NotAMonitor
+		);
+	});
+}
+
+fn report(member_id: AccountId, domain_id: DomainId) -> HealthCheckReport<Test> {
+	HealthCheckReport {
+		member_id,
+		service_id: service_id(),
+		domain_id,
+		check_type: HealthCheckType::RPC,
+		status: HealthCheckStatus::Active,
+		latency_ms: Some(120),
+	}
+}
+
+#[test]
+fn health_checks_are_registered_in_batches() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		let batch = vec![report(member(), domain_id()), report(member(), domain_id())];
+		assert_noop!(
+			IbpCheck::register_health_checks(RuntimeOrigin::signed(member()), batch.clone().try_into().unwrap()),
+			Error::<Test>::NotAMonitor
+		);
+		assert_ok!(IbpCheck::register_health_checks(RuntimeOrigin::signed(monitor()), batch.try_into().unwrap()));
+		assert_eq!(IbpCheck::recent_checks(&member(), &service_id()).len(), 2);
+		assert_eq!(Rollups::<Test>::get((member(), service_id()))[0].up, 2);
+
+		// a batch is registered entirely or not at all
+		let batch = vec![report(member(), domain_id()), report(member(), bounded("unknown.com"))];
+		assert_noop!(
+			IbpCheck::register_health_checks(RuntimeOrigin::signed(monitor()), batch.try_into().unwrap()),
+			Error::<Test>::UnknownDomainService
+		);
+	});
+}
+
+#[test]
+fn health_checks_need_a_registered_domain_service() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_noop!(
+			IbpCheck::register_health_check(
+				RuntimeOrigin::signed(monitor()),
+				member(),
+				service_id(),
+				bounded("unknown.network"),
+				HealthCheckType::RPC,
+				HealthCheckStatus::Active,
+				None,
+			),
+			Error::<Test>::UnknownDomainService
+		);
+	});
+}
+
+#[test]
+fn full_history_drops_oldest_check() {
+	new_test_ext_with_service().execute_with(|| {
+		for block_number in 1..=11 {
+			System::set_block_number(block_number);
+			register_check(HealthCheckStatus::Active);
+		}
+		let history = Checks::<Test>::get((member(), service_id()));
+		// MaxChecksPerPair is 10 in the mock
+		assert_eq!(history.len(), 10);
+		assert_eq!(history.first().unwrap().block_number, 2);
+		assert_eq!(history.last().unwrap().block_number, 11);
+	});
+}
+
+#[test]
+fn expired_checks_are_pruned() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		register_check(HealthCheckStatus::Active);
+		// CheckRetentionPeriod is 100 in the mock
+		System::set_block_number(102);
+		assert!(IbpCheck::recent_checks(&member(), &service_id()).is_empty());
+		assert_ok!(IbpCheck::prune_health_checks(RuntimeOrigin::signed(member()), member(), service_id()));
+		assert!(!Checks::<Test>::contains_key((member(), service_id())));
+		System::assert_last_event(Event::HealthChecksPruned(member(), service_id(), 1).into());
+	});
+}
+
+#[test]
+fn pruning_nothing_emits_no_event() {
+	new_test_ext_with_service().execute_with(|| {
+		System::set_block_number(1);
+		register_check(HealthCheckStatus::Active);
+		let events = System::events().len();
+		assert_ok!(IbpCheck::prune_health_checks(RuntimeOrigin::signed(member()), member(), service_id()));
+		assert_eq!(Checks::<Test>::get((member(), service_id())).len(), 1);
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn uptime_counts_active_checks_of_the_epochs() {
+	new_test_ext_with_service().execute_with(|| {
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 0, 9), Perbill::zero());
+		// EpochLength is 10 in the mock
+		for (block_number, status) in [
+			(1, HealthCheckStatus::Error),
+			(2, HealthCheckStatus::Active),
+			(3, HealthCheckStatus::Active),
+			(11, HealthCheckStatus::Active),
+		] {
+			System::set_block_number(block_number);
+			register_check(status);
+		}
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 0, 9), Perbill::from_rational(2u32, 3));
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 0, 19), Perbill::from_rational(3u32, 4));
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 10, 19), Perbill::one());
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 20, 29), Perbill::zero());
+
+		// pruned checks still count, their rollups are kept
+		System::set_block_number(1_000);
+		assert_ok!(IbpCheck::prune_health_checks(RuntimeOrigin::signed(monitor()), member(), service_id()));
+		assert_eq!(IbpCheck::uptime(&member(), &service_id(), 0, 19), Perbill::from_rational(3u32, 4));
+	});
+}
+
+#[test]
+fn checks_are_rolled_up_per_epoch() {
+	new_test_ext_with_service().execute_with(|| {
+		// EpochLength is 10 in the mock
+		for (block_number, status) in [
+			(1, HealthCheckStatus::Active),
+			(5, HealthCheckStatus::Error),
+			(9, HealthCheckStatus::Chilled),
+			(12, HealthCheckStatus::Active),
+		] {
+			System::set_block_number(block_number);
+			register_check_with_latency(status, Some(80));
+		}
+		let rollups = IbpCheck::uptime_rollups(&member(), &service_id());
+		assert_eq!(rollups.len(), 2);
+		assert_eq!((rollups[0].epoch, rollups[0].up, rollups[0].down, rollups[0].total), (0, 1, 1, 3));
+		assert_eq!(rollups[0].latency_histogram, [0, 3, 0, 0, 0, 0, 0, 0]);
+		assert_eq!(rollups[0].uptime(), Perbill::from_rational(1u32, 3));
+		assert_eq!((rollups[1].epoch, rollups[1].up, rollups[1].total), (1, 1, 1));
+	});
+}
+
+#[test]
+fn full_rollups_drop_oldest_epoch() {
+	new_test_ext_with_service().execute_with(|| {
+		for block_number in [5, 15, 25, 35] {
+			System::set_block_number(block_number);
+			register_check(HealthCheckStatus::Active);
+		}
+		// MaxRollupEpochs is 3 in the mock, epochs without checks are skipped
+		let epochs = || -> Vec<u32> {
+			Rollups::<Test>::get((member(), service_id())).iter().map(|rollup| rollup.epoch).collect()
+		};
+		assert_eq!(epochs(), vec![1, 2, 3]);
+		System::set_block_number(75);
+		register_check(HealthCheckStatus::Active);
+		assert_eq!(epochs(), vec![2, 3, 7]);
+	});
+}
+
+#[test]
+fn rollup_latency_percentiles() {
+	let mut rollup = UptimeRollup::new(0);
+	assert_eq!(rollup.latency_percentile(Percent::from_percent(50)), None);
+	for latency_ms in [40, 40, 90, 90, 90, 90, 200, 200, 700, 12_000] {
+		rollup.record(&HealthCheckStatus::Active, Some(latency_ms));
+	}
+	rollup.record(&HealthCheckStatus::Error, None);
+	assert_eq!(rollup.total, 11);
+	assert_eq!(rollup.latency_percentile(Percent::zero()), Some(50));
+	assert_eq!(rollup.latency_percentile(Percent::from_percent(50)), Some(100));
+	assert_eq!(rollup.latency_percentile(Percent::from_percent(90)), Some(1_000));
+	// slower than the last bound, counted in the last bucket
+	assert_eq!(rollup.latency_percentile(Percent::from_percent(99)), Some(9_000));
+}
+
+const HEALTHY: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"peers":8,"isSyncing":false,"shouldHavePeers":true}}"#;
+const SYNCING: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"peers":8,"isSyncing":true,"shouldHavePeers":true}}"#;
+const GENESIS_HASH: &str =
+	r#"{"jsonrpc":"2.0","id":1,"result":"0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3"}"#;
+const HEADER: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"number":"0x1388","parentHash":"0x00"}}"#;
+const RPC_ERROR: &str = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#;
+
+fn expect_rpc(state: &RwLock<testing::OffchainState>, method: &str, params: &str, response: &str) {
+	expect_rpc_at(state, ENDPOINT_URL, method, params, response);
+}
+
+fn expect_rpc_at(state: &RwLock<testing::OffchainState>, uri: &str, method: &str, params: &str, response: &str) {
+	state.write().expect_request(testing::PendingRequest {
+		method: "POST".into(),
+		uri: uri.into(),
+		headers: vec![("Content-Type".into(), "application/json".into())],
+		body: rpc_request_body(method, params),
+		response: Some(response.as_bytes().to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+fn expect_healthy_node(state: &RwLock<testing::OffchainState>) {
+	expect_healthy_node_at(state, ENDPOINT_URL);
+}
+
+fn expect_healthy_node_at(state: &RwLock<testing::OffchainState>, uri: &str) {
+	expect_rpc_at(state, uri, "system_health", "[]", HEALTHY);
+	expect_rpc_at(state, uri, "chain_getBlockHash", "[0]", GENESIS_HASH);
+	expect_rpc_at(state, uri, "chain_getHeader", "[]", HEADER);
+}
+
+/// `new_test_ext_with_service` with offchain extensions. When `monitor_key` is set the
+/// keystore holds the key of a second monitor, which is the only monitor able to sign.
+/// The service requires level One, which only `member()` has, so it is the only member probed.
+fn new_offchain_ext(
+	monitor_key: bool,
+) -> (sp_io::TestExternalities, Arc<RwLock<testing::OffchainState>>, Arc<RwLock<testing::PoolState>>) {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (ext, pool_state) = offchain_ext_with(monitor_key, offchain.clone(), offchain);
+	(ext, offchain_state, pool_state)
+}
+
+/// `new_offchain_ext` with the HTTP requests of the worker sent through `worker`
+fn offchain_ext_with(
+	monitor_key: bool,
+	worker: impl Externalities + 'static,
+	offchain: testing::TestOffchainExt,
+) -> (sp_io::TestExternalities, Arc<RwLock<testing::PoolState>>) {
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	let key_monitor = monitor_key.then(|| keystore.sr25519_generate_new(KEY_TYPE, None).unwrap());
+
+	let mut ext = new_test_ext_with_service();
+	ext.register_extension(OffchainWorkerExt::new(worker));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt::new(keystore));
+	ext.execute_with(|| {
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(curator()), member(), MembershipLevel::One));
+		assert_ok!(IbpService::update_service_level(RuntimeOrigin::signed(curator()), service_id(), MembershipLevel::One));
+		if let Some(key_monitor) = key_monitor {
+			assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(key_monitor), bounded("key monitor")));
+			assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(curator()), key_monitor, Default::default()));
+			assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(curator()), key_monitor));
+		}
+	});
+	(ext, pool_state)
+}
+
+/// The health check statuses submitted in the only pooled transaction, one per endpoint probed
+fn submitted_statuses(pool_state: &RwLock<testing::PoolState>) -> Vec<HealthCheckStatus> {
+	let tx = pool_state.write().transactions.pop().unwrap();
+	assert!(pool_state.read().transactions.is_empty());
+	let tx = Extrinsic::decode(&mut &*tx).unwrap();
+	assert!(tx.signature.is_some());
+	match tx.call {
+		RuntimeCall::IbpCheck(crate::Call::register_health_checks { checks }) => checks
+			.into_iter()
+			.map(|check| {
+				// only successful probes report a latency
+				assert_eq!(check.latency_ms.is_some(), check.status == HealthCheckStatus::Active);
+				assert_eq!(check.member_id, member());
+				assert_eq!(check.service_id, service_id());
+				assert_eq!(check.domain_id, bounded::<DomainId>("localhost"));
+				assert_eq!(check.check_type, HealthCheckType::RPC);
+				check.status
+			})
+			.collect(),
+		call => panic!("unexpected call {:?}", call),
+	}
+}
+
+#[test]
+fn probe_endpoint_accepts_healthy_node() {
+	let (mut ext, offchain_state, _) = new_offchain_ext(false);
+	expect_healthy_node(&offchain_state);
+	ext.execute_with(|| {
+		assert_eq!(probe_endpoint(ENDPOINT_URL.as_bytes(), None), Ok(()));
+	});
+}
+
+#[test]
+fn probe_endpoint_checks_the_genesis_hash() {
+	let polkadot: [u8; 32] =
+		sp_core::bytes::from_hex("0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3")
+			.unwrap()
+			.try_into()
+			.unwrap();
+	let (mut ext, offchain_state, _) = new_offchain_ext(false);
+	expect_healthy_node(&offchain_state);
+	expect_rpc(&offchain_state, "system_health", "[]", HEALTHY);
+	expect_rpc(&offchain_state, "chain_getBlockHash", "[0]", GENESIS_HASH);
+	ext.execute_with(|| {
+		assert_eq!(probe_endpoint(ENDPOINT_URL.as_bytes(), Some(&polkadot)), Ok(()));
+		assert_eq!(probe_endpoint(ENDPOINT_URL.as_bytes(), Some(&[1u8; 32])), Err(ProbeError::WrongGenesis));
+	});
+}
+
+#[test]
+fn probe_endpoint_rejects_syncing_node() {
+	let (mut ext, offchain_state, _) = new_offchain_ext(false);
+	expect_rpc(&offchain_state, "system_health", "[]", SYNCING);
+	ext.execute_with(|| {
+		assert_eq!(probe_endpoint(ENDPOINT_URL.as_bytes(), None), Err(ProbeError::Syncing));
+	});
+}
+
+#[test]
+fn probe_endpoint_rejects_rpc_errors() {
+	let (mut ext, offchain_state, _) = new_offchain_ext(false);
+	expect_rpc(&offchain_state, "system_health", "[]", HEALTHY);
+	expect_rpc(&offchain_state, "chain_getBlockHash", "[0]", RPC_ERROR);
+	ext.execute_with(|| {
+		assert_eq!(probe_endpoint(ENDPOINT_URL.as_bytes(), None), Err(ProbeError::RpcError));
+	});
+}
+
+#[test]
+fn offchain_worker_submits_active_check() {
+	let (mut ext, offchain_state, pool_state) = new_offchain_ext(true);
+	expect_healthy_node(&offchain_state);
+	ext.execute_with(|| {
+		// ProbeInterval is 5 in the mock
+		assert_ok!(IbpCheck::probe_members(5));
+	});
+	assert_eq!(submitted_statuses(&pool_state), vec![HealthCheckStatus::Active]);
+}
+
+#[test]
+fn offchain_worker_submits_error_check() {
+	let (mut ext, offchain_state, pool_state) = new_offchain_ext(true);
+	expect_rpc(&offchain_state, "system_health", "[]", SYNCING);
+	ext.execute_with(|| {
+		assert_ok!(IbpCheck::probe_members(5));
+	});
+	assert_eq!(submitted_statuses(&pool_state), vec![HealthCheckStatus::Error]);
+}
+
+#[test]
+fn offchain_worker_submits_the_round_in_one_transaction() {
+	let second_url = "http://localhost:9945";
+	EndpointUrls::set(vec![ENDPOINT_URL.into(), second_url.into()]);
+	let (mut ext, offchain_state, pool_state) = new_offchain_ext(true);
+	expect_healthy_node(&offchain_state);
+	expect_rpc_at(&offchain_state, second_url, "system_health", "[]", SYNCING);
+	ext.execute_with(|| {
+		assert_ok!(IbpCheck::probe_members(5));
+	});
+	// the checks of a round share a nonce, separate transactions would replace each other
+	assert_eq!(submitted_statuses(&pool_state), vec![HealthCheckStatus::Active, HealthCheckStatus::Error]);
+}
+
+#[test]
+fn offchain_worker_submits_at_most_a_batch_per_round() {
+	EndpointUrls::set(vec![ENDPOINT_URL.into(), "http://localhost:9945".into()]);
+	MaxChecksPerBatch::set(1);
+	let (mut ext, offchain_state, pool_state) = new_offchain_ext(true);
+	expect_healthy_node(&offchain_state);
+	ext.execute_with(|| {
+		assert_ok!(IbpCheck::probe_members(5));
+	});
+	assert_eq!(submitted_statuses(&pool_state), vec![HealthCheckStatus::Active]);
+}
+
+#[test]
+fn offchain_worker_probes_once_per_block() {
+	let (mut ext, offchain_state, pool_state) = new_offchain_ext(true);
+	expect_healthy_node(&offchain_state);
+	ext.execute_with(|| {
+		assert_ok!(IbpCheck::probe_members(5));
+		assert_eq!(IbpCheck::probe_members(5), Err("already probed"));
+	});
+	assert_eq!(pool_state.read().transactions.len(), 1);
+}
+
+#[test]
+fn offchain_worker_skips_non_probe_blocks() {
+	let (mut ext, _, pool_state) = new_offchain_ext(true);
+	ext.execute_with(|| {
+		assert_eq!(IbpCheck::probe_members(4), Err("not a probe block"));
+	});
+	assert!(pool_state.read().transactions.is_empty());
+}
+
+#[test]
+fn offchain_worker_needs_a_monitor_key() {
+	let (mut ext, _, pool_state) = new_offchain_ext(false);
+	ext.execute_with(|| {
+		assert_eq!(IbpCheck::probe_members(5), Err("no monitor key in the keystore"));
+	});
+	assert!(pool_state.read().transactions.is_empty());
+}
+
+#[test]
+fn declared_endpoints_are_probed_over_https() {
+	new_test_ext_with_service().execute_with(|| {
+		assert!(IbpService::endpoints(&member(), &service_id()).is_empty());
+		assert_ok!(IbpService::set_endpoint(
+			RuntimeOrigin::signed(member()),
+			service_id(),
+			bounded("wss://rpc.example.com:443/polkadot"),
+			EndpointProtocol::Wss,
+			None,
+		));
+		assert_eq!(
+			IbpService::endpoints(&member(), &service_id()),
+			vec![Endpoint {
+				domain_id: domain_id(),
+				url: b"https://rpc.example.com:443/polkadot".to_vec(),
+				genesis_hash: Some([1u8; 32]),
+			}]
 		);
+		// hostnames that are not registered for the service are not probed
+		assert_ok!(IbpService::update_endpoint(
+			RuntimeOrigin::signed(member()),
+			service_id(),
+			bounded("https://polkadot.example.com"),
+			EndpointProtocol::Https,
+			None,
+		));
+		assert!(IbpService::endpoints(&member(), &service_id()).is_empty());
+	});
+}
+
+/// A stand-in RPC node on a local port, answering every JSON-RPC method it knows as a healthy node
+fn spawn_rpc_node() -> String {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", listener.local_addr().unwrap());
+	std::thread::spawn(move || {
+		for mut stream in listener.incoming().flatten() {
+			let request = read_http_message(&mut stream);
+			let response = [("system_health", HEALTHY), ("chain_getBlockHash", GENESIS_HASH), ("chain_getHeader", HEADER)]
+				.into_iter()
+				.find(|(method, _)| request.contains(&format!(r#""method":"{}""#, method)))
+				.map_or(RPC_ERROR, |(_, response)| response);
+			let _ = write!(
+				stream,
+				"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+				response.len(),
+				response
+			);
+		}
+	});
+	url
+}
+
+/// Reads an HTTP message, up to the end of its `Content-Length` body or of the stream
+fn read_http_message(stream: &mut TcpStream) -> String {
+	let mut message = Vec::new();
+	let mut buffer = [0u8; 1024];
+	loop {
+		let read = stream.read(&mut buffer).unwrap_or(0);
+		message.extend_from_slice(&buffer[..read]);
+		let text = String::from_utf8_lossy(&message);
+		let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+			head.lines()
+				.filter_map(|line| line.split_once(':'))
+				.find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+				.is_some_and(|(_, length)| body.len() >= length.trim().parse().unwrap_or(0))
+		});
+		if read == 0 || complete {
+			return text.into_owned();
+		}
+	}
+}
+
+#[derive(Default)]
+struct SocketRequest {
+	uri: String,
+	headers: Vec<(String, String)>,
+	body: Vec<u8>,
+	response: Option<(u16, Vec<u8>)>,
+	read: usize,
+}
+
+/// Offchain externalities sending the HTTP requests of the worker over a socket, everything
+/// else is served by `TestOffchainExt`
+struct SocketOffchainExt {
+	offchain: testing::TestOffchainExt,
+	requests: BTreeMap<u16, SocketRequest>,
+	next_id: u16,
+}
+
+impl SocketOffchainExt {
+	fn send(request: &mut SocketRequest) -> Option<(u16, Vec<u8>)> {
+		let target = request.uri.strip_prefix("http://")?;
+		let (host, path) = target.find('/').map_or((target, "/"), |at| target.split_at(at));
+		let mut stream = TcpStream::connect(host).ok()?;
+		let mut message = format!("POST {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n", path, host, request.body.len());
+		for (name, value) in &request.headers {
+			message.push_str(&format!("{}: {}\r\n", name, value));
+		}
+		message.push_str("\r\n");
+		stream.write_all(message.as_bytes()).ok()?;
+		stream.write_all(&request.body).ok()?;
+		let response = read_http_message(&mut stream);
+		let (head, body) = response.split_once("\r\n\r\n")?;
+		let code = head.split_whitespace().nth(1)?.parse().ok()?;
+		Some((code, body.as_bytes().to_vec()))
+	}
+}
+
+impl Externalities for SocketOffchainExt {
+	fn is_validator(&self) -> bool {
+		self.offchain.is_validator()
+	}
+
+	fn network_state(&self) -> Result<OpaqueNetworkState, ()> {
+		self.offchain.network_state()
+	}
+
+	fn timestamp(&mut self) -> Timestamp {
+		self.offchain.timestamp()
+	}
+
+	fn sleep_until(&mut self, deadline: Timestamp) {
+		self.offchain.sleep_until(deadline)
+	}
+
+	fn random_seed(&mut self) -> [u8; 32] {
+		self.offchain.random_seed()
+	}
+
+	fn http_request_start(&mut self, method: &str, uri: &str, _meta: &[u8]) -> Result<HttpRequestId, ()> {
+		assert_eq!(method, "POST");
+		self.next_id += 1;
+		self.requests.insert(self.next_id, SocketRequest { uri: uri.into(), ..Default::default() });
+		Ok(HttpRequestId(self.next_id))
+	}
+
+	fn http_request_add_header(&mut self, request_id: HttpRequestId, name: &str, value: &str) -> Result<(), ()> {
+		let request = self.requests.get_mut(&request_id.0).ok_or(())?;
+		request.headers.push((name.into(), value.into()));
+		Ok(())
+	}
+
+	fn http_request_write_body(
+		&mut self,
+		request_id: HttpRequestId,
+		chunk: &[u8],
+		_deadline: Option<Timestamp>,
+	) -> Result<(), HttpError> {
+		let request = self.requests.get_mut(&request_id.0).ok_or(HttpError::Invalid)?;
+		if !chunk.is_empty() {
+			request.body.extend_from_slice(chunk);
+			return Ok(());
+		}
+		// an empty chunk finalizes the request
+		request.response = Some(Self::send(request).ok_or(HttpError::IoError)?);
+		Ok(())
+	}
+
+	fn http_response_wait(&mut self, ids: &[HttpRequestId], _deadline: Option<Timestamp>) -> Vec<HttpRequestStatus> {
+		ids.iter()
+			.map(|id| match self.requests.get(&id.0).and_then(|request| request.response.as_ref()) {
+				Some((code, _)) => HttpRequestStatus::Finished(*code),
+				None => HttpRequestStatus::Invalid,
+			})
+			.collect()
+	}
+
+	fn http_response_headers(&mut self, _request_id: HttpRequestId) -> Vec<(Vec<u8>, Vec<u8>)> {
+		Vec::new()
+	}
+
+	fn http_response_read_body(
+		&mut self,
+		request_id: HttpRequestId,
+		buffer: &mut [u8],
+		_deadline: Option<Timestamp>,
+	) -> Result<usize, HttpError> {
+		let request = self.requests.get_mut(&request_id.0).ok_or(HttpError::Invalid)?;
+		let (_, body) = request.response.as_ref().ok_or(HttpError::Invalid)?;
+		let read = buffer.len().min(body.len() - request.read);
+		buffer[..read].copy_from_slice(&body[request.read..request.read + read]);
+		request.read += read;
+		if read == 0 {
+			self.requests.remove(&request_id.0);
+		}
+		Ok(read)
+	}
+
+	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+		self.offchain.set_authorized_nodes(nodes, authorized_only)
+	}
+}
+
+#[test]
+fn offchain_worker_probes_a_local_rpc_node() {
+	let url = spawn_rpc_node();
+	EndpointUrls::set(vec![url, "http://127.0.0.1:1".into()]);
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let worker = SocketOffchainExt { offchain: offchain.clone(), requests: BTreeMap::new(), next_id: 0 };
+	let (mut ext, pool_state) = offchain_ext_with(true, worker, offchain);
+	ext.execute_with(|| {
+		assert_ok!(IbpCheck::probe_members(5));
 	});
+	// nothing listens on the second endpoint
+	assert_eq!(submitted_statuses(&pool_state), vec![HealthCheckStatus::Active, HealthCheckStatus::Error]);
 }
@@ -1,29 +1,28 @@
 
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_ibp_check
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS.
+//! Storage access counts follow the dispatchables; execution times are conservative placeholders.
+//! Regenerate on reference hardware before relying on them, with `scripts/benchmark-weights.sh`,
+//! which builds with `--features runtime-benchmarks` and runs for each IBP pallet:
 
-// Executed Command:
-// ../../target/release/node-template
+// Command:
+// ./target/release/ibp-node
 // benchmark
 // pallet
 // --chain
 // dev
 // --pallet
-// pallet_template
+// pallet_ibp_check
 // --extrinsic
 // *
 // --steps=50
 // --repeat=20
 // --wasm-execution=compiled
 // --output
-// pallets/template/src/weights.rs
+// pallets/ibp-check/src/weights.rs
 // --template
-// ../../.maintain/frame-weight-template.hbs
+// ./.maintain/frame-weight-template.hbs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -32,41 +31,47 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_ibp_check.
 pub trait WeightInfo {
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+	fn register_health_check() -> Weight;
+	fn register_health_checks(n: u32, ) -> Weight;
+	fn prune_health_checks() -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_ibp_check using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// fn register_member() -> Weight {
-	// 	// Proof Size summary in bytes:
-	// 	//  Measured:  `0`
-	// 	//  Estimated: `0`
-	// 	// Minimum execution time: 8_000_000 picoseconds.
-	// 	Weight::from_parts(10_000, 0)
-	// 		.saturating_add(T::DbWeight::get().writes(1_u64))
-	// }
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:0)
+	/// Storage: IbpCheck Rollups (r:1 w:1), worst case with a full history of `MaxRollupEpochs` epochs
+	/// Storage: IbpCheck Checks (r:1 w:1), worst case with a full history of `MaxChecksPerPair` checks
+	fn register_health_check() -> Weight {
+		Weight::from_parts(55_000_000, 30000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember Members (r:n w:0)
+	/// Storage: IbpService Services (r:n w:0)
+	/// Storage: IbpService Domains (r:n w:0)
+	/// Storage: IbpService DomainServices (r:n w:0)
+	/// Storage: IbpCheck Rollups (r:n w:n), worst case with full histories of distinct pairs
+	/// Storage: IbpCheck Checks (r:n w:n), worst case with full histories of distinct pairs
+	/// The range of component `n` is `[1, 64]`.
+	fn register_health_checks(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 1600)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 30000).saturating_mul(n.into()))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpCheck Checks (r:1 w:1), worst case with a full history of expired checks
+	fn prune_health_checks() -> Weight {
+		Weight::from_parts(35_000_000, 22000)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
@@ -74,24 +79,37 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:0)
+	/// Storage: IbpCheck Rollups (r:1 w:1), worst case with a full history of `MaxRollupEpochs` epochs
+	/// Storage: IbpCheck Checks (r:1 w:1), worst case with a full history of `MaxChecksPerPair` checks
+	fn register_health_check() -> Weight {
+		Weight::from_parts(55_000_000, 30000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember Members (r:n w:0)
+	/// Storage: IbpService Services (r:n w:0)
+	/// Storage: IbpService Domains (r:n w:0)
+	/// Storage: IbpService DomainServices (r:n w:0)
+	/// Storage: IbpCheck Rollups (r:n w:n), worst case with full histories of distinct pairs
+	/// Storage: IbpCheck Checks (r:n w:n), worst case with full histories of distinct pairs
+	/// The range of component `n` is `[1, 64]`.
+	fn register_health_checks(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 1600)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 30000).saturating_mul(n.into()))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpCheck Checks (r:1 w:1), worst case with a full history of expired checks
+	fn prune_health_checks() -> Weight {
+		Weight::from_parts(35_000_000, 22000)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
@@ -1,11 +1,56 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-ibp-member
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as IbpMember;
 use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, ReservableCurrency};
 use frame_system::RawOrigin;
+extern crate alloc;
+use alloc::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn member_name(prefix: &[u8], index: u32) -> MemberName {
+	let mut name: Vec<u8> = prefix.to_vec();
+	name.extend_from_slice(&index.to_be_bytes());
+	MemberName::try_from(name).expect("name fits in 32 bytes")
+}
+
+fn add_member<T: Config>(prefix: &'static str, index: u32, status: MemberStatus) -> T::AccountId {
+	let account_id: T::AccountId = account(prefix, index, SEED);
+	let member_data = MemberData::<T> {
+		id: Some(account_id.clone()),
+		name: member_name(prefix.as_bytes(), index),
+		status,
+		level: MembershipLevel::None,
+	};
+	if status != MemberStatus::Deleted {
+		MemberNames::<T>::insert(normalize_name(&member_data.name), &account_id);
+	}
+	Members::<T>::insert(&account_id, member_data);
+	account_id
+}
+
+fn reason() -> StatusReason {
+	StatusReason::try_from([b'x'; 64].to_vec()).expect("reason fits in 64 bytes")
+}
+
+/// A bond the funded accounts can pay many times over
+fn bond<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance() * 100u32.into()
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, bond::<T>() * 10u32.into());
+}
+
+fn add_curator<T: Config>(index: u32) -> T::AccountId {
+	let curator: T::AccountId = account("curator", index, SEED);
+	IbpMember::<T>::assign_curator(RawOrigin::Root.into(), curator.clone()).expect("curator is assigned");
+	curator
+}
 
 #[benchmarks]
 mod benchmarks {
@@ -13,33 +58,196 @@ mod benchmarks {
 
 	#[benchmark]
 	fn register_member() {
-		let value = 100u32.into();
 		let caller: T::AccountId = whitelisted_caller();
+		let name = member_name(b"caller", 0);
+		LevelBonds::<T>::insert(MembershipLevel::None, bond::<T>());
+		fund::<T>(&caller);
 		#[extrinsic_call]
-		register_member(RawOrigin::Signed(caller), value);
+		register_member(RawOrigin::Signed(caller.clone()), name);
 
-		assert_eq!(MemberData::<T>::get(), Some(value));
+		assert_eq!(Members::<T>::get(&caller).status, MemberStatus::Locked);
+		assert_eq!(MemberBonds::<T>::get(&caller), bond::<T>());
 	}
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn set_member_name() {
+		let caller = add_member::<T>("caller", 0, MemberStatus::Active);
+		let name = member_name(b"renamed", 0);
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		set_member_name(RawOrigin::Signed(caller.clone()), name.clone());
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(Members::<T>::get(&caller).name, name);
+		assert_eq!(IbpMember::<T>::member_by_name(&name), Some(caller));
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn chill_member() {
+		let caller = add_member::<T>("caller", 0, MemberStatus::Active);
+		#[extrinsic_call]
+		chill_member(RawOrigin::Signed(caller.clone()), reason());
+
+		assert_eq!(Members::<T>::get(&caller).status, MemberStatus::Chilled);
+	}
+
+	#[benchmark]
+	fn unchill_member() {
+		let caller = add_member::<T>("caller", 0, MemberStatus::Chilled);
+		#[extrinsic_call]
+		unchill_member(RawOrigin::Signed(caller.clone()), reason());
+
+		assert_eq!(Members::<T>::get(&caller).status, MemberStatus::Active);
+	}
+
+	#[benchmark]
+	fn lock_member() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		#[extrinsic_call]
+		lock_member(RawOrigin::Signed(curator), target.clone(), reason());
+
+		assert_eq!(Members::<T>::get(&target).status, MemberStatus::Locked);
+	}
+
+	#[benchmark]
+	fn unlock_member() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Locked);
+		#[extrinsic_call]
+		unlock_member(RawOrigin::Signed(curator), target.clone(), reason());
+
+		assert_eq!(Members::<T>::get(&target).status, MemberStatus::Active);
+	}
+
+	#[benchmark]
+	fn delete_member() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		#[extrinsic_call]
+		delete_member(RawOrigin::Signed(curator), target.clone(), reason());
+
+		assert_eq!(Members::<T>::get(&target).status, MemberStatus::Deleted);
+		assert_eq!(IbpMember::<T>::member_by_name(&Members::<T>::get(&target).name), None);
+	}
+
+	#[benchmark]
+	fn undelete_member() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Deleted);
+		LevelBonds::<T>::insert(MembershipLevel::None, bond::<T>());
+		fund::<T>(&target);
+		#[extrinsic_call]
+		undelete_member(RawOrigin::Signed(curator), target.clone(), reason());
+
+		assert_eq!(Members::<T>::get(&target).status, MemberStatus::Locked);
+	}
+
+	#[benchmark]
+	fn update_member_level() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		LevelBonds::<T>::insert(MembershipLevel::Six, bond::<T>());
+		fund::<T>(&target);
+		#[extrinsic_call]
+		update_member_level(RawOrigin::Signed(curator), target.clone(), MembershipLevel::Six);
+
+		assert_eq!(Members::<T>::get(&target).level, MembershipLevel::Six);
+		assert_eq!(MemberBonds::<T>::get(&target), bond::<T>());
+	}
+
+	#[benchmark]
+	fn assign_monitor() {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		#[extrinsic_call]
+		assign_monitor(RawOrigin::Signed(curator), target.clone());
+
+		assert!(IbpMember::<T>::monitors(&target));
+	}
+
+	#[benchmark]
+	fn remove_monitor() -> Result<(), BenchmarkError> {
+		let curator = add_curator::<T>(0);
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		IbpMember::<T>::assign_monitor(RawOrigin::Signed(curator.clone()).into(), target.clone())?;
+		#[extrinsic_call]
+		remove_monitor(RawOrigin::Signed(curator), target.clone());
+
+		assert!(!IbpMember::<T>::monitors(&target));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn assign_curator() {
+		let target: T::AccountId = account("target", 0, SEED);
+		#[extrinsic_call]
+		assign_curator(RawOrigin::Root, target.clone());
+
+		assert!(IbpMember::<T>::curators(&target));
+	}
+
+	#[benchmark]
+	fn remove_curator() {
+		// the last curator cannot be removed
+		add_curator::<T>(0);
+		let target = add_curator::<T>(1);
+		#[extrinsic_call]
+		remove_curator(RawOrigin::Root, target.clone());
+
+		assert!(!IbpMember::<T>::curators(&target));
+	}
+
+	#[benchmark]
+	fn set_level_bond() {
+		#[extrinsic_call]
+		set_level_bond(RawOrigin::Root, MembershipLevel::Six, bond::<T>());
+
+		assert_eq!(LevelBonds::<T>::get(MembershipLevel::Six), bond::<T>());
+	}
+
+	#[benchmark]
+	fn withdraw_unbonded() -> Result<(), BenchmarkError> {
+		let caller = add_member::<T>("caller", 0, MemberStatus::Deleted);
+		fund::<T>(&caller);
+		T::Currency::reserve(&caller, bond::<T>())?;
+		let unlock_at = frame_system::Pallet::<T>::block_number();
+		Unbonding::<T>::insert(&caller, UnbondingBond { amount: bond::<T>(), unlock_at });
+		#[extrinsic_call]
+		withdraw_unbonded(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(Unbonding::<T>::get(&caller), None);
+		assert_eq!(T::Currency::reserved_balance(&caller), 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn slash_bond() -> Result<(), BenchmarkError> {
+		let curator = add_curator::<T>(0);
+		// slash both the bond and an unbonding bond
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		fund::<T>(&target);
+		T::Currency::reserve(&target, bond::<T>() * 2u32.into())?;
+		MemberBonds::<T>::insert(&target, bond::<T>());
+		let unlock_at = frame_system::Pallet::<T>::block_number();
+		Unbonding::<T>::insert(&target, UnbondingBond { amount: bond::<T>(), unlock_at });
+		#[extrinsic_call]
+		slash_bond(RawOrigin::Signed(curator), target.clone(), bond::<T>() * 2u32.into(), reason());
+
+		assert_eq!(T::Currency::reserved_balance(&target), 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn leave_membership() {
+		let target = add_member::<T>("target", 0, MemberStatus::Active);
+		fund::<T>(&target);
+		T::Currency::reserve(&target, bond::<T>()).expect("target is funded");
+		MemberBonds::<T>::insert(&target, bond::<T>());
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		leave_membership(RawOrigin::Signed(target.clone()), reason());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(Members::<T>::get(&target).status, MemberStatus::Deleted);
+		assert!(Unbonding::<T>::contains_key(&target));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(IbpMember, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -42,10 +42,14 @@
 
 use codec::{ Decode, Encode, MaxEncodedLen };
 use scale_info::{self, TypeInfo};
+use serde::{Deserialize, Serialize};
 use frame_support::{
   traits::ConstU32,
+  weights::Weight,
   BoundedVec,
 };
+extern crate alloc;
+use alloc::vec::Vec;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
@@ -70,10 +74,61 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 pub use weights::*;
+pub mod migrations;
 
 pub type MemberName = BoundedVec<u8, ConstU32<32>>;
 
-#[derive(Clone, Copy, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+/// The form of a member name used for uniqueness: ASCII lowercase, surrounding whitespace
+/// trimmed and inner whitespace collapsed to a single space, so "Dotters" and "dotters " collide.
+pub fn normalize_name(name: &MemberName) -> MemberName {
+    let mut normalized = Vec::with_capacity(name.len());
+    for word in name.split(|byte| byte.is_ascii_whitespace()).filter(|word| !word.is_empty()) {
+        if !normalized.is_empty() {
+            normalized.push(b' ');
+        }
+        normalized.extend(word.iter().map(|byte| byte.to_ascii_lowercase()));
+    }
+    // never longer than the input
+    MemberName::truncate_from(normalized)
+}
+
+/// Maximum number of curators
+pub const MAX_CURATORS: u32 = 5;
+
+/// Notified after the status or level of a member changes, e.g. to recompute the services the
+/// member must provide
+pub trait OnMemberChanged<AccountId> {
+    fn on_member_changed(member_id: &AccountId);
+    /// Upper bound of the weight of `on_member_changed`, charged by the calls that trigger it
+    fn on_member_changed_weight() -> Weight;
+}
+
+impl<AccountId> OnMemberChanged<AccountId> for () {
+    fn on_member_changed(_member_id: &AccountId) {}
+    fn on_member_changed_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Locks members without a curator, e.g. when an alert escalates. Implemented by this pallet,
+/// used by the alert escalation in `pallet-ibp-alert`.
+pub trait MemberEnforcement<AccountId> {
+    /// Lock `member_id` until a curator unlocks it, the audit log records no actor
+    fn lock(member_id: &AccountId, reason: StatusReason) -> frame_support::dispatch::DispatchResult;
+    /// Upper bound of the weight of `lock`, the `OnMemberChanged` notification included
+    fn lock_weight() -> Weight;
+}
+
+impl<AccountId> MemberEnforcement<AccountId> for () {
+    fn lock(_member_id: &AccountId, _reason: StatusReason) -> frame_support::dispatch::DispatchResult {
+        Ok(())
+    }
+    fn lock_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum MemberStatus {
     None = 0,
     Locked = 1,
@@ -82,7 +137,73 @@ pub enum MemberStatus {
     Deleted = 9,
 }
 
-#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+/// Why a status changed: a short code such as `sla-breach`, or a note from the curator
+pub type StatusReason = BoundedVec<u8, ConstU32<64>>;
+
+/// What moves a member from one status to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberAction {
+    Chill,
+    Unchill,
+    Lock,
+    Unlock,
+    Delete,
+    Undelete,
+}
+
+/// Every allowed member status transition, as `(from, action, to)`. The calls changing the
+/// status of a member all go through this table, anything else is an `InvalidStatusTransition`.
+pub const MEMBER_TRANSITIONS: &[(MemberStatus, MemberAction, MemberStatus)] = &[
+    (MemberStatus::Active, MemberAction::Chill, MemberStatus::Chilled),
+    (MemberStatus::Chilled, MemberAction::Unchill, MemberStatus::Active),
+    (MemberStatus::None, MemberAction::Lock, MemberStatus::Locked),
+    (MemberStatus::Active, MemberAction::Lock, MemberStatus::Locked),
+    (MemberStatus::Chilled, MemberAction::Lock, MemberStatus::Locked),
+    (MemberStatus::Locked, MemberAction::Unlock, MemberStatus::Active),
+    (MemberStatus::None, MemberAction::Delete, MemberStatus::Deleted),
+    (MemberStatus::Locked, MemberAction::Delete, MemberStatus::Deleted),
+    (MemberStatus::Active, MemberAction::Delete, MemberStatus::Deleted),
+    (MemberStatus::Chilled, MemberAction::Delete, MemberStatus::Deleted),
+    // restored members come back locked, as when registered
+    (MemberStatus::Deleted, MemberAction::Undelete, MemberStatus::Locked),
+];
+
+impl MemberStatus {
+    /// The status `action` leads to from this one, `None` when `MEMBER_TRANSITIONS` does not allow it
+    pub fn transition(self, action: MemberAction) -> Option<MemberStatus> {
+        MEMBER_TRANSITIONS
+            .iter()
+            .find(|(from, allowed, _)| *from == self && *allowed == action)
+            .map(|(_, _, to)| *to)
+    }
+}
+
+/// A change recorded in a member's audit log, with the old and new values where there are any
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum AuditAction {
+    Registered { name: MemberName },
+    Renamed { old: MemberName, new: MemberName },
+    StatusChanged { old: MemberStatus, new: MemberStatus },
+    LevelChanged { old: MembershipLevel, new: MembershipLevel },
+    MonitorAssigned,
+    MonitorRemoved,
+    CuratorAssigned,
+    CuratorRemoved,
+}
+
+/// An entry of a member's audit log
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct AuditEntry<AccountId, BlockNumber> {
+    /// Block the change was made in
+    pub block_number: BlockNumber,
+    /// Account that made the change, `None` for root
+    pub actor: Option<AccountId>,
+    pub action: AuditAction,
+    /// Why the change was made, given for status changes
+    pub reason: Option<StatusReason>,
+}
+
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum MembershipLevel {
     None = 0,
     One = 1,
@@ -111,15 +232,25 @@ pub mod pallet {
     // Import various useful types required by all FRAME pallets.
     use super::*;
     use frame_support::pallet_prelude::*;
+    use frame_support::{
+        sp_runtime::traits::{Saturating, Zero},
+        traits::{Currency, OnUnbalanced, ReservableCurrency},
+    };
     use frame_system::pallet_prelude::*;
     // import AccountId from frame_system
     // use frame_system::Config as SystemConfig;
-    extern crate alloc;
-    use alloc::vec::Vec;
+
+    /// The in-code storage version.
+    /// v1: added the `MemberNames` index
+    /// v2: added `SessionLength`, moved here from the alert pallet
+    /// v3: added `MemberCount`
+    /// v4: added `ActiveMonitorCount`
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
     // (`Call`s) in this pallet.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -134,10 +265,41 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// A type representing the weights required by the dispatchables of this pallet.
         type WeightInfo: WeightInfo;
+        /// Session length in blocks when the genesis config does not set one
+        #[pallet::constant]
+        type DefaultSessionLength: Get<BlockNumberFor<Self>>;
+        /// Called after a member is registered or its status or level changes
+        type OnMemberChanged: OnMemberChanged<Self::AccountId>;
+        /// Audit entries kept per member, the oldest are dropped first
+        #[pallet::constant]
+        type MaxAuditEntries: Get<u32>;
+        /// Currency the member bonds are reserved in
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Blocks a deleted member waits before its bond can be withdrawn
+        #[pallet::constant]
+        type UnbondingPeriod: Get<BlockNumberFor<Self>>;
+        /// Destination of slashed bonds, `()` burns them
+        type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+        /// Maximum number of registered members, deleted ones included.
+        /// Bounds the work of anything iterating all members, e.g. service assignments.
+        #[pallet::constant]
+        type MaxMembers: Get<u32>;
         // type MemberId: Parameter + Member + MaybeSerializeDeserialize + Default + MaxEncodedLen + TypeInfo;
     }
 
     pub type MemberIdOf<T> = <T as frame_system::Config>::AccountId;
+    pub type AuditEntryOf<T> = AuditEntry<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type NegativeImbalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+    /// A bond waiting out the unbonding period after its member was deleted
+    #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+    pub struct UnbondingBond<Balance, BlockNumber> {
+        pub amount: Balance,
+        /// First block the bond can be withdrawn at
+        pub unlock_at: BlockNumber,
+    }
 
     // pub type MemberData = (MemberStatus, MembershipLevel);
     #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -187,6 +349,33 @@ pub mod pallet {
     #[pallet::getter(fn members)]
     pub type Members<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, MemberData<T>, ValueQuery>;
 
+    /// Why the status of each member last changed
+    #[pallet::storage]
+    #[pallet::getter(fn member_status_reason)]
+    pub type MemberStatusReasons<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, StatusReason, OptionQuery>;
+
+    /// The most recent changes made to each member, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn audit_log)]
+    pub type MemberAuditLog<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<AuditEntryOf<T>, T::MaxAuditEntries>, ValueQuery>;
+
+    /// Bond required at each membership level, unset levels require none
+    #[pallet::storage]
+    #[pallet::getter(fn level_bond)]
+    pub type LevelBonds<T: Config> = StorageMap<_, Twox64Concat, MembershipLevel, BalanceOf<T>, ValueQuery>;
+
+    /// Bond currently reserved from each member
+    #[pallet::storage]
+    #[pallet::getter(fn member_bond)]
+    pub type MemberBonds<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Bonds of deleted members, still reserved until withdrawn
+    #[pallet::storage]
+    #[pallet::getter(fn unbonding)]
+    pub type Unbonding<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, UnbondingBond<BalanceOf<T>, BlockNumberFor<T>>, OptionQuery>;
+
     /// Curators are allowed to update the status and level of a member and service
     #[pallet::storage]
     #[pallet::getter(fn curators)] // getter function for the storage item
@@ -200,20 +389,87 @@ pub mod pallet {
     #[pallet::storage]
     pub type CuratorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
-    // /// GenesisConfig - TODO move this to governanace!
-    // #[pallet::genesis_config]
-    // pub struct GenesisConfig<T: Config> {
-    //     pub members: Vec<(T::AccountId, MemberData<T>)>,
-    //     pub curators: Vec<T::AccountId>,
-    // }
-
-    // #[pallet::genesis_build]
-    // impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-    //     fn build(&self) {
-    //         // add ALICE as the first curator
-    //         Curators::<T>::insert(&self.curators[0], true);
-    //     }
-    // }
+    /// Number of monitors whose membership is active, kept up to date as monitors are assigned
+    /// and removed and as their members change status
+    #[pallet::storage]
+    pub type ActiveMonitorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Number of entries in `Members`, at most `MaxMembers`
+    #[pallet::storage]
+    pub type MemberCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Reverse index of member names, keyed by the normalized name (see [`normalize_name`]).
+    /// Deleted members release their name.
+    #[pallet::storage]
+    #[pallet::getter(fn member_names)]
+    pub type MemberNames<T: Config> = StorageMap<_, Blake2_128Concat, MemberName, T::AccountId, OptionQuery>;
+
+    /// Number of blocks per session, IBP housekeeping runs at session boundaries
+    #[pallet::storage]
+    #[pallet::getter(fn session_length)]
+    pub type SessionLength<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery, T::DefaultSessionLength>;
+
+    /// GenesisConfig - TODO move this to governanace!
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Initial members: (account, name, status, level)
+        pub members: Vec<(T::AccountId, MemberName, MemberStatus, MembershipLevel)>,
+        /// Initial curators
+        pub curators: Vec<T::AccountId>,
+        /// Initial monitors, each must also be listed in `members`
+        pub monitors: Vec<T::AccountId>,
+        /// Bond required at each membership level, genesis members are not bonded
+        pub level_bonds: Vec<(MembershipLevel, BalanceOf<T>)>,
+        /// Session length in blocks, `DefaultSessionLength` when unset
+        pub session_length: Option<BlockNumberFor<T>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            assert!(self.members.len() as u32 <= T::MaxMembers::get(), "too many members in genesis");
+            for (account_id, name, status, level) in &self.members {
+                assert!(!Members::<T>::contains_key(account_id), "duplicate member in genesis");
+                let normalized = normalize_name(name);
+                assert!(!normalized.is_empty(), "empty member name in genesis");
+                assert!(!MemberNames::<T>::contains_key(&normalized), "duplicate member name in genesis");
+                if *status != MemberStatus::Deleted {
+                    MemberNames::<T>::insert(normalized, account_id);
+                }
+                let member_data = MemberData::<T> {
+                    id: Some(account_id.clone()),
+                    name: name.clone(),
+                    status: *status,
+                    level: *level,
+                };
+                Members::<T>::insert(account_id, member_data);
+            }
+            MemberCount::<T>::put(self.members.len() as u32);
+            assert!(self.curators.len() as u32 <= MAX_CURATORS, "too many curators in genesis");
+            for account_id in &self.curators {
+                assert!(!Curators::<T>::contains_key(account_id), "duplicate curator in genesis");
+                Curators::<T>::insert(account_id, true);
+            }
+            CuratorCount::<T>::put(self.curators.len() as u32);
+            for account_id in &self.monitors {
+                // only members can be monitors
+                assert!(Members::<T>::contains_key(account_id), "monitor is not a member in genesis");
+                assert!(!Monitors::<T>::contains_key(account_id), "duplicate monitor in genesis");
+                Monitors::<T>::insert(account_id, true);
+                if Members::<T>::get(account_id).status == MemberStatus::Active {
+                    ActiveMonitorCount::<T>::mutate(|count| *count += 1);
+                }
+            }
+            for (level, amount) in &self.level_bonds {
+                LevelBonds::<T>::insert(level, amount);
+            }
+            if let Some(session_length) = self.session_length {
+                assert!(!session_length.is_zero(), "session length must not be zero");
+                SessionLength::<T>::put(session_length);
+            }
+        }
+    }
 
     /// Events that functions in this pallet can emit.
     ///
@@ -229,7 +485,8 @@ pub mod pallet {
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         MemberRegistered(T::AccountId),
-        MemberStatusUpdated(T::AccountId, MemberStatus, MemberStatus),
+        /// (member, previous status, status, reason)
+        MemberStatusUpdated(T::AccountId, MemberStatus, MemberStatus, StatusReason),
         MembershipLevelUpdated(T::AccountId, MembershipLevel, MembershipLevel),
         MemberCreated(T::AccountId),
         // MemberLocked(T::AccountId),
@@ -243,6 +500,16 @@ pub mod pallet {
         // events for monitor
         MonitorAssigned(T::AccountId),
         MonitorRemoved(T::AccountId),
+        /// (level, bond)
+        LevelBondSet(MembershipLevel, BalanceOf<T>),
+        /// (member, amount added to its bond)
+        BondReserved(T::AccountId, BalanceOf<T>),
+        /// (member, bond, first block it can be withdrawn at)
+        BondUnbonding(T::AccountId, BalanceOf<T>, BlockNumberFor<T>),
+        /// (member, amount returned to its free balance)
+        BondReleased(T::AccountId, BalanceOf<T>),
+        /// (member, amount slashed, reason)
+        BondSlashed(T::AccountId, BalanceOf<T>, StatusReason),
     }
 
     /// Errors that can be returned by this pallet.
@@ -261,6 +528,8 @@ pub mod pallet {
         StorageOverflow,
         MemberAlreadyExists,
         MemberNotFound,
+        /// `MaxMembers` members are registered
+        TooManyMembers,
         NameAlreadyExists,
         InvalidStatusTransition,
         NotACurator,
@@ -270,6 +539,18 @@ pub mod pallet {
         NotAMonitor,
         MonitorAlreadyExists,
         MonitorLimitReached,
+        /// The member name is empty once normalized
+        InvalidMemberName,
+        /// The free balance does not cover the bond of the membership level
+        InsufficientBalance,
+        /// There is no bond waiting to be withdrawn
+        NotUnbonding,
+        /// The unbonding period has not passed yet
+        UnbondingNotFinished,
+        /// Only active members change level
+        MemberNotActive,
+        /// The member is leaving, its bond is unbonding
+        MemberUnbonding,
     }
 
     /// The pallet's dispatchable functions ([`Call`]s).
@@ -291,38 +572,45 @@ pub mod pallet {
         /// The member will be level 0 and status locked
         /// Only curators can update the level or status of a member
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn register_member( origin: OriginFor<T>, name: MemberName ) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::register_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn register_member(origin: OriginFor<T>, name: MemberName) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             ensure!(!Members::<T>::contains_key(&sender), Error::<T>::MemberAlreadyExists);
+            let member_count = MemberCount::<T>::get();
+            ensure!(member_count < T::MaxMembers::get(), Error::<T>::TooManyMembers);
             // Ensure the member name is unique
-            let is_name_unique = !Members::<T>::iter().any(|(_, member_data)| {
-                member_data.name == name
-            });
-            ensure!(is_name_unique, Error::<T>::NameAlreadyExists);
+            let normalized = Self::ensure_name_available(&name, None)?;
             let member_id = sender.clone();
             let level = MembershipLevel::None;
             let status = MemberStatus::Locked;
-            let member_data = MemberData { id: Some(member_id), name, status, level };
+            Self::reserve_bond(&sender, level)?;
+            let member_data = MemberData { id: Some(member_id), name: name.clone(), status, level };
             Members::<T>::insert(&sender, member_data);
+            MemberCount::<T>::put(member_count + 1);
+            MemberNames::<T>::insert(normalized, &sender);
+            Self::audit(&sender, Some(&sender), AuditAction::Registered { name }, None);
+            T::OnMemberChanged::on_member_changed(&sender);
             Self::deposit_event(Event::MemberRegistered(sender));
             Ok(())
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::set_member_name())]
         pub fn set_member_name(origin: OriginFor<T>, name: MemberName) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             ensure!(Members::<T>::contains_key(&sender), Error::<T>::MemberNotFound);
-            // Ensure the member name is unique
-            let is_name_unique = !Members::<T>::iter().any(|(_, member_data)| {
-                member_data.name == name
-            });
-            ensure!(is_name_unique, Error::<T>::NameAlreadyExists);
+            // Ensure the member name is unique, the member may change the case of its own name
+            let normalized = Self::ensure_name_available(&name, Some(&sender))?;
 
             Members::<T>::try_mutate(&sender, |data_option| -> DispatchResult {
                 let data = data_option; // .as_mut(); //.ok_or(Error::<T, I>::MemberNotFound)?;
-                data.name = name.clone();
+                // deleted members do not hold a name in the index
+                if data.status != MemberStatus::Deleted {
+                    MemberNames::<T>::remove(normalize_name(&data.name));
+                    MemberNames::<T>::insert(&normalized, &sender);
+                }
+                let old = core::mem::replace(&mut data.name, name.clone());
+                Self::audit(&sender, Some(&sender), AuditAction::Renamed { old, new: name }, None);
                 Ok(())
             })?;
             Ok(())
@@ -330,166 +618,125 @@ pub mod pallet {
 
         /// voluntary action by the member
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
-        pub fn chill_member(origin: OriginFor<T>) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::chill_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn chill_member(origin: OriginFor<T>, reason: StatusReason) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
-            ensure!(Members::<T>::contains_key(&sender), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&sender, |data_option| -> DispatchResult {
-                // check if the member exists
-                let data = data_option; // .as_mut(); //.ok_or(Error::<T, I>::MemberNotFound)?;
-                ensure!(data.status != MemberStatus::Deleted, Error::<T>::InvalidStatusTransition);
-                ensure!(data.status != MemberStatus::Locked, Error::<T>::InvalidStatusTransition);
-                let prev_status = data.status;
-                data.status = MemberStatus::Chilled;
-                Self::deposit_event(Event::MemberStatusUpdated(sender.clone(), prev_status, MemberStatus::Chilled));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &sender, MemberAction::Chill, reason)
         }
         
         /// voluntary action by the member
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
-        pub fn unchill_member(origin: OriginFor<T>) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::unchill_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn unchill_member(origin: OriginFor<T>, reason: StatusReason) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
-            ensure!(Members::<T>::contains_key(&sender), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&sender, |data_option| -> DispatchResult {
-                // check if the member exists
-                let data = data_option; // .as_mut(); //.ok_or(Error::<T, I>::MemberNotFound)?;
-                ensure!(data.status == MemberStatus::Chilled, Error::<T>::InvalidStatusTransition);
-                let prev_status = data.status;
-                data.status = MemberStatus::Active;
-                Self::deposit_event(Event::MemberStatusUpdated(sender.clone(), prev_status, MemberStatus::Active));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &sender, MemberAction::Unchill, reason)
         }
         
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
-        pub fn lock_member(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::lock_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn lock_member(origin: OriginFor<T>, account_id: T::AccountId, reason: StatusReason) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
-            ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&account_id, |data_option| -> DispatchResult {
-                // check if the member exists
-                let data = data_option; // .as_mut(); //.ok_or(Error::<T, I>::MemberNotFound)?;
-                let prev_status = data.status;
-                ensure!(data.status != MemberStatus::Deleted, Error::<T>::InvalidStatusTransition);
-                data.status = MemberStatus::Locked;
-                Self::deposit_event(Event::MemberStatusUpdated(account_id.clone(), prev_status, MemberStatus::Locked));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &account_id, MemberAction::Lock, reason)
         }
 
         #[pallet::call_index(20)]
-        #[pallet::weight(10_000)]
-        pub fn unlock_member(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::unlock_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn unlock_member(origin: OriginFor<T>, account_id: T::AccountId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
-            ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&account_id, |data_opt| -> DispatchResult {
-                //ensure!(status != None, Error::<T>::MemberNotFound);
-                let data = data_opt; // .as_mut(); //.ok_or(Error::<T>::MemberNotFound)?;
-                ensure!(data.status == MemberStatus::Locked, Error::<T>::InvalidStatusTransition);
-                let prev_status = data.status;
-                data.status = MemberStatus::Active;
-                Self::deposit_event(Event::MemberStatusUpdated(account_id.clone(), prev_status, MemberStatus::Active));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &account_id, MemberAction::Unlock, reason)
         }
 
         #[pallet::call_index(30)]
-        #[pallet::weight(10_000)]
-        pub fn delete_member(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::delete_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn delete_member(origin: OriginFor<T>, account_id: T::AccountId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
-            ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&account_id, |data_opt| -> DispatchResult {
-                let data = data_opt; // .as_mut(); // .ok_or(Error::<T>::MemberNotFound)?;
-                let prev_status = data.status;
-                data.status = MemberStatus::Deleted;
-                Self::deposit_event(Event::MemberStatusUpdated(account_id.clone(), prev_status, MemberStatus::Deleted));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &account_id, MemberAction::Delete, reason)
         }
 
         #[pallet::call_index(40)]
-        #[pallet::weight(10_000)]
-        pub fn undelete_member(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::undelete_member().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn undelete_member(origin: OriginFor<T>, account_id: T::AccountId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
-            ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
-            Members::<T>::try_mutate(&account_id, |data_opt| -> DispatchResult {            
-                let data = data_opt; // .as_mut(); // .ok_or(Error::<T>::MemberNotFound)?;
-                ensure!(data.status == MemberStatus::Deleted, Error::<T>::InvalidStatusTransition);
-                let prev_status = data.status;
-                data.status = MemberStatus::Deleted;
-                Self::deposit_event(Event::MemberStatusUpdated(account_id.clone(), prev_status, MemberStatus::Deleted));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_member(Some(&sender), &account_id, MemberAction::Undelete, reason)
         }
 
+        /// Only active members change level, a deleted member would otherwise bond again
         #[pallet::call_index(50)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_member_level().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
         pub fn update_member_level(origin: OriginFor<T>, account_id: T::AccountId, level: MembershipLevel) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
             ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
+            ensure!(!Unbonding::<T>::contains_key(&account_id), Error::<T>::MemberUnbonding);
             Members::<T>::try_mutate(&account_id, |data_opt| -> DispatchResult {
                 let data = data_opt; // .as_mut(); // .ok_or(Error::<T>::MemberNotFound)?;
+                ensure!(data.status == MemberStatus::Active, Error::<T>::MemberNotActive);
                 let prev_level = data.level.clone();
+                // upgrades top the bond up, downgrades keep it until the member leaves
+                Self::reserve_bond(&account_id, level)?;
                 data.level = level.clone();
                 Self::deposit_event(Event::MembershipLevelUpdated(account_id.clone(), prev_level.clone(), level.clone()));
+                Self::audit(&account_id, Some(&sender), AuditAction::LevelChanged { old: prev_level, new: level }, None);
                 Ok(())
             })?;
+            T::OnMemberChanged::on_member_changed(&account_id);
             Ok(())
         }
 
         #[pallet::call_index(60)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::assign_monitor())]
         pub fn assign_monitor(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only members can be monitors
             ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
             // only curators can assign monitors
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
+            ensure!(!Monitors::<T>::contains_key(&account_id), Error::<T>::MonitorAlreadyExists);
             Monitors::<T>::insert(&account_id, true);
+            if Members::<T>::get(&account_id).status == MemberStatus::Active {
+                ActiveMonitorCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            }
+            Self::audit(&account_id, Some(&sender), AuditAction::MonitorAssigned, None);
             Self::deposit_event(Event::MonitorAssigned(account_id));
             Ok(())
         }
 
         #[pallet::call_index(70)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_monitor())]
         pub fn remove_monitor(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
             ensure!(Monitors::<T>::contains_key(&account_id), Error::<T>::NotAMonitor);
             Monitors::<T>::remove(&account_id);
+            if Members::<T>::get(&account_id).status == MemberStatus::Active {
+                ActiveMonitorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+            }
+            Self::audit(&account_id, Some(&sender), AuditAction::MonitorRemoved, None);
             Self::deposit_event(Event::MonitorRemoved(account_id));
             Ok(())
         }
 
         #[pallet::call_index(80)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::assign_curator())]
         pub fn assign_curator(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
             // TODO do this via governance
             ensure_root(origin)?;
             // ensure the sender is ALICE
             ensure!(!Curators::<T>::contains_key(&account_id), Error::<T>::CuratorAlreadyExists);
-            ensure!(CuratorCount::<T>::get() < 5, Error::<T>::CuratorLimitReached);
+            ensure!(CuratorCount::<T>::get() < MAX_CURATORS, Error::<T>::CuratorLimitReached);
             Curators::<T>::insert(&account_id, true);
-            // CuratorCount::put(CuratorCount::get() + 1);
+            CuratorCount::<T>::mutate(|count| *count += 1);
+            Self::audit(&account_id, None, AuditAction::CuratorAssigned, None);
             Self::deposit_event(Event::CuratorAssigned(account_id));
             Ok(())
         }
 
         #[pallet::call_index(90)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_curator())]
         pub fn remove_curator(origin: OriginFor<T>, account_id: T::AccountId) -> DispatchResult {
             ensure_root(origin)?;
 
@@ -497,10 +744,240 @@ pub mod pallet {
             ensure!(CuratorCount::<T>::get() > 1, Error::<T>::CannotRemoveLastCurator);
 
             Curators::<T>::remove(&account_id);
-            // CuratorCount::put(CuratorCount::get() - 1);
+            CuratorCount::<T>::mutate(|count| *count -= 1);
+            Self::audit(&account_id, None, AuditAction::CuratorRemoved, None);
             Self::deposit_event(Event::CuratorRemoved(account_id));
             Ok(())
         }
 
+        /// Set the bond required at `level`, members already at the level are topped up on their
+        /// next level change
+        #[pallet::call_index(100)]
+        #[pallet::weight(T::WeightInfo::set_level_bond())]
+        pub fn set_level_bond(origin: OriginFor<T>, level: MembershipLevel, amount: BalanceOf<T>) -> DispatchResult {
+            // TODO do this via governance
+            ensure_root(origin)?;
+            LevelBonds::<T>::insert(level, amount);
+            Self::deposit_event(Event::LevelBondSet(level, amount));
+            Ok(())
+        }
+
+        /// Return the bond of a deleted member once the unbonding period has passed
+        #[pallet::call_index(110)]
+        #[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+        pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let unbonding = Unbonding::<T>::get(&sender).ok_or(Error::<T>::NotUnbonding)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now >= unbonding.unlock_at, Error::<T>::UnbondingNotFinished);
+            Unbonding::<T>::remove(&sender);
+            T::Currency::unreserve(&sender, unbonding.amount);
+            Self::deposit_event(Event::BondReleased(sender, unbonding.amount));
+            Ok(())
+        }
+
+        /// Slash up to `amount` from the bond of a member, including a bond that is unbonding
+        #[pallet::call_index(120)]
+        #[pallet::weight(T::WeightInfo::slash_bond())]
+        pub fn slash_bond(
+            origin: OriginFor<T>,
+            account_id: T::AccountId,
+            amount: BalanceOf<T>,
+            reason: StatusReason,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(Curators::<T>::contains_key(&sender), Error::<T>::NotACurator);
+            ensure!(Members::<T>::contains_key(&account_id), Error::<T>::MemberNotFound);
+            Self::slash_member_bond(&account_id, amount, reason);
+            Ok(())
+        }
+
+        /// voluntary action by the member: leave the membership, releasing the name and starting
+        /// the unbonding period. A curator can undelete the member to bring it back.
+        #[pallet::call_index(130)]
+        #[pallet::weight(T::WeightInfo::leave_membership().saturating_add(T::OnMemberChanged::on_member_changed_weight()))]
+        pub fn leave_membership(origin: OriginFor<T>, reason: StatusReason) -> DispatchResult {
+            let sender: T::AccountId = ensure_signed(origin)?;
+            Self::transition_member(Some(&sender), &sender, MemberAction::Delete, reason)
+        }
+
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The member currently holding `name`, compared in normalized form
+        pub fn member_by_name(name: &MemberName) -> Option<T::AccountId> {
+            MemberNames::<T>::get(normalize_name(name))
+        }
+
+        /// Number of monitors whose membership is active, e.g. to size an alert quorum
+        pub fn active_monitor_count() -> u32 {
+            ActiveMonitorCount::<T>::get()
+        }
+
+        /// Returns the normalized name if no other member holds it.
+        /// `owner` may already hold the name, e.g. when only the case changes.
+        fn ensure_name_available(
+            name: &MemberName,
+            owner: Option<&T::AccountId>,
+        ) -> Result<MemberName, Error<T>> {
+            let normalized = normalize_name(name);
+            ensure!(!normalized.is_empty(), Error::<T>::InvalidMemberName);
+            match MemberNames::<T>::get(&normalized) {
+                Some(holder) if Some(&holder) != owner => Err(Error::<T>::NameAlreadyExists),
+                _ => Ok(normalized),
+            }
+        }
+
+        /// Slash up to `amount` from the bond of `member_id`, then from its unbonding bond, and
+        /// send it to `T::Slashed`. SLA policies in other pallets slash through this.
+        /// Returns the amount slashed.
+        pub fn slash_member_bond(member_id: &T::AccountId, amount: BalanceOf<T>, reason: StatusReason) -> BalanceOf<T> {
+            let bonded = MemberBonds::<T>::get(member_id);
+            let from_bond = amount.min(bonded);
+            let from_unbonding = Unbonding::<T>::get(member_id)
+                .map_or(Zero::zero(), |unbonding| amount.saturating_sub(from_bond).min(unbonding.amount));
+            let (imbalance, unslashed) = T::Currency::slash_reserved(member_id, from_bond.saturating_add(from_unbonding));
+            // the reserve can be short of the bond if another pallet slashed the account
+            let slashed = from_bond.saturating_add(from_unbonding).saturating_sub(unslashed);
+            MemberBonds::<T>::insert(member_id, bonded.saturating_sub(from_bond));
+            if !from_unbonding.is_zero() {
+                Unbonding::<T>::mutate_exists(member_id, |unbonding| {
+                    if let Some(bond) = unbonding {
+                        bond.amount = bond.amount.saturating_sub(from_unbonding);
+                        if bond.amount.is_zero() {
+                            *unbonding = None;
+                        }
+                    }
+                });
+            }
+            T::Slashed::on_unbalanced(imbalance);
+            Self::deposit_event(Event::BondSlashed(member_id.clone(), slashed, reason));
+            slashed
+        }
+
+        /// Reserve whatever the bond of `member_id` is short of the bond of `level`
+        fn reserve_bond(member_id: &T::AccountId, level: MembershipLevel) -> DispatchResult {
+            let bonded = MemberBonds::<T>::get(member_id);
+            let shortfall = LevelBonds::<T>::get(level).saturating_sub(bonded);
+            if shortfall.is_zero() {
+                return Ok(());
+            }
+            T::Currency::reserve(member_id, shortfall).map_err(|_| Error::<T>::InsufficientBalance)?;
+            MemberBonds::<T>::insert(member_id, bonded.saturating_add(shortfall));
+            Self::deposit_event(Event::BondReserved(member_id.clone(), shortfall));
+            Ok(())
+        }
+
+        /// Move the bond of a deleted member to `Unbonding`, restarting the unbonding period
+        fn unbond(member_id: &T::AccountId) {
+            let amount = MemberBonds::<T>::take(member_id);
+            let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UnbondingPeriod::get());
+            let amount = Unbonding::<T>::get(member_id).map_or(amount, |unbonding| unbonding.amount.saturating_add(amount));
+            if amount.is_zero() {
+                return;
+            }
+            Unbonding::<T>::insert(member_id, UnbondingBond { amount, unlock_at });
+            Self::deposit_event(Event::BondUnbonding(member_id.clone(), amount, unlock_at));
+        }
+
+        /// Bond an undeleted member again: its unbonding bond, then whatever its level requires
+        fn rebond(member_id: &T::AccountId, level: MembershipLevel) -> DispatchResult {
+            if let Some(unbonding) = Unbonding::<T>::take(member_id) {
+                MemberBonds::<T>::mutate(member_id, |bond| *bond = bond.saturating_add(unbonding.amount));
+            }
+            Self::reserve_bond(member_id, level)
+        }
+
+        /// Append to the audit log of `member_id`, dropping the oldest entry once the log is full
+        fn audit(member_id: &T::AccountId, actor: Option<&T::AccountId>, action: AuditAction, reason: Option<StatusReason>) {
+            let entry = AuditEntry {
+                block_number: frame_system::Pallet::<T>::block_number(),
+                actor: actor.cloned(),
+                action,
+                reason,
+            };
+            MemberAuditLog::<T>::mutate(member_id, |log| {
+                if !log.is_empty() && log.len() as u32 >= T::MaxAuditEntries::get() {
+                    log.remove(0);
+                }
+                // only fails when `MaxAuditEntries` is zero
+                let _ = log.try_push(entry);
+            });
+        }
+
+        /// Move a member along `MEMBER_TRANSITIONS` on behalf of `actor`, recording why.
+        /// Without an actor the change was made by the runtime.
+        fn transition_member(
+            actor: Option<&T::AccountId>,
+            account_id: &T::AccountId,
+            action: MemberAction,
+            reason: StatusReason,
+        ) -> DispatchResult {
+            ensure!(Members::<T>::contains_key(account_id), Error::<T>::MemberNotFound);
+            Members::<T>::try_mutate(account_id, |data| -> DispatchResult {
+                let prev_status = data.status;
+                let status = prev_status.transition(action).ok_or(Error::<T>::InvalidStatusTransition)?;
+                match action {
+                    // release the name and start unbonding
+                    MemberAction::Delete => {
+                        MemberNames::<T>::remove(normalize_name(&data.name));
+                        Self::unbond(account_id);
+                    },
+                    // reclaim the name, it may have been taken while the member was deleted, and
+                    // the bond of its level
+                    MemberAction::Undelete => {
+                        let normalized = Self::ensure_name_available(&data.name, None)?;
+                        MemberNames::<T>::insert(normalized, account_id);
+                        Self::rebond(account_id, data.level)?;
+                    },
+                    _ => {},
+                }
+                data.status = status;
+                // a monitor counts towards `ActiveMonitorCount` while its membership is active
+                if prev_status != status && Monitors::<T>::contains_key(account_id) {
+                    if status == MemberStatus::Active {
+                        ActiveMonitorCount::<T>::mutate(|count| *count = count.saturating_add(1));
+                    } else if prev_status == MemberStatus::Active {
+                        ActiveMonitorCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+                    }
+                }
+                match action {
+                    MemberAction::Chill => Self::deposit_event(Event::MemberChilled(account_id.clone())),
+                    MemberAction::Unchill => Self::deposit_event(Event::MemberUnChilled(account_id.clone())),
+                    MemberAction::Delete => Self::deposit_event(Event::MemberDeleted(account_id.clone())),
+                    _ => {},
+                }
+                Self::deposit_event(Event::MemberStatusUpdated(account_id.clone(), prev_status, status, reason.clone()));
+                Self::audit(
+                    account_id,
+                    actor,
+                    AuditAction::StatusChanged { old: prev_status, new: status },
+                    Some(reason.clone()),
+                );
+                Ok(())
+            })?;
+            MemberStatusReasons::<T>::insert(account_id, reason);
+            T::OnMemberChanged::on_member_changed(account_id);
+            Ok(())
+        }
+    }
+
+    impl<T: Config> MemberEnforcement<T::AccountId> for Pallet<T> {
+        fn lock(member_id: &T::AccountId, reason: StatusReason) -> DispatchResult {
+            Self::transition_member(None, member_id, MemberAction::Lock, reason)
+        }
+
+        fn lock_weight() -> Weight {
+            T::WeightInfo::lock_member().saturating_add(T::OnMemberChanged::on_member_changed_weight())
+        }
+    }
+
+    /// The session length from storage, the period for `pallet_session::PeriodicSessions`
+    pub struct IbpSessionLength<T>(PhantomData<T>);
+
+    impl<T: Config> Get<BlockNumberFor<T>> for IbpSessionLength<T> {
+        fn get() -> BlockNumberFor<T> {
+            SessionLength::<T>::get()
+        }
     }
 }
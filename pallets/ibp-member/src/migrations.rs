@@ -0,0 +1,154 @@
+//! Storage migrations for the member pallet.
+
+use super::*;
+use frame_support::{
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+#[cfg(feature = "try-runtime")]
+use frame_support::{ensure, sp_runtime::{traits::Zero, TryRuntimeError}};
+
+pub mod v1 {
+    use super::*;
+
+    /// Build the `MemberNames` index from the existing members.
+    ///
+    /// Deleted members are not indexed. When two members share a normalized name the first one
+    /// found keeps it; the other has to pick a new name with `set_member_name`.
+    pub struct BuildMemberNames<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for BuildMemberNames<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1u64;
+            let mut writes = 1u64;
+            for (account_id, member_data) in Members::<T>::iter() {
+                reads += 2;
+                if member_data.status == MemberStatus::Deleted {
+                    continue;
+                }
+                let normalized = normalize_name(&member_data.name);
+                if normalized.is_empty() || MemberNames::<T>::contains_key(&normalized) {
+                    continue;
+                }
+                MemberNames::<T>::insert(normalized, account_id);
+                writes += 1;
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            for (normalized, account_id) in MemberNames::<T>::iter() {
+                let member_data = Members::<T>::get(&account_id);
+                ensure!(member_data.id.is_some(), "indexed name points to an unknown member");
+                ensure!(normalize_name(&member_data.name) == normalized, "indexed name does not match");
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+
+    /// Seed `SessionLength` for chains that started before sessions were wired in.
+    ///
+    /// A session length already in storage is kept, otherwise `DefaultSessionLength` is written so
+    /// the session period no longer depends on the runtime default.
+    pub struct SeedSessionLength<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for SeedSessionLength<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+            if !SessionLength::<T>::exists() {
+                SessionLength::<T>::put(T::DefaultSessionLength::get());
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "storage version not updated");
+            ensure!(SessionLength::<T>::exists(), "session length not seeded");
+            ensure!(!SessionLength::<T>::get().is_zero(), "session length is zero");
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+
+    /// Count the existing members into `MemberCount`.
+    ///
+    /// A chain with more than `MaxMembers` members keeps them all, new registrations fail until
+    /// the count is back under the bound.
+    pub struct CountMembers<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for CountMembers<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                return T::DbWeight::get().reads(1);
+            }
+            let count = Members::<T>::iter_keys().count() as u64;
+            MemberCount::<T>::put(count as u32);
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1 + count, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "storage version not updated");
+            ensure!(
+                MemberCount::<T>::get() == Members::<T>::iter_keys().count() as u32,
+                "member count does not match the members"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v4 {
+    use super::*;
+
+    /// Count the monitors whose membership is active into `ActiveMonitorCount`.
+    pub struct CountActiveMonitors<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for CountActiveMonitors<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 3 {
+                return T::DbWeight::get().reads(1);
+            }
+            let monitors: Vec<T::AccountId> = Monitors::<T>::iter_keys().collect();
+            let active = monitors
+                .iter()
+                .filter(|account_id| Members::<T>::get(account_id).status == MemberStatus::Active)
+                .count() as u32;
+            ActiveMonitorCount::<T>::put(active);
+            StorageVersion::new(4).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1 + 2 * monitors.len() as u64, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 4, "storage version not updated");
+            let active = Monitors::<T>::iter_keys()
+                .filter(|account_id| Members::<T>::get(account_id).status == MemberStatus::Active)
+                .count() as u32;
+            ensure!(ActiveMonitorCount::<T>::get() == active, "active monitor count does not match the monitors");
+            Ok(())
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{self as pallet_template, MemberStatus};
+use crate as pallet_ibp_member;
 use frame_support::{
 	derive_impl,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64, Currency, OnUnbalanced},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -16,7 +16,8 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Balances: pallet_balances,
+		IbpMember: pallet_ibp_member,
 	}
 );
 
@@ -38,7 +39,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -47,16 +48,57 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
 	type WeightInfo = ();
-  // type AccountId = u64;
-  type MemberStatus = u64;
-  type MembershipLevel = u64;
-  type MemberData = (MemberStatus, MembershipLevel);
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+impl pallet_ibp_member::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type DefaultSessionLength = ConstU64<10>;
+	type OnMemberChanged = ();
+	type MaxAuditEntries = frame_support::traits::ConstU32<3>;
+	type Currency = Balances;
+	type UnbondingPeriod = ConstU64<10>;
+	type Slashed = SlashToTreasury;
+	type MaxMembers = ConstU32<16>;
+}
+
+/// Receives slashed bonds
+pub const TREASURY: u64 = 100;
+
+pub struct SlashToTreasury;
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for SlashToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	new_test_ext_with_genesis(Default::default())
+}
+
+/// Genesis storage with the given member pallet genesis config
+pub fn new_test_ext_with_genesis(genesis: pallet_ibp_member::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	genesis.assimilate_storage(&mut storage).unwrap();
+	// accounts 1 to 9 can pay bonds
+	pallet_balances::GenesisConfig::<Test> { balances: (1..10).map(|account| (account, 1_000)).collect() }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	storage.into()
 }
@@ -1,27 +1,631 @@
-use crate::{mock::*, Error, Event, Something};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, normalize_name, ActiveMonitorCount, AuditAction, AuditEntry, CuratorCount, Error, Event,
+	GenesisConfig, MemberAction, MemberBonds, MemberCount, MemberData, MemberEnforcement, MemberName, MemberNames,
+	MemberStatus, MemberStatusReasons, Members, MembershipLevel, SessionLength, StatusReason, Unbonding,
+	UnbondingBond,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchResult,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+fn name(value: &str) -> MemberName {
+	MemberName::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+fn reason(value: &str) -> StatusReason {
+	StatusReason::try_from(value.as_bytes().to_vec()).unwrap()
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn register_member_works() {
 	new_test_ext().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(Something::<Test>::get(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		let member = Members::<Test>::get(1);
+		assert_eq!(member.id, Some(1));
+		assert_eq!(member.status, MemberStatus::Locked);
+		assert_eq!(member.level, MembershipLevel::None);
+		System::assert_last_event(Event::MemberRegistered(1).into());
+	});
+}
+
+#[test]
+fn register_member_rejects_duplicate_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_noop!(
+			IbpMember::register_member(RuntimeOrigin::signed(2), name("alice")),
+			Error::<Test>::NameAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn register_member_is_bounded_by_max_members() {
+	new_test_ext().execute_with(|| {
+		// MaxMembers is 16 in the mock
+		MemberCount::<Test>::put(15);
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_eq!(MemberCount::<Test>::get(), 16);
+		assert_noop!(
+			IbpMember::register_member(RuntimeOrigin::signed(2), name("bob")),
+			Error::<Test>::TooManyMembers
+		);
+	});
+}
+
+#[test]
+fn only_curators_can_lock_members() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_noop!(
+			IbpMember::lock_member(RuntimeOrigin::signed(2), 1, reason("sla")),
+			Error::<Test>::NotACurator
+		);
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 2));
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(2), 1, reason("onboarded")));
+		assert_ok!(IbpMember::lock_member(RuntimeOrigin::signed(2), 1, reason("sla")));
+		assert_eq!(Members::<Test>::get(1).status, MemberStatus::Locked);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn runtime_locks_members_without_actor() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		System::set_block_number(5);
+		assert_ok!(IbpMember::lock(&1, reason("ServiceDown")));
+		assert_eq!(Members::<Test>::get(1).status, MemberStatus::Locked);
+		assert_eq!(
+			IbpMember::audit_log(1).last(),
+			Some(&AuditEntry {
+				block_number: 5,
+				actor: None,
+				action: AuditAction::StatusChanged { old: MemberStatus::Active, new: MemberStatus::Locked },
+				reason: Some(reason("ServiceDown")),
+			})
+		);
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			IbpMember::lock(&1, reason("ServiceDown")),
+			Error::<Test>::InvalidStatusTransition
 		);
 	});
 }
+
+#[test]
+fn cannot_remove_last_curator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			IbpMember::remove_curator(RuntimeOrigin::root(), 1),
+			Error::<Test>::CannotRemoveLastCurator
+		);
+	});
+}
+
+#[test]
+fn names_are_normalized() {
+	assert_eq!(normalize_name(&name("Dotters")), name("dotters"));
+	assert_eq!(normalize_name(&name("  Dotters \t")), name("dotters"));
+	assert_eq!(normalize_name(&name("Dot  Ters")), name("dot ters"));
+	assert!(normalize_name(&name(" \n ")).is_empty());
+}
+
+#[test]
+fn normalized_names_collide() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("Dotters")));
+		assert_noop!(
+			IbpMember::register_member(RuntimeOrigin::signed(2), name("dotters ")),
+			Error::<Test>::NameAlreadyExists
+		);
+		assert_noop!(
+			IbpMember::register_member(RuntimeOrigin::signed(2), name("   ")),
+			Error::<Test>::InvalidMemberName
+		);
+		assert_eq!(IbpMember::member_by_name(&name("DOTTERS")), Some(1));
+	});
+}
+
+#[test]
+fn rename_releases_old_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		// a member may change the case of its own name
+		assert_ok!(IbpMember::set_member_name(RuntimeOrigin::signed(1), name("Alice")));
+		assert_eq!(Members::<Test>::get(1).name, name("Alice"));
+		assert_ok!(IbpMember::set_member_name(RuntimeOrigin::signed(1), name("bob")));
+		assert_eq!(IbpMember::member_by_name(&name("alice")), None);
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(2), name("alice")));
+		assert_noop!(
+			IbpMember::set_member_name(RuntimeOrigin::signed(2), name("Bob")),
+			Error::<Test>::NameAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn delete_releases_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		assert_eq!(IbpMember::member_by_name(&name("alice")), None);
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(2), name("Alice")));
+		// the name is taken, the member cannot be restored until it is released
+		assert_noop!(
+			IbpMember::undelete_member(RuntimeOrigin::signed(9), 1, reason("rejoined")),
+			Error::<Test>::NameAlreadyExists
+		);
+		assert_ok!(IbpMember::set_member_name(RuntimeOrigin::signed(2), name("bob")));
+		assert_ok!(IbpMember::undelete_member(RuntimeOrigin::signed(9), 1, reason("rejoined")));
+		assert_eq!(Members::<Test>::get(1).status, MemberStatus::Locked);
+		assert_eq!(IbpMember::member_by_name(&name("alice")), Some(1));
+	});
+}
+
+#[test]
+fn migration_builds_name_index() {
+	new_test_ext().execute_with(|| {
+		let members = [
+			(1, "Alice", MemberStatus::Active),
+			(2, "alice ", MemberStatus::Active),
+			(3, "Bob", MemberStatus::Locked),
+			(4, "Carol", MemberStatus::Deleted),
+		];
+		for (account_id, member_name, status) in members {
+			let member_data = MemberData::<Test> {
+				id: Some(account_id),
+				name: name(member_name),
+				status,
+				level: MembershipLevel::None,
+			};
+			Members::<Test>::insert(account_id, member_data);
+		}
+		assert_eq!(IbpMember::on_chain_storage_version(), 0);
+
+		migrations::v1::BuildMemberNames::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpMember::on_chain_storage_version(), 1);
+		let alice = MemberNames::<Test>::get(name("alice")).unwrap();
+		assert!(alice == 1 || alice == 2);
+		assert_eq!(MemberNames::<Test>::get(name("bob")), Some(3));
+		assert_eq!(MemberNames::<Test>::get(name("carol")), None);
+		assert_eq!(MemberNames::<Test>::iter().count(), 2);
+	});
+}
+
+#[test]
+fn session_length_defaults_and_is_seeded_by_migration() {
+	new_test_ext().execute_with(|| {
+		// DefaultSessionLength is 10 in the mock
+		assert_eq!(SessionLength::<Test>::get(), 10);
+		assert!(!SessionLength::<Test>::exists());
+		StorageVersion::new(1).put::<IbpMember>();
+
+		migrations::v2::SeedSessionLength::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpMember::on_chain_storage_version(), 2);
+		assert!(SessionLength::<Test>::exists());
+		assert_eq!(SessionLength::<Test>::get(), 10);
+	});
+}
+
+#[test]
+fn migration_counts_members() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(2), name("bob")));
+		MemberCount::<Test>::kill();
+		StorageVersion::new(2).put::<IbpMember>();
+
+		migrations::v3::CountMembers::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpMember::on_chain_storage_version(), 3);
+		assert_eq!(MemberCount::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn active_monitors_are_counted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(2), name("bob")));
+		// a locked monitor does not count until its membership is active
+		assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(9), 1));
+		assert_eq!(IbpMember::active_monitor_count(), 0);
+		assert_noop!(IbpMember::assign_monitor(RuntimeOrigin::signed(9), 1), Error::<Test>::MonitorAlreadyExists);
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		assert_eq!(IbpMember::active_monitor_count(), 1);
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 2, reason("onboarded")));
+		assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(9), 2));
+		assert_eq!(IbpMember::active_monitor_count(), 2);
+
+		assert_ok!(IbpMember::chill_member(RuntimeOrigin::signed(1), reason("maintenance")));
+		assert_eq!(IbpMember::active_monitor_count(), 1);
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		assert_eq!(IbpMember::active_monitor_count(), 1);
+		assert_ok!(IbpMember::remove_monitor(RuntimeOrigin::signed(9), 2));
+		assert_eq!(IbpMember::active_monitor_count(), 0);
+	});
+}
+
+#[test]
+fn migration_counts_active_monitors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		for (account_id, member_name) in [(1, "alice"), (2, "bob")] {
+			assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(account_id), name(member_name)));
+			assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(9), account_id));
+		}
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		ActiveMonitorCount::<Test>::kill();
+		StorageVersion::new(3).put::<IbpMember>();
+
+		migrations::v4::CountActiveMonitors::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpMember::on_chain_storage_version(), 4);
+		assert_eq!(ActiveMonitorCount::<Test>::get(), 1);
+	});
+}
+
+const STATUSES: [MemberStatus; 5] =
+	[MemberStatus::None, MemberStatus::Locked, MemberStatus::Active, MemberStatus::Chilled, MemberStatus::Deleted];
+
+const ACTIONS: [MemberAction; 6] = [
+	MemberAction::Chill,
+	MemberAction::Unchill,
+	MemberAction::Lock,
+	MemberAction::Unlock,
+	MemberAction::Delete,
+	MemberAction::Undelete,
+];
+
+/// The outcome of every action from every status, spelled out apart from `MEMBER_TRANSITIONS`
+fn expected_transition(from: MemberStatus, action: MemberAction) -> Option<MemberStatus> {
+	match (from, action) {
+		(MemberStatus::Active, MemberAction::Chill) => Some(MemberStatus::Chilled),
+		(MemberStatus::Chilled, MemberAction::Unchill) => Some(MemberStatus::Active),
+		(MemberStatus::None | MemberStatus::Active | MemberStatus::Chilled, MemberAction::Lock) =>
+			Some(MemberStatus::Locked),
+		(MemberStatus::Locked, MemberAction::Unlock) => Some(MemberStatus::Active),
+		(MemberStatus::Deleted, MemberAction::Delete) => None,
+		(_, MemberAction::Delete) => Some(MemberStatus::Deleted),
+		(MemberStatus::Deleted, MemberAction::Undelete) => Some(MemberStatus::Locked),
+		_ => None,
+	}
+}
+
+/// Apply `action` to member 1 through its call, curator 9 acting for curator-only calls
+fn apply_action(action: MemberAction) -> DispatchResult {
+	let reason = reason("matrix");
+	match action {
+		MemberAction::Chill => IbpMember::chill_member(RuntimeOrigin::signed(1), reason),
+		MemberAction::Unchill => IbpMember::unchill_member(RuntimeOrigin::signed(1), reason),
+		MemberAction::Lock => IbpMember::lock_member(RuntimeOrigin::signed(9), 1, reason),
+		MemberAction::Unlock => IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason),
+		MemberAction::Delete => IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason),
+		MemberAction::Undelete => IbpMember::undelete_member(RuntimeOrigin::signed(9), 1, reason),
+	}
+}
+
+#[test]
+fn member_status_transition_matrix() {
+	for from in STATUSES {
+		for action in ACTIONS {
+			assert_eq!(from.transition(action), expected_transition(from, action), "{:?} {:?}", from, action);
+			new_test_ext().execute_with(|| {
+				System::set_block_number(1);
+				assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+				assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+				Members::<Test>::mutate(1, |member| member.status = from);
+				if from == MemberStatus::Deleted {
+					MemberNames::<Test>::remove(name("alice"));
+				}
+
+				let result = apply_action(action);
+				match expected_transition(from, action) {
+					Some(to) => {
+						assert_ok!(result);
+						assert_eq!(Members::<Test>::get(1).status, to);
+						assert_eq!(MemberStatusReasons::<Test>::get(1), Some(reason("matrix")));
+						System::assert_last_event(Event::MemberStatusUpdated(1, from, to, reason("matrix")).into());
+						match action {
+							MemberAction::Chill => System::assert_has_event(Event::MemberChilled(1).into()),
+							MemberAction::Unchill => System::assert_has_event(Event::MemberUnChilled(1).into()),
+							MemberAction::Delete => System::assert_has_event(Event::MemberDeleted(1).into()),
+							_ => {},
+						}
+					},
+					None => {
+						assert_eq!(result, Err(Error::<Test>::InvalidStatusTransition.into()), "{:?} {:?}", from, action);
+						assert_eq!(Members::<Test>::get(1).status, from);
+						assert_eq!(MemberStatusReasons::<Test>::get(1), None);
+					},
+				}
+			});
+		}
+	}
+}
+
+#[test]
+fn audit_log_records_actor_block_and_values() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		System::set_block_number(1);
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		System::set_block_number(2);
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		System::set_block_number(3);
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::Two));
+
+		assert_eq!(
+			IbpMember::audit_log(1).into_inner(),
+			vec![
+				AuditEntry {
+					block_number: 1,
+					actor: Some(1),
+					action: AuditAction::Registered { name: name("alice") },
+					reason: None,
+				},
+				AuditEntry {
+					block_number: 2,
+					actor: Some(9),
+					action: AuditAction::StatusChanged { old: MemberStatus::Locked, new: MemberStatus::Active },
+					reason: Some(reason("onboarded")),
+				},
+				AuditEntry {
+					block_number: 3,
+					actor: Some(9),
+					action: AuditAction::LevelChanged { old: MembershipLevel::None, new: MembershipLevel::Two },
+					reason: None,
+				},
+			]
+		);
+		assert!(IbpMember::audit_log(2).is_empty());
+	});
+}
+
+#[test]
+fn audit_log_drops_oldest_entries() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+		assert_ok!(IbpMember::assign_monitor(RuntimeOrigin::signed(9), 1));
+		assert_ok!(IbpMember::remove_monitor(RuntimeOrigin::signed(9), 1));
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+
+		// the mock keeps 3 entries, the registration was dropped
+		let actions: Vec<_> = IbpMember::audit_log(1).into_iter().map(|entry| entry.action).collect();
+		assert_eq!(
+			actions,
+			vec![
+				AuditAction::MonitorAssigned,
+				AuditAction::MonitorRemoved,
+				AuditAction::StatusChanged { old: MemberStatus::Locked, new: MemberStatus::Active },
+			]
+		);
+	});
+}
+
+/// Bonds of 100 to register and 300 at level two, curator 9 and member 1 registered
+fn setup_bonds() {
+	assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 9));
+	assert_ok!(IbpMember::set_level_bond(RuntimeOrigin::root(), MembershipLevel::None, 100));
+	assert_ok!(IbpMember::set_level_bond(RuntimeOrigin::root(), MembershipLevel::Two, 300));
+	assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(1), name("alice")));
+}
+
+#[test]
+fn registration_reserves_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_bonds();
+		assert_eq!(MemberBonds::<Test>::get(1), 100);
+		assert_eq!(Balances::reserved_balance(1), 100);
+		System::assert_has_event(Event::BondReserved(1, 100).into());
+
+		// account 50 has no funds
+		assert_noop!(
+			IbpMember::register_member(RuntimeOrigin::signed(50), name("bob")),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn level_upgrade_tops_up_bond() {
+	new_test_ext().execute_with(|| {
+		setup_bonds();
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::Two));
+		assert_eq!(MemberBonds::<Test>::get(1), 300);
+		assert_eq!(Balances::reserved_balance(1), 300);
+
+		// downgrades keep the bond
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::One));
+		assert_eq!(MemberBonds::<Test>::get(1), 300);
+
+		// the upgrade fails when the member cannot pay
+		assert_ok!(IbpMember::set_level_bond(RuntimeOrigin::root(), MembershipLevel::Three, 5_000));
+		assert_noop!(
+			IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::Three),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn bond_is_released_after_unbonding_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_bonds();
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		assert_eq!(MemberBonds::<Test>::get(1), 0);
+		assert_eq!(Unbonding::<Test>::get(1), Some(UnbondingBond { amount: 100, unlock_at: 11 }));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		System::set_block_number(10);
+		assert_noop!(IbpMember::withdraw_unbonded(RuntimeOrigin::signed(1)), Error::<Test>::UnbondingNotFinished);
+		System::set_block_number(11);
+		assert_ok!(IbpMember::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+		System::assert_last_event(Event::BondReleased(1, 100).into());
+		assert_noop!(IbpMember::withdraw_unbonded(RuntimeOrigin::signed(1)), Error::<Test>::NotUnbonding);
+	});
+}
+
+#[test]
+fn only_active_members_change_level() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_bonds();
+		// registered members are locked until a curator unlocks them
+		assert_noop!(
+			IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::Two),
+			Error::<Test>::MemberNotActive
+		);
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		assert_noop!(
+			IbpMember::update_member_level(RuntimeOrigin::signed(9), 1, MembershipLevel::Two),
+			Error::<Test>::MemberUnbonding
+		);
+		// no fresh bond was reserved
+		assert_eq!(MemberBonds::<Test>::get(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 100);
+	});
+}
+
+#[test]
+fn members_leave_and_withdraw_their_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_bonds();
+		assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(9), 1, reason("onboarded")));
+		assert_ok!(IbpMember::leave_membership(RuntimeOrigin::signed(1), reason("retiring")));
+		assert_eq!(Members::<Test>::get(1).status, MemberStatus::Deleted);
+		assert_eq!(MemberStatusReasons::<Test>::get(1), Some(reason("retiring")));
+		assert_eq!(IbpMember::member_by_name(&name("alice")), None);
+		assert_eq!(Unbonding::<Test>::get(1), Some(UnbondingBond { amount: 100, unlock_at: 11 }));
+		System::assert_has_event(Event::MemberDeleted(1).into());
+		assert_eq!(IbpMember::audit_log(1).last().unwrap().actor, Some(1));
+		assert_noop!(
+			IbpMember::leave_membership(RuntimeOrigin::signed(1), reason("again")),
+			Error::<Test>::InvalidStatusTransition
+		);
+		assert_noop!(
+			IbpMember::leave_membership(RuntimeOrigin::signed(2), reason("retiring")),
+			Error::<Test>::MemberNotFound
+		);
+
+		System::set_block_number(11);
+		assert_ok!(IbpMember::withdraw_unbonded(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn undelete_rebonds_member() {
+	new_test_ext().execute_with(|| {
+		setup_bonds();
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		assert_ok!(IbpMember::undelete_member(RuntimeOrigin::signed(9), 1, reason("back")));
+		assert_eq!(Unbonding::<Test>::get(1), None);
+		assert_eq!(MemberBonds::<Test>::get(1), 100);
+		assert_eq!(Balances::reserved_balance(1), 100);
+	});
+}
+
+#[test]
+fn curators_slash_bond_to_destination() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		setup_bonds();
+		assert_noop!(
+			IbpMember::slash_bond(RuntimeOrigin::signed(2), 1, 40, reason("sla-breach")),
+			Error::<Test>::NotACurator
+		);
+		assert_ok!(IbpMember::slash_bond(RuntimeOrigin::signed(9), 1, 40, reason("sla-breach")));
+		assert_eq!(MemberBonds::<Test>::get(1), 60);
+		assert_eq!(Balances::reserved_balance(1), 60);
+		assert_eq!(Balances::free_balance(TREASURY), 40);
+		System::assert_last_event(Event::BondSlashed(1, 40, reason("sla-breach")).into());
+
+		// the slash is capped at the bond
+		assert_ok!(IbpMember::slash_bond(RuntimeOrigin::signed(9), 1, 500, reason("sla-breach")));
+		assert_eq!(MemberBonds::<Test>::get(1), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 100);
+		System::assert_last_event(Event::BondSlashed(1, 60, reason("sla-breach")).into());
+	});
+}
+
+#[test]
+fn unbonding_bond_can_be_slashed() {
+	new_test_ext().execute_with(|| {
+		setup_bonds();
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(9), 1, reason("left")));
+		// as an SLA policy would
+		assert_eq!(IbpMember::slash_member_bond(&1, 30, reason("sla-breach")), 30);
+		assert_eq!(Unbonding::<Test>::get(1).map(|unbonding| unbonding.amount), Some(70));
+		assert_eq!(IbpMember::slash_member_bond(&1, 100, reason("sla-breach")), 70);
+		assert_eq!(Unbonding::<Test>::get(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 100);
+	});
+}
+
+fn genesis_with_curators(curators: Vec<u64>) -> GenesisConfig<Test> {
+	GenesisConfig {
+		members: vec![
+			(1, name("Alice"), MemberStatus::Active, MembershipLevel::One),
+			(2, name("Bob  Smith"), MemberStatus::Locked, MembershipLevel::None),
+			(3, name("Carol"), MemberStatus::Deleted, MembershipLevel::None),
+		],
+		curators,
+		monitors: vec![2],
+		level_bonds: vec![(MembershipLevel::One, 100)],
+		session_length: Some(20),
+	}
+}
+
+#[test]
+fn genesis_builds_members_curators_and_monitors() {
+	new_test_ext_with_genesis(genesis_with_curators(vec![1, 4])).execute_with(|| {
+		assert_eq!(Members::<Test>::get(1).status, MemberStatus::Active);
+		assert_eq!(Members::<Test>::get(2).level, MembershipLevel::None);
+		assert!(IbpMember::curators(1) && IbpMember::curators(4));
+		assert_eq!(CuratorCount::<Test>::get(), 2);
+		assert_eq!(MemberCount::<Test>::get(), 3);
+		assert!(IbpMember::monitors(2));
+		// monitor 2 is locked
+		assert_eq!(IbpMember::active_monitor_count(), 0);
+		assert_eq!(SessionLength::<Test>::get(), 20);
+		// names are indexed normalized, deleted members release their name
+		assert_eq!(MemberNames::<Test>::get(name("alice")), Some(1));
+		assert_eq!(MemberNames::<Test>::get(name("bob smith")), Some(2));
+		assert_eq!(MemberNames::<Test>::get(name("carol")), None);
+		assert_noop!(IbpMember::register_member(RuntimeOrigin::signed(5), name("ALICE")), Error::<Test>::NameAlreadyExists);
+		assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(5), name("Carol")));
+	});
+}
+
+#[test]
+#[should_panic(expected = "too many curators in genesis")]
+fn genesis_rejects_too_many_curators() {
+	new_test_ext_with_genesis(genesis_with_curators((1..=crate::MAX_CURATORS as u64 + 1).collect()));
+}
+
+#[test]
+#[should_panic(expected = "duplicate member name in genesis")]
+fn genesis_rejects_duplicate_names() {
+	let mut genesis = genesis_with_curators(vec![1]);
+	genesis.members.push((4, name("alice"), MemberStatus::Active, MembershipLevel::None));
+	new_test_ext_with_genesis(genesis);
+}
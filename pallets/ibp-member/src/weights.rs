@@ -1,29 +1,28 @@
 
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_ibp_member
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS.
+//! Storage access counts follow the dispatchables; execution times are conservative placeholders.
+//! Regenerate on reference hardware before relying on them, with `scripts/benchmark-weights.sh`,
+//! which builds with `--features runtime-benchmarks` and runs for each IBP pallet:
 
-// Executed Command:
-// ../../target/release/node-template
+// Command:
+// ./target/release/ibp-node
 // benchmark
 // pallet
 // --chain
 // dev
 // --pallet
-// pallet_template
+// pallet_ibp_member
 // --extrinsic
 // *
 // --steps=50
 // --repeat=20
 // --wasm-execution=compiled
 // --output
-// pallets/template/src/weights.rs
+// pallets/ibp-member/src/weights.rs
 // --template
-// ../../.maintain/frame-weight-template.hbs
+// ./.maintain/frame-weight-template.hbs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -32,68 +31,382 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_ibp_member.
 pub trait WeightInfo {
-	// fn register_member() -> Weight;
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+	fn register_member() -> Weight;
+	fn set_member_name() -> Weight;
+	fn chill_member() -> Weight;
+	fn unchill_member() -> Weight;
+	fn lock_member() -> Weight;
+	fn unlock_member() -> Weight;
+	fn delete_member() -> Weight;
+	fn undelete_member() -> Weight;
+	fn update_member_level() -> Weight;
+	fn assign_monitor() -> Weight;
+	fn remove_monitor() -> Weight;
+	fn assign_curator() -> Weight;
+	fn remove_curator() -> Weight;
+	fn set_level_bond() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn slash_bond() -> Weight;
+	fn leave_membership() -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_ibp_member using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// fn register_member() -> Weight {
-	// 	// Proof Size summary in bytes:
-	// 	//  Measured:  `0`
-	// 	//  Estimated: `0`
-	// 	// Minimum execution time: 8_000_000 picoseconds.
-	// 	Weight::from_parts(10_000, 0)
-	// 		.saturating_add(T::DbWeight::get().writes(1_u64))
-	// }
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberCount (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn register_member() -> Weight {
+		Weight::from_parts(19_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:2)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn set_member_name() -> Weight {
+		Weight::from_parts(20_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn chill_member() -> Weight {
+		Weight::from_parts(12_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn unchill_member() -> Weight {
+		Weight::from_parts(12_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn lock_member() -> Weight {
+		Weight::from_parts(14_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn unlock_member() -> Weight {
+		Weight::from_parts(14_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:0 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn delete_member() -> Weight {
+		Weight::from_parts(16_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn undelete_member() -> Weight {
+		Weight::from_parts(17_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:0)
+	fn update_member_level() -> Weight {
+		Weight::from_parts(15_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Monitors (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn assign_monitor() -> Weight {
+		Weight::from_parts(13_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Monitors (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn remove_monitor() -> Weight {
+		Weight::from_parts(13_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:1)
+	/// Storage: IbpMember CuratorCount (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn assign_curator() -> Weight {
+		Weight::from_parts(11_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:1)
+	/// Storage: IbpMember CuratorCount (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn remove_curator() -> Weight {
+		Weight::from_parts(11_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember LevelBonds (r:0 w:1)
+	fn set_level_bond() -> Weight {
+		Weight::from_parts(9_000_000, 3600)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(22_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn slash_bond() -> Weight {
+		Weight::from_parts(30_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:0 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn leave_membership() -> Weight {
+		Weight::from_parts(15_000_000, 3500)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberCount (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn register_member() -> Weight {
+		Weight::from_parts(19_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:2)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn set_member_name() -> Weight {
+		Weight::from_parts(20_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn chill_member() -> Weight {
+		Weight::from_parts(12_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn unchill_member() -> Weight {
+		Weight::from_parts(12_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn lock_member() -> Weight {
+		Weight::from_parts(14_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn unlock_member() -> Weight {
+		Weight::from_parts(14_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:0 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn delete_member() -> Weight {
+		Weight::from_parts(16_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:1 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn undelete_member() -> Weight {
+		Weight::from_parts(17_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember LevelBonds (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:0)
+	fn update_member_level() -> Weight {
+		Weight::from_parts(15_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Monitors (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn assign_monitor() -> Weight {
+		Weight::from_parts(13_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Monitors (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn remove_monitor() -> Weight {
+		Weight::from_parts(13_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:1)
+	/// Storage: IbpMember CuratorCount (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn assign_curator() -> Weight {
+		Weight::from_parts(11_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:1)
+	/// Storage: IbpMember CuratorCount (r:1 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	fn remove_curator() -> Weight {
+		Weight::from_parts(11_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember LevelBonds (r:0 w:1)
+	fn set_level_bond() -> Weight {
+		Weight::from_parts(9_000_000, 3600)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(22_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn slash_bond() -> Weight {
+		Weight::from_parts(30_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:1)
+	/// Storage: IbpMember MemberNames (r:0 w:1)
+	/// Storage: IbpMember MemberStatusReasons (r:0 w:1)
+	/// Storage: IbpMember MemberAuditLog (r:1 w:1)
+	/// Storage: IbpMember MemberBonds (r:1 w:1)
+	/// Storage: IbpMember Unbonding (r:1 w:1)
+	/// Storage: IbpMember Monitors (r:1 w:0)
+	/// Storage: IbpMember ActiveMonitorCount (r:1 w:1)
+	fn leave_membership() -> Weight {
+		Weight::from_parts(15_000_000, 3500)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
 }
@@ -0,0 +1,105 @@
+//! Benchmarking setup for pallet-ibp-reward
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as IbpReward;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use pallet_ibp_member::{BalanceOf, MemberData, MemberStatus, MembershipLevel, Members};
+use pallet_ibp_service::{ServiceAssignments, ServiceId};
+extern crate alloc;
+use alloc::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn add_member<T: Config>(index: u32) -> T::AccountId {
+	let account_id: T::AccountId = account("member", index, SEED);
+	let mut name = b"member".to_vec();
+	name.extend_from_slice(&index.to_be_bytes());
+	let member_data = MemberData::<T> {
+		id: Some(account_id.clone()),
+		name: name.try_into().expect("name fits in 32 bytes"),
+		status: MemberStatus::Active,
+		level: MembershipLevel::One,
+	};
+	Members::<T>::insert(&account_id, member_data);
+	account_id
+}
+
+fn service_id(index: u32) -> ServiceId {
+	let mut id: Vec<u8> = b"service".to_vec();
+	id.extend_from_slice(&index.to_be_bytes());
+	id.try_into().expect("id fits in 32 bytes")
+}
+
+fn reward<T: Config>() -> BalanceOf<T> {
+	<T as pallet_ibp_member::Config>::Currency::minimum_balance() * 100u32.into()
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_level_reward() {
+		#[extrinsic_call]
+		set_level_reward(RawOrigin::Root, MembershipLevel::One, reward::<T>());
+
+		assert_eq!(LevelRewards::<T>::get(MembershipLevel::One), reward::<T>());
+	}
+
+	#[benchmark]
+	fn claim_payout() {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as pallet_ibp_member::Config>::Currency::make_free_balance_be(
+			&IbpReward::<T>::pot_account(),
+			reward::<T>() * 10u32.into(),
+		);
+		Claimable::<T>::insert(&caller, reward::<T>());
+		TotalClaimable::<T>::put(reward::<T>());
+		#[extrinsic_call]
+		claim_payout(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(<T as pallet_ibp_member::Config>::Currency::free_balance(&caller), reward::<T>());
+	}
+
+	/// A member providing `s` services, tallied alone in its page
+	#[benchmark]
+	fn tally_payout(s: Linear<0, { <T as pallet_ibp_service::Config>::MaxServices::get() }>) {
+		let member_id = add_member::<T>(0);
+		for index in 0..s {
+			ServiceAssignments::<T>::insert(&member_id, service_id(index), ());
+		}
+		LevelRewards::<T>::insert(MembershipLevel::One, reward::<T>());
+		IbpReward::<T>::end_era(T::EraLength::get());
+		#[block]
+		{
+			IbpReward::<T>::settle_era(1);
+		}
+
+		assert!(SettlementCursor::<T>::exists());
+	}
+
+	/// A payout credited alone in its page
+	#[benchmark]
+	fn credit_payout() {
+		let member_id = add_member::<T>(0);
+		IbpReward::<T>::end_era(T::EraLength::get());
+		Settlement::<T>::mutate(|settlement| {
+			if let Some(settlement) = settlement {
+				settlement.phase = SettlementPhase::Credit { share: Perbill::one() };
+			}
+		});
+		PendingPayouts::<T>::insert(&member_id, reward::<T>());
+		#[block]
+		{
+			IbpReward::<T>::settle_era(1);
+		}
+
+		assert_eq!(Claimable::<T>::get(&member_id), reward::<T>());
+	}
+
+	impl_benchmark_test_suite!(IbpReward, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,393 @@
+//! IBP Reward Pallet
+//!
+//! Pays members for the infrastructure they run. Every `EraLength` blocks the era ends, on the
+//! first block of an uptime epoch so that eras cover whole epochs, and each active member is owed the reward of its `MembershipLevel` for every service assigned to it,
+//! scaled by the uptime the monitors measured on that service during the era. Payouts are funded
+//! from the pot account, shared out pro rata when the pot cannot cover the era, and claimed by
+//! the members with `claim_payout`. An ended era is settled over as many blocks as it takes,
+//! `MaxEraPayoutsPerBlock` members at a time.
+
+// We make sure this pallet uses `no_std` for compiling to Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{ Decode, Encode, MaxEncodedLen };
+use frame_support::sp_runtime::Perbill;
+use scale_info::TypeInfo;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+// FRAME pallets require their own "mock runtimes" to be able to run unit tests. This module
+// contains a mock runtime specific for testing this pallet's functionality.
+#[cfg(test)]
+mod mock;
+
+// This module contains the unit tests for this pallet.
+// Learn about pallet unit testing here: https://docs.substrate.io/test/unit-testing/
+#[cfg(test)]
+mod tests;
+
+// Every callable function or "dispatchable" a pallet exposes must have weight values that correctly
+// estimate a dispatchable's execution time. The benchmarking module is used to calculate weights
+// for each dispatchable and generates this pallet's weight.rs file. Learn more about benchmarking here: https://docs.substrate.io/test/benchmark/
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use pallet_ibp_member::Pallet as MemberPallet;
+use pallet_ibp_service::Pallet as ServicePallet;
+/// Uptime is measured by `pallet-ibp-check`
+pub use pallet_ibp_check::UptimeProvider;
+
+/// Index of an era, counted from genesis
+pub type EraIndex = u32;
+
+/// The payout a member can expect, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PayoutPreview<Balance> {
+    /// The era in progress
+    pub era: EraIndex,
+    /// The payout for the era in progress if it ended now, before the pot is shared out
+    pub payout: Balance,
+    /// Payouts of past eras not claimed yet
+    pub claimable: Balance,
+}
+
+/// Where the settlement of an ended era is at
+#[derive(Clone, Copy, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum SettlementPhase {
+    /// Computing the payout of every member into `PendingPayouts`
+    Tally,
+    /// Crediting `PendingPayouts`, scaled by the share of the payouts the pot covers
+    Credit { share: Perbill },
+}
+
+/// An ended era whose payouts are being credited
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct EraSettlement<Balance, BlockNumber> {
+    pub era: EraIndex,
+    /// First block of the era
+    pub start: BlockNumber,
+    /// Last block of the era
+    pub end: BlockNumber,
+    /// Sum of the payouts tallied so far
+    pub owed: Balance,
+    /// Sum of the payouts credited so far
+    pub paid: Balance,
+    /// Number of members credited so far
+    pub payees: u32,
+    pub phase: SettlementPhase,
+}
+
+// All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
+#[frame_support::pallet]
+pub mod pallet {
+    // Import various useful types required by all FRAME pallets.
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        sp_runtime::traits::{AccountIdConversion, Saturating, Zero},
+        traits::{Currency, ExistenceRequirement},
+        PalletId,
+    };
+    use frame_system::pallet_prelude::*;
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    use pallet_ibp_member::{BalanceOf, MemberData, MemberStatus, MembershipLevel};
+
+    // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
+    // (`Call`s) in this pallet.
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// The pallet's configuration trait.
+    ///
+    /// Rewards are paid in the currency members bond in, `pallet_ibp_member::Config::Currency`.
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_ibp_member::Config + pallet_ibp_service::Config {
+        /// The overarching runtime event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// A type representing the weights required by the dispatchables of this pallet.
+        type WeightInfo: WeightInfo;
+        /// Id of the pot account the payouts are funded from
+        #[pallet::constant]
+        type PotId: Get<PalletId>;
+        /// Number of blocks per era. Uptime is measured per epoch of the `Uptime` provider, the
+        /// era must be a multiple of its epoch.
+        #[pallet::constant]
+        type EraLength: Get<BlockNumberFor<Self>>;
+        /// Where the uptime of members is measured
+        type Uptime: UptimeProvider<Self::AccountId, BlockNumberFor<Self>>;
+        /// Members tallied, and payouts credited, per block while an ended era is settled
+        #[pallet::constant]
+        type MaxEraPayoutsPerBlock: Get<u32>;
+    }
+
+    /// The era in progress
+    #[pallet::storage]
+    #[pallet::getter(fn current_era)]
+    pub type CurrentEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+    /// Block the era in progress started at
+    #[pallet::storage]
+    #[pallet::getter(fn era_start)]
+    pub type EraStart<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+    /// Reward per era for each service assigned to a member at full uptime, by membership level
+    #[pallet::storage]
+    #[pallet::getter(fn level_reward)]
+    pub type LevelRewards<T: Config> = StorageMap<_, Twox64Concat, MembershipLevel, BalanceOf<T>, ValueQuery>;
+
+    /// Payouts of past eras each member has not claimed yet
+    #[pallet::storage]
+    #[pallet::getter(fn claimable)]
+    pub type Claimable<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Sum of `Claimable`, kept in the pot for the members
+    #[pallet::storage]
+    #[pallet::getter(fn total_claimable)]
+    pub type TotalClaimable<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// The ended era being settled, the next era only ends once it is done
+    #[pallet::storage]
+    #[pallet::getter(fn settlement)]
+    pub type Settlement<T: Config> =
+        StorageValue<_, EraSettlement<BalanceOf<T>, BlockNumberFor<T>>, OptionQuery>;
+
+    /// Raw key of the last member tallied by the settlement
+    #[pallet::storage]
+    pub type SettlementCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Payouts of the era being settled, before the pot is shared out
+    #[pallet::storage]
+    pub type PendingPayouts<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Events that functions in this pallet can emit.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// (level, reward per service per era)
+        LevelRewardSet(MembershipLevel, BalanceOf<T>),
+        /// (era, total paid out, members paid)
+        EraPaid(EraIndex, BalanceOf<T>, u32),
+        /// (member, amount)
+        PayoutClaimed(T::AccountId, BalanceOf<T>),
+    }
+
+    /// Errors that can be returned by this pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The member has no payout to claim
+        NothingToClaim,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// End the era at the first epoch boundary once `EraLength` blocks have passed since it
+        /// started, then settle it a page of `MaxEraPayoutsPerBlock` members per block
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if !Settlement::<T>::exists() {
+                let era_length = T::EraLength::get();
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+                if era_length.is_zero()
+                    || now < EraStart::<T>::get().saturating_add(era_length)
+                    || !Self::is_epoch_start(now)
+                {
+                    return weight;
+                }
+                Self::end_era(now);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 3));
+            }
+            weight.saturating_add(Self::settle_era(T::MaxEraPayoutsPerBlock::get()))
+        }
+
+        /// Eras of whole epochs can only end on epoch boundaries if the era is a multiple of the epoch
+        fn integrity_test() {
+            let epoch_length = T::Uptime::epoch_length();
+            assert!(
+                epoch_length.is_zero() || (T::EraLength::get() % epoch_length).is_zero(),
+                "`EraLength` must be a multiple of the epoch length of `Uptime`"
+            );
+        }
+    }
+
+    /// The pallet's dispatchable functions ([`Call`]s).
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+
+        /// Set the reward per era for each service assigned to a member of `level`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_level_reward())]
+        pub fn set_level_reward(origin: OriginFor<T>, level: MembershipLevel, amount: BalanceOf<T>) -> DispatchResult {
+            // TODO do this via governance
+            ensure_root(origin)?;
+            LevelRewards::<T>::insert(level, amount);
+            Self::deposit_event(Event::LevelRewardSet(level, amount));
+            Ok(())
+        }
+
+        /// Transfer the payouts of past eras from the pot to the caller
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::claim_payout())]
+        pub fn claim_payout(origin: OriginFor<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let amount = Claimable::<T>::get(&sender);
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+            <T as pallet_ibp_member::Config>::Currency::transfer(
+                &Self::pot_account(),
+                &sender,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Claimable::<T>::remove(&sender);
+            TotalClaimable::<T>::mutate(|total| *total = total.saturating_sub(amount));
+            Self::deposit_event(Event::PayoutClaimed(sender, amount));
+            Ok(())
+        }
+
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The account payouts are funded from
+        pub fn pot_account() -> T::AccountId {
+            T::PotId::get().into_account_truncating()
+        }
+
+        /// Pot funds not owed to members yet, the pot is kept alive
+        pub fn available_funds() -> BalanceOf<T> {
+            let free = <T as pallet_ibp_member::Config>::Currency::free_balance(&Self::pot_account());
+            let minimum = <T as pallet_ibp_member::Config>::Currency::minimum_balance();
+            free.saturating_sub(minimum).saturating_sub(TotalClaimable::<T>::get())
+        }
+
+        /// What `member_id` earned from block `from` to block `to`: the reward of its level for
+        /// every assigned service, scaled by the uptime of the service. Only active members earn.
+        /// Returns the payout and the number of services assigned.
+        pub fn payout(
+            member_id: &T::AccountId,
+            member: &MemberData<T>,
+            from: BlockNumberFor<T>,
+            to: BlockNumberFor<T>,
+        ) -> (BalanceOf<T>, u32) {
+            if member.status != MemberStatus::Active {
+                return (Zero::zero(), 0);
+            }
+            let reward = LevelRewards::<T>::get(member.level);
+            let services = ServicePallet::<T>::assigned_services(member_id);
+            let payout = services.iter().fold(BalanceOf::<T>::zero(), |payout, service_id| {
+                let uptime = T::Uptime::uptime(member_id, service_id, from, to);
+                payout.saturating_add(uptime.mul_floor(reward))
+            });
+            (payout, services.len() as u32)
+        }
+
+        /// The payout `member_id` can expect for the era in progress, and its unclaimed payouts
+        pub fn next_payout(member_id: &T::AccountId) -> PayoutPreview<BalanceOf<T>> {
+            let member = MemberPallet::<T>::members(member_id);
+            let now = frame_system::Pallet::<T>::block_number();
+            let (payout, _) = Self::payout(member_id, &member, EraStart::<T>::get(), now);
+            PayoutPreview {
+                era: CurrentEra::<T>::get(),
+                payout: payout.min(Self::available_funds()),
+                claimable: Claimable::<T>::get(member_id),
+            }
+        }
+
+        /// Whether an uptime epoch starts at `block_number`. An era that starts on an epoch
+        /// boundary, as the first era does and every era after it, covers whole epochs only.
+        fn is_epoch_start(block_number: BlockNumberFor<T>) -> bool {
+            let epoch_length = T::Uptime::epoch_length();
+            epoch_length.is_zero() || (block_number % epoch_length).is_zero()
+        }
+
+        /// End the era in progress and start the next one, the ended era is settled by
+        /// `settle_era` from this block on
+        pub(crate) fn end_era(now: BlockNumberFor<T>) {
+            let era = CurrentEra::<T>::get();
+            Settlement::<T>::put(EraSettlement {
+                era,
+                start: EraStart::<T>::get(),
+                end: now.saturating_sub(1u32.into()),
+                owed: Zero::zero(),
+                paid: Zero::zero(),
+                payees: 0,
+                phase: SettlementPhase::Tally,
+            });
+            CurrentEra::<T>::put(era.saturating_add(1));
+            EraStart::<T>::put(now);
+        }
+
+        /// Settle up to `limit` members of the ended era: tally the payout of every member, then
+        /// credit the payouts, shared out pro rata when the pot cannot cover them all.
+        /// Returns the weight used.
+        pub(crate) fn settle_era(limit: u32) -> Weight {
+            let Some(mut settlement) = Settlement::<T>::get() else {
+                return Weight::zero();
+            };
+            let mut weight = T::DbWeight::get().reads_writes(2, 2);
+            let mut budget = limit.max(1);
+            if settlement.phase == SettlementPhase::Tally {
+                let mut members = match SettlementCursor::<T>::take() {
+                    Some(cursor) => pallet_ibp_member::Members::<T>::iter_from(cursor),
+                    None => pallet_ibp_member::Members::<T>::iter(),
+                };
+                let mut tallied = true;
+                while let Some((member_id, member)) = members.next() {
+                    let (payout, services) = Self::payout(&member_id, &member, settlement.start, settlement.end);
+                    weight.saturating_accrue(T::WeightInfo::tally_payout(services));
+                    if !payout.is_zero() {
+                        settlement.owed = settlement.owed.saturating_add(payout);
+                        PendingPayouts::<T>::insert(&member_id, payout);
+                    }
+                    budget -= 1;
+                    if budget == 0 {
+                        SettlementCursor::<T>::put(members.last_raw_key().to_vec());
+                        tallied = false;
+                        break;
+                    }
+                }
+                if tallied {
+                    // share the pot out pro rata when it cannot cover the era
+                    let available = Self::available_funds();
+                    let share = if settlement.owed > available {
+                        Perbill::from_rational(available, settlement.owed)
+                    } else {
+                        Perbill::one()
+                    };
+                    weight.saturating_accrue(T::DbWeight::get().reads(2));
+                    settlement.phase = SettlementPhase::Credit { share };
+                }
+            }
+            if let SettlementPhase::Credit { share } = settlement.phase {
+                let mut payouts = PendingPayouts::<T>::drain();
+                let mut credited = BalanceOf::<T>::zero();
+                let mut settled = false;
+                while budget > 0 {
+                    let Some((member_id, payout)) = payouts.next() else {
+                        settled = true;
+                        break;
+                    };
+                    let payout = share.mul_floor(payout);
+                    Claimable::<T>::mutate(&member_id, |claimable| *claimable = claimable.saturating_add(payout));
+                    credited = credited.saturating_add(payout);
+                    settlement.payees += 1;
+                    budget -= 1;
+                    weight.saturating_accrue(T::WeightInfo::credit_payout());
+                }
+                TotalClaimable::<T>::mutate(|total| *total = total.saturating_add(credited));
+                settlement.paid = settlement.paid.saturating_add(credited);
+                if settled {
+                    Settlement::<T>::kill();
+                    Self::deposit_event(Event::EraPaid(settlement.era, settlement.paid, settlement.payees));
+                    return weight;
+                }
+            }
+            Settlement::<T>::put(settlement);
+            weight
+        }
+    }
+}
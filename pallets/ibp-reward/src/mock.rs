@@ -0,0 +1,133 @@
+use crate as pallet_ibp_reward;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+	PalletId,
+};
+use pallet_ibp_check::UptimeProvider;
+use pallet_ibp_service::ServiceId;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, Perbill,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		IbpMember: pallet_ibp_member,
+		IbpService: pallet_ibp_service,
+		IbpReward: pallet_ibp_reward,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+impl pallet_ibp_member::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type OnMemberChanged = IbpService;
+	type MaxAuditEntries = ConstU32<3>;
+	type Currency = Balances;
+	type UnbondingPeriod = ConstU64<10>;
+	type Slashed = ();
+	type DefaultSessionLength = ConstU64<10>;
+	type MaxMembers = ConstU32<16>;
+}
+
+impl pallet_ibp_service::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxOverrideExpiries = ConstU32<4>;
+	type MaxServices = ConstU32<16>;
+}
+
+parameter_types! {
+	pub const PotId: PalletId = PalletId(*b"ibp/pot_");
+	pub static MaxEraPayoutsPerBlock: u32 = 16;
+	pub static EpochLength: u64 = 5;
+}
+
+thread_local! {
+	static UPTIME: RefCell<BTreeMap<(u64, ServiceId), Perbill>> = RefCell::new(BTreeMap::new());
+}
+
+/// Uptime set by the tests, zero when unset
+pub struct MockUptime;
+
+impl MockUptime {
+	pub fn set(member_id: u64, service_id: ServiceId, uptime: Perbill) {
+		UPTIME.with(|map| map.borrow_mut().insert((member_id, service_id), uptime));
+	}
+}
+
+impl UptimeProvider<u64, u64> for MockUptime {
+	fn epoch_length() -> u64 {
+		EpochLength::get()
+	}
+
+	fn uptime(member_id: &u64, service_id: &ServiceId, _from: u64, _to: u64) -> Perbill {
+		UPTIME.with(|map| map.borrow().get(&(*member_id, service_id.clone())).copied().unwrap_or_default())
+	}
+}
+
+impl pallet_ibp_reward::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type PotId = PotId;
+	type EraLength = ConstU64<10>;
+	type Uptime = MockUptime;
+	type MaxEraPayoutsPerBlock = MaxEraPayoutsPerBlock;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+}
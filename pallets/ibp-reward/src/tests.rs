@@ -0,0 +1,188 @@
+use crate::{mock::*, Claimable, CurrentEra, EraStart, Error, Event, PayoutPreview, PendingPayouts, Settlement, TotalClaimable};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Hooks},
+};
+use pallet_ibp_member::MembershipLevel;
+use pallet_ibp_service::ServiceId;
+use pallet_ibp_test_utils::{add_member, bounded, register_polkadot, register_service};
+use sp_runtime::Perbill;
+
+const CURATOR: u64 = 1;
+/// Level one, provides both services
+const SENIOR: u64 = 2;
+/// Level none, provides the level none service only
+const JUNIOR: u64 = 3;
+
+fn polkadot_rpc() -> ServiceId {
+	bounded("polkadot-rpc")
+}
+
+fn kusama_rpc() -> ServiceId {
+	bounded("kusama-rpc")
+}
+
+/// Two active members, a service per level, rewards of 10 at level none and 100 at level one,
+/// and a pot of `pot` including the existential deposit
+fn new_test_ext_with_members(pot: u64) -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), CURATOR));
+		for (account_id, name) in [(SENIOR, "senior"), (JUNIOR, "junior")] {
+			add_member::<Test>(&CURATOR, &account_id, name);
+		}
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(CURATOR), SENIOR, MembershipLevel::One));
+		register_polkadot::<Test>(&CURATOR);
+		for (service_id, level) in [(polkadot_rpc(), MembershipLevel::None), (kusama_rpc(), MembershipLevel::One)] {
+			register_service::<Test>(&CURATOR, service_id, level);
+		}
+		assert_eq!(IbpService::assigned_services(&SENIOR).len(), 2);
+		assert_eq!(IbpService::assigned_services(&JUNIOR), vec![polkadot_rpc()]);
+
+		assert_ok!(IbpReward::set_level_reward(RuntimeOrigin::root(), MembershipLevel::None, 10));
+		assert_ok!(IbpReward::set_level_reward(RuntimeOrigin::root(), MembershipLevel::One, 100));
+		Balances::make_free_balance_be(&IbpReward::pot_account(), pot);
+
+		MockUptime::set(SENIOR, polkadot_rpc(), Perbill::one());
+		MockUptime::set(SENIOR, kusama_rpc(), Perbill::from_percent(50));
+		MockUptime::set(JUNIOR, polkadot_rpc(), Perbill::one());
+	});
+	ext
+}
+
+#[test]
+fn era_end_credits_payouts() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		System::set_block_number(9);
+		IbpReward::on_initialize(9);
+		assert_eq!(CurrentEra::<Test>::get(), 0);
+
+		System::set_block_number(10);
+		IbpReward::on_initialize(10);
+		// 100 + 50% of 100, and 10
+		assert_eq!(Claimable::<Test>::get(SENIOR), 150);
+		assert_eq!(Claimable::<Test>::get(JUNIOR), 10);
+		assert_eq!(TotalClaimable::<Test>::get(), 160);
+		assert_eq!(CurrentEra::<Test>::get(), 1);
+		assert_eq!(EraStart::<Test>::get(), 10);
+		System::assert_last_event(Event::EraPaid(0, 160, 2).into());
+
+		// the next era ends 10 blocks later
+		System::set_block_number(19);
+		IbpReward::on_initialize(19);
+		assert_eq!(CurrentEra::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn only_active_members_earn() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		assert_ok!(IbpMember::chill_member(RuntimeOrigin::signed(SENIOR), Default::default()));
+		IbpReward::on_initialize(10);
+		assert_eq!(Claimable::<Test>::get(SENIOR), 0);
+		assert_eq!(Claimable::<Test>::get(JUNIOR), 10);
+	});
+}
+
+#[test]
+fn short_pot_is_shared_pro_rata() {
+	// 80 available above the existential deposit, half of the 160 owed
+	new_test_ext_with_members(81).execute_with(|| {
+		IbpReward::on_initialize(10);
+		assert_eq!(Claimable::<Test>::get(SENIOR), 75);
+		assert_eq!(Claimable::<Test>::get(JUNIOR), 5);
+
+		// nothing is left for the next era until the pot is topped up
+		IbpReward::on_initialize(20);
+		assert_eq!(TotalClaimable::<Test>::get(), 80);
+	});
+}
+
+#[test]
+fn members_claim_payouts_from_pot() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		System::set_block_number(10);
+		IbpReward::on_initialize(10);
+		assert_ok!(IbpReward::claim_payout(RuntimeOrigin::signed(SENIOR)));
+		assert_eq!(Balances::free_balance(SENIOR), 150);
+		assert_eq!(Balances::free_balance(IbpReward::pot_account()), 851);
+		assert_eq!(TotalClaimable::<Test>::get(), 10);
+		System::assert_last_event(Event::PayoutClaimed(SENIOR, 150).into());
+		assert_noop!(IbpReward::claim_payout(RuntimeOrigin::signed(SENIOR)), Error::<Test>::NothingToClaim);
+	});
+}
+
+#[test]
+fn next_payout_previews_era_in_progress() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		assert_eq!(IbpReward::next_payout(&SENIOR), PayoutPreview { era: 0, payout: 150, claimable: 0 });
+		IbpReward::on_initialize(10);
+		assert_eq!(IbpReward::next_payout(&SENIOR), PayoutPreview { era: 1, payout: 150, claimable: 150 });
+		MockUptime::set(SENIOR, polkadot_rpc(), Perbill::zero());
+		assert_eq!(IbpReward::next_payout(&SENIOR), PayoutPreview { era: 1, payout: 50, claimable: 150 });
+	});
+}
+
+#[test]
+fn ended_eras_are_settled_a_page_per_block() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		MaxEraPayoutsPerBlock::set(1);
+		System::set_block_number(10);
+		IbpReward::on_initialize(10);
+		// the next era starts right away, the ended one is tallied a member at a time
+		assert_eq!(CurrentEra::<Test>::get(), 1);
+		assert_eq!(EraStart::<Test>::get(), 10);
+		assert_eq!(PendingPayouts::<Test>::iter().count(), 1);
+		assert_eq!(TotalClaimable::<Test>::get(), 0);
+
+		let mut block_number = 10;
+		while Settlement::<Test>::exists() {
+			block_number += 1;
+			System::set_block_number(block_number);
+			IbpReward::on_initialize(block_number);
+		}
+		// a page per member tallied, then per payout credited, then one to find none left
+		assert_eq!(block_number, 14);
+		assert_eq!(Claimable::<Test>::get(SENIOR), 150);
+		assert_eq!(Claimable::<Test>::get(JUNIOR), 10);
+		assert_eq!(TotalClaimable::<Test>::get(), 160);
+		assert_eq!(PendingPayouts::<Test>::iter().count(), 0);
+		System::assert_last_event(Event::EraPaid(0, 160, 2).into());
+
+		// the next era still ends 10 blocks after it started
+		System::set_block_number(20);
+		IbpReward::on_initialize(20);
+		assert_eq!(CurrentEra::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn era_ends_on_an_epoch_boundary() {
+	new_test_ext_with_members(1_001).execute_with(|| {
+		// an era started off an epoch boundary, e.g. when the pallet was added by an upgrade
+		EraStart::<Test>::put(3);
+		System::set_block_number(13);
+		IbpReward::on_initialize(13);
+		assert_eq!(CurrentEra::<Test>::get(), 0);
+
+		System::set_block_number(15);
+		IbpReward::on_initialize(15);
+		assert_eq!(CurrentEra::<Test>::get(), 1);
+		assert_eq!(EraStart::<Test>::get(), 15);
+		System::assert_last_event(Event::EraPaid(0, 160, 2).into());
+
+		// eras of whole epochs from then on
+		System::set_block_number(25);
+		IbpReward::on_initialize(25);
+		assert_eq!(CurrentEra::<Test>::get(), 2);
+	});
+}
+
+#[test]
+#[should_panic(expected = "`EraLength` must be a multiple of the epoch length of `Uptime`")]
+fn era_length_must_be_a_multiple_of_the_epoch() {
+	EpochLength::set(3);
+	new_test_ext().execute_with(|| {
+		<IbpReward as Hooks<u64>>::integrity_test();
+	});
+}
@@ -0,0 +1,117 @@
+
+//! Weights for pallet_ibp_reward
+//!
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS.
+//! Storage access counts follow the dispatchables; execution times are conservative placeholders.
+//! Regenerate on reference hardware before relying on them, with `scripts/benchmark-weights.sh`,
+//! which builds with `--features runtime-benchmarks` and runs for each IBP pallet:
+
+// Command:
+// ./target/release/ibp-node
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_ibp_reward
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --output
+// pallets/ibp-reward/src/weights.rs
+// --template
+// ./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_ibp_reward.
+pub trait WeightInfo {
+	fn set_level_reward() -> Weight;
+	fn claim_payout() -> Weight;
+	fn tally_payout(s: u32, ) -> Weight;
+	fn credit_payout() -> Weight;
+}
+
+/// Weights for pallet_ibp_reward using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: IbpReward LevelRewards (r:0 w:1)
+	fn set_level_reward() -> Weight {
+		Weight::from_parts(9_000_000, 1500)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpReward Claimable (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: IbpReward TotalClaimable (r:1 w:1)
+	fn claim_payout() -> Weight {
+		Weight::from_parts(40_000_000, 6200)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpReward LevelRewards (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:0)
+	/// Storage: IbpCheck Rollups (r:s w:0)
+	/// Storage: IbpReward PendingPayouts (r:0 w:1)
+	/// The range of component `s` is `[0, 256]`.
+	fn tally_payout(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 2600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 3800).saturating_mul(s.into()))
+	}
+	/// Storage: IbpReward PendingPayouts (r:1 w:1)
+	/// Storage: IbpReward Claimable (r:1 w:1)
+	fn credit_payout() -> Weight {
+		Weight::from_parts(12_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: IbpReward LevelRewards (r:0 w:1)
+	fn set_level_reward() -> Weight {
+		Weight::from_parts(9_000_000, 1500)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpReward Claimable (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: IbpReward TotalClaimable (r:1 w:1)
+	fn claim_payout() -> Weight {
+		Weight::from_parts(40_000_000, 6200)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpReward LevelRewards (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:0)
+	/// Storage: IbpCheck Rollups (r:s w:0)
+	/// Storage: IbpReward PendingPayouts (r:0 w:1)
+	/// The range of component `s` is `[0, 256]`.
+	fn tally_payout(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 2600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 3800).saturating_mul(s.into()))
+	}
+	/// Storage: IbpReward PendingPayouts (r:1 w:1)
+	/// Storage: IbpReward Claimable (r:1 w:1)
+	fn credit_payout() -> Weight {
+		Weight::from_parts(12_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -0,0 +1,249 @@
+//! Runtime API definitions for the IBP pallets.
+//!
+//! The business rules (which member provides which service, which alerts match a filter)
+//! live in the pallets, this crate only exposes them to the node and its clients.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{Perbill, Percent};
+use sp_std::vec::Vec;
+
+use pallet_ibp_check::{EpochIndex, HealthCheckData, HealthCheckStatus, HealthCheckType, UptimeRollup};
+use pallet_ibp_member::{AuditEntry, MemberData, MemberName, MemberStatus, MembershipLevel};
+use pallet_ibp_service::{ChainId, ServiceData, ServiceId, ServiceStatus, ServiceType};
+
+pub use pallet_ibp_alert::{
+    AlertData, AlertFilter, AlertSeverity, AlertStats, AlertType, AlertTypeInfo, DomainId, PendingAlert,
+};
+pub use pallet_ibp_reward::PayoutPreview;
+
+/// A member, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct MemberInfo<AccountId> {
+    pub id: Option<AccountId>,
+    pub name: MemberName,
+    pub status: MemberStatus,
+    pub level: MembershipLevel,
+}
+
+impl<T: pallet_ibp_member::Config> From<MemberData<T>> for MemberInfo<T::AccountId> {
+    fn from(data: MemberData<T>) -> Self {
+        Self { id: data.id, name: data.name, status: data.status, level: data.level }
+    }
+}
+
+/// A service, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct ServiceInfo {
+    pub id: ServiceId,
+    pub chain_id: ChainId,
+    pub service_type: ServiceType,
+    pub level: MembershipLevel,
+    pub status: ServiceStatus,
+}
+
+impl<T: pallet_ibp_service::Config> From<ServiceData<T>> for ServiceInfo {
+    fn from(data: ServiceData<T>) -> Self {
+        Self {
+            id: data.id,
+            chain_id: data.chain_id,
+            service_type: data.service_type,
+            level: data.level,
+            status: data.status,
+        }
+    }
+}
+
+/// An alert, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct AlertInfo<AccountId> {
+    pub monitor_id: AccountId,
+    pub alert_id: u64,
+    pub member_id: AccountId,
+    pub domain_id: DomainId,
+    pub service_id: ServiceId,
+    pub alert_type: AlertType,
+    pub severity: AlertSeverity,
+    /// Unix time in milliseconds
+    pub raised_at_time: u64,
+    pub acknowledged_by: Option<AccountId>,
+    pub acknowledged_at_time: Option<u64>,
+}
+
+impl<T: pallet_ibp_alert::Config> From<AlertData<T>> for AlertInfo<T::AccountId> {
+    fn from(data: AlertData<T>) -> Self {
+        Self {
+            monitor_id: data.monitor_id,
+            alert_id: data.alert_id,
+            member_id: data.member_id,
+            domain_id: data.domain_id,
+            service_id: data.service_id,
+            alert_type: data.alert_type,
+            severity: data.severity,
+            raised_at_time: data.raised_at_time,
+            acknowledged_by: data.acknowledged_by,
+            acknowledged_at_time: data.acknowledged_at_time,
+        }
+    }
+}
+
+/// An alert as returned by `IbpApi` before version 5, without severity or lifecycle
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct AlertInfoBeforeV5<AccountId> {
+    pub monitor_id: AccountId,
+    pub alert_id: u64,
+    pub member_id: AccountId,
+    pub domain_id: DomainId,
+    pub service_id: ServiceId,
+    pub alert_type: AlertType,
+}
+
+/// An alert waiting for quorum, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct PendingAlertInfo<AccountId, BlockNumber> {
+    pub member_id: AccountId,
+    pub service_id: ServiceId,
+    pub alert_type: AlertType,
+    pub expires_at: BlockNumber,
+    /// Monitors that reported the alert, oldest report first
+    pub monitors: Vec<AccountId>,
+    /// Reports needed for the alert to take effect
+    pub required: u32,
+}
+
+impl<T: pallet_ibp_alert::Config> PendingAlertInfo<T::AccountId, frame_system::pallet_prelude::BlockNumberFor<T>> {
+    pub fn new(key: (T::AccountId, ServiceId, AlertType), pending: PendingAlert<T>, required: u32) -> Self {
+        let (member_id, service_id, alert_type) = key;
+        Self {
+            member_id,
+            service_id,
+            alert_type,
+            expires_at: pending.expires_at,
+            monitors: pending.reports.into_iter().map(|report| report.monitor_id).collect(),
+            required,
+        }
+    }
+}
+
+/// A health check, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct CheckInfo<AccountId, BlockNumber> {
+    pub monitor_id: AccountId,
+    pub block_number: BlockNumber,
+    pub member_id: AccountId,
+    pub domain_id: DomainId,
+    pub service_id: ServiceId,
+    pub check_type: HealthCheckType,
+    pub level: MembershipLevel,
+    pub status: HealthCheckStatus,
+}
+
+impl<T: pallet_ibp_check::Config> From<HealthCheckData<T>>
+    for CheckInfo<T::AccountId, frame_system::pallet_prelude::BlockNumberFor<T>>
+{
+    fn from(data: HealthCheckData<T>) -> Self {
+        Self {
+            monitor_id: data.monitor_id,
+            block_number: data.block_number,
+            member_id: data.member_id,
+            domain_id: data.domain_id,
+            service_id: data.service_id,
+            check_type: data.check_type,
+            level: data.level,
+            status: data.status,
+        }
+    }
+}
+
+/// Uptime and latency of a (member, service) pair over one epoch, as returned by the runtime API
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+pub struct RollupInfo {
+    pub epoch: EpochIndex,
+    pub up: u32,
+    pub down: u32,
+    pub total: u32,
+    pub uptime: Perbill,
+    /// Latency percentiles in milliseconds, bucket upper bounds, `None` without latency samples
+    pub latency_p50: Option<u32>,
+    pub latency_p90: Option<u32>,
+    pub latency_p99: Option<u32>,
+}
+
+impl From<UptimeRollup> for RollupInfo {
+    fn from(rollup: UptimeRollup) -> Self {
+        Self {
+            epoch: rollup.epoch,
+            up: rollup.up,
+            down: rollup.down,
+            total: rollup.total,
+            uptime: rollup.uptime(),
+            latency_p50: rollup.latency_percentile(Percent::from_percent(50)),
+            latency_p90: rollup.latency_percentile(Percent::from_percent(90)),
+            latency_p99: rollup.latency_percentile(Percent::from_percent(99)),
+        }
+    }
+}
+
+sp_api::decl_runtime_apis! {
+    /// Query IBP members, services, alerts and health checks
+    ///
+    /// Methods added after version 1 carry the version they were added in, runtimes opt in with
+    /// `#[api_version(N)]` on their implementation:
+    /// 2: `member_audit_log`
+    /// 3: `uptime_rollups`
+    /// 4: `pending_alerts`
+    /// 5: `AlertInfo` carries severity and lifecycle, `alert_stats`
+    /// 6: `alert_types`
+    pub trait IbpApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// A single member
+        fn member(id: AccountId) -> Option<MemberInfo<AccountId>>;
+        /// All members
+        fn members() -> Vec<MemberInfo<AccountId>>;
+        /// The most recent changes made to a member, oldest first
+        #[api_version(2)]
+        fn member_audit_log(id: AccountId) -> Vec<AuditEntry<AccountId, BlockNumber>>;
+        /// A single service
+        fn service(id: ServiceId) -> Option<ServiceInfo>;
+        /// All services
+        fn services() -> Vec<ServiceInfo>;
+        /// The services a member must provide, based on membership level and overrides
+        fn services_for_member(id: AccountId) -> Vec<ServiceInfo>;
+        /// The members that must provide a service, based on membership level and overrides
+        fn members_for_service(service_id: ServiceId) -> Vec<MemberInfo<AccountId>>;
+        /// Current alerts matching the filter
+        #[changed_in(5)]
+        fn active_alerts(filter: AlertFilter<AccountId>) -> Vec<AlertInfoBeforeV5<AccountId>>;
+        /// Current alerts matching the filter
+        fn active_alerts(filter: AlertFilter<AccountId>) -> Vec<AlertInfo<AccountId>>;
+        /// Alerts waiting for quorum matching the filter, `monitor_id` matches any reporting monitor
+        #[api_version(4)]
+        fn pending_alerts(filter: AlertFilter<AccountId>) -> Vec<PendingAlertInfo<AccountId, BlockNumber>>;
+        /// Mean time to acknowledge and to resolve over the recently resolved alerts of a member
+        #[api_version(5)]
+        fn alert_stats(member: AccountId) -> AlertStats;
+        /// The registered alert types, by code
+        #[api_version(6)]
+        fn alert_types() -> Vec<(AlertType, AlertTypeInfo<BlockNumber>)>;
+        /// Retained health checks for a (member, service) pair, oldest first
+        fn recent_checks(member: AccountId, service: ServiceId) -> Vec<CheckInfo<AccountId, BlockNumber>>;
+        /// Retained uptime rollups for a (member, service) pair, oldest epoch first
+        #[api_version(3)]
+        fn uptime_rollups(member: AccountId, service: ServiceId) -> Vec<RollupInfo>;
+    }
+
+    /// Preview IBP member rewards
+    pub trait IbpRewardApi<AccountId, Balance>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// The payout a member can expect for the era in progress, and its unclaimed payouts
+        fn next_payout(member: AccountId) -> PayoutPreview<Balance>;
+    }
+}
@@ -1,35 +1,498 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-ibp-service
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as IbpService;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{pallet_prelude::PhantomData, traits::Get};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_ibp_member::{MemberData, MemberStatus, MembershipLevel, Members, StatusReason};
+
+const SEED: u32 = 0;
+
+fn service_id(value: &[u8]) -> ServiceId {
+	ServiceId::try_from(value.to_vec()).expect("id fits in 32 bytes")
+}
+
+fn add_curator<T: Config>() -> T::AccountId {
+	let curator: T::AccountId = whitelisted_caller();
+	pallet_ibp_member::Pallet::<T>::assign_curator(RawOrigin::Root.into(), curator.clone())
+		.expect("curator is assigned");
+	curator
+}
+
+fn add_member<T: Config>() -> T::AccountId {
+	let member_id: T::AccountId = account("member", 0, SEED);
+	let member_data = MemberData::<T> {
+		id: Some(member_id.clone()),
+		name: Default::default(),
+		status: MemberStatus::Active,
+		level: MembershipLevel::One,
+	};
+	Members::<T>::insert(&member_id, member_data);
+	member_id
+}
+
+fn chain(relation: ChainRelation) -> ChainData {
+	ChainData {
+		genesis_hash: [1u8; 32],
+		relation,
+		ss58_prefix: 0,
+		token_symbol: TokenSymbol::try_from(b"DOT".to_vec()).expect("symbol fits in 8 bytes"),
+		status: ChainStatus::Active,
+	}
+}
+
+fn reason() -> StatusReason {
+	StatusReason::try_from([b'x'; 64].to_vec()).expect("reason fits in 64 bytes")
+}
+
+fn add_relay_chain<T: Config>() -> ChainId {
+	let id = service_id(b"polkadot");
+	Chains::<T>::insert(&id, chain(ChainRelation::Relay));
+	id
+}
+
+fn domain_id(value: &[u8]) -> DomainId {
+	DomainId::try_from(value.to_vec()).expect("domain fits in 32 bytes")
+}
+
+fn hostname(value: &[u8]) -> Hostname {
+	Hostname::try_from(value.to_vec()).expect("hostname fits in 64 bytes")
+}
+
+fn add_domain<T: Config>(owner: DomainOwner<T::AccountId>) -> DomainId {
+	let id = domain_id(b"dotters.network");
+	Domains::<T>::insert(&id, DomainData { owner, status: DomainStatus::Active });
+	id
+}
+
+fn endpoint(url: &[u8]) -> Endpoint {
+	Endpoint {
+		url: EndpointUrl::try_from(url.to_vec()).expect("url fits in 256 bytes"),
+		protocol: EndpointProtocol::Wss,
+		region: Some(Region::try_from(b"eu-central".to_vec()).expect("region fits in 32 bytes")),
+	}
+}
+
+fn add_service<T: Config>(status: ServiceStatus) -> ServiceId {
+	let id = service_id(b"polkadot-rpc");
+	let service_data = ServiceData::<T> {
+		id: id.clone(),
+		chain_id: service_id(b"polkadot"),
+		service_type: ServiceType::RPC,
+		level: MembershipLevel::One,
+		status,
+		_marker: PhantomData,
+	};
+	Services::<T>::insert(&id, service_data);
+	id
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
-		let caller: T::AccountId = whitelisted_caller();
+	fn register_service() {
+		let curator = add_curator::<T>();
+		add_relay_chain::<T>();
+		let id = service_id(b"polkadot-rpc");
+		#[extrinsic_call]
+		register_service(
+			RawOrigin::Signed(curator),
+			id.clone(),
+			service_id(b"polkadot"),
+			ServiceType::RPC,
+			MembershipLevel::One,
+			ServiceStatus::Active,
+		);
+
+		assert!(Services::<T>::contains_key(&id));
+	}
+
+	#[benchmark]
+	fn lock_service() {
+		let curator = add_curator::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		#[extrinsic_call]
+		lock_service(RawOrigin::Signed(curator), id.clone(), reason());
+
+		assert_eq!(Services::<T>::get(&id).status, ServiceStatus::Locked);
+	}
+
+	#[benchmark]
+	fn unlock_service() {
+		let curator = add_curator::<T>();
+		let id = add_service::<T>(ServiceStatus::Locked);
+		#[extrinsic_call]
+		unlock_service(RawOrigin::Signed(curator), id.clone(), reason());
+
+		assert_eq!(Services::<T>::get(&id).status, ServiceStatus::Active);
+	}
+
+	#[benchmark]
+	fn delete_service() {
+		let curator = add_curator::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		#[extrinsic_call]
+		delete_service(RawOrigin::Signed(curator), id.clone(), reason());
+
+		assert_eq!(Services::<T>::get(&id).status, ServiceStatus::Deleted);
+	}
+
+	#[benchmark]
+	fn undelete_service() {
+		let curator = add_curator::<T>();
+		add_relay_chain::<T>();
+		let id = add_service::<T>(ServiceStatus::Deleted);
+		#[extrinsic_call]
+		undelete_service(RawOrigin::Signed(curator), id.clone(), reason());
+
+		assert_eq!(Services::<T>::get(&id).status, ServiceStatus::Locked);
+	}
+
+	#[benchmark]
+	fn update_service_level() {
+		let curator = add_curator::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		#[extrinsic_call]
+		update_service_level(RawOrigin::Signed(curator), id.clone(), MembershipLevel::Six);
+
+		assert_eq!(Services::<T>::get(&id).level, MembershipLevel::Six);
+	}
+
+	#[benchmark]
+	fn set_member_service_override() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		set_member_service_override(RawOrigin::Signed(curator), member_id.clone(), id.clone());
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert!(ServiceOverrides::<T>::contains_key((member_id, id)));
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
+	fn clear_service_override() {
 		let caller: T::AccountId = whitelisted_caller();
+		let id = add_service::<T>(ServiceStatus::Active);
+		ServiceOverrides::<T>::insert((caller.clone(), id.clone()), 1);
+		#[extrinsic_call]
+		clear_service_override(RawOrigin::Signed(caller.clone()), id.clone());
+
+		assert!(!ServiceOverrides::<T>::contains_key((caller, id)));
+	}
+
+	#[benchmark]
+	fn clear_member_service_override() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		ServiceOverrides::<T>::insert((member_id.clone(), id.clone()), 1);
+		#[extrinsic_call]
+		clear_member_service_override(RawOrigin::Signed(curator), member_id.clone(), id.clone());
+
+		assert!(!ServiceOverrides::<T>::contains_key((member_id, id)));
+	}
+
+	#[benchmark]
+	fn set_endpoint() {
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		let Endpoint { url, protocol, region } = endpoint(b"wss://rpc.example.com/polkadot");
+		#[extrinsic_call]
+		set_endpoint(RawOrigin::Signed(member_id.clone()), id.clone(), url, protocol, region);
+
+		assert!(MemberEndpoints::<T>::contains_key((member_id, id)));
+	}
+
+	#[benchmark]
+	fn update_endpoint() {
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		MemberEndpoints::<T>::insert((member_id.clone(), id.clone()), endpoint(b"wss://rpc.example.com/polkadot"));
+		let updated = endpoint(b"wss://rpc2.example.com/polkadot");
+		#[extrinsic_call]
+		update_endpoint(
+			RawOrigin::Signed(member_id.clone()),
+			id.clone(),
+			updated.url.clone(),
+			updated.protocol.clone(),
+			updated.region.clone(),
+		);
+
+		assert_eq!(MemberEndpoints::<T>::get((member_id, id)), Some(updated));
+	}
+
+	#[benchmark]
+	fn remove_endpoint() {
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		MemberEndpoints::<T>::insert((member_id.clone(), id.clone()), endpoint(b"wss://rpc.example.com/polkadot"));
+		#[extrinsic_call]
+		remove_endpoint(RawOrigin::Signed(member_id.clone()), id.clone());
+
+		assert!(!MemberEndpoints::<T>::contains_key((member_id, id)));
+	}
+
+	/// Worst case: a parachain, its relay chain is read
+	#[benchmark]
+	fn register_chain() {
+		let curator = add_curator::<T>();
+		let relay_chain = add_relay_chain::<T>();
+		let id = service_id(b"asset-hub-polkadot");
+		let ChainData { genesis_hash, relation, ss58_prefix, token_symbol, .. } =
+			chain(ChainRelation::Parachain { relay_chain, para_id: 1000 });
+		#[extrinsic_call]
+		register_chain(RawOrigin::Signed(curator), id.clone(), genesis_hash, relation, ss58_prefix, token_symbol);
+
+		assert!(Chains::<T>::contains_key(&id));
+	}
+
+	/// Worst case: a parachain moves to another relay chain, both relay chains are counted
+	#[benchmark]
+	fn update_chain() {
+		let curator = add_curator::<T>();
+		let relay_chain = add_relay_chain::<T>();
+		let other_relay_chain = service_id(b"kusama");
+		Chains::<T>::insert(&other_relay_chain, chain(ChainRelation::Relay));
+		let id = service_id(b"asset-hub-polkadot");
+		Chains::<T>::insert(&id, chain(ChainRelation::Parachain { relay_chain: relay_chain.clone(), para_id: 1000 }));
+		ParachainCount::<T>::insert(&relay_chain, 1);
+		ActiveParachainCount::<T>::insert(&relay_chain, 1);
+		let parachain = chain(ChainRelation::Parachain { relay_chain: other_relay_chain.clone(), para_id: 1000 });
+		#[extrinsic_call]
+		update_chain(
+			RawOrigin::Signed(curator),
+			id.clone(),
+			[2u8; 32],
+			parachain.relation,
+			parachain.ss58_prefix,
+			parachain.token_symbol,
+		);
+
+		assert_eq!(Chains::<T>::get(&id).map(|chain| chain.genesis_hash), Some([2u8; 32]));
+		assert_eq!(ParachainCount::<T>::get(&relay_chain), 0);
+		assert_eq!(ActiveParachainCount::<T>::get(&other_relay_chain), 1);
+	}
+
+	/// Every registered service belongs to the deprecated chain, a parachain
+	#[benchmark]
+	fn deprecate_chain(s: Linear<0, 100>) {
+		let curator = add_curator::<T>();
+		let relay_chain = add_relay_chain::<T>();
+		let chain_id = service_id(b"asset-hub-polkadot");
+		Chains::<T>::insert(&chain_id, chain(ChainRelation::Parachain { relay_chain: relay_chain.clone(), para_id: 1000 }));
+		ParachainCount::<T>::insert(&relay_chain, 1);
+		ActiveParachainCount::<T>::insert(&relay_chain, 1);
+		for i in 0..s {
+			let id = service_id(&[b"service-".as_slice(), &i.to_le_bytes()].concat());
+			let service_data = ServiceData::<T> {
+				id: id.clone(),
+				chain_id: chain_id.clone(),
+				service_type: ServiceType::RPC,
+				level: MembershipLevel::One,
+				status: ServiceStatus::Active,
+				_marker: PhantomData,
+			};
+			Services::<T>::insert(&id, service_data);
+			ChainServices::<T>::insert(&chain_id, &id, ());
+		}
+		#[extrinsic_call]
+		deprecate_chain(RawOrigin::Signed(curator), chain_id.clone(), s);
+
+		assert_eq!(Chains::<T>::get(&chain_id).map(|chain| chain.status), Some(ChainStatus::Deprecated));
+		assert_eq!(ActiveParachainCount::<T>::get(&relay_chain), 0);
+		assert!(Services::<T>::iter_values().all(|service| service.status == ServiceStatus::Deleted));
+	}
+
+	#[benchmark]
+	fn request_override() {
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		ServiceAssignments::<T>::insert(&member_id, &id, ());
+		let reason = OverrideReason::try_from([b'x'; 256].to_vec()).expect("reason fits in 256 bytes");
+		#[extrinsic_call]
+		request_override(RawOrigin::Signed(member_id.clone()), id.clone(), reason, 100u32.into());
+
+		assert!(OverrideRequests::<T>::contains_key((member_id, id)));
+	}
+
+	/// Worst case: every block up to the last one the expiry can be pushed back to is full
+	#[benchmark]
+	fn approve_override() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		let request = OverrideRequest { reason: Default::default(), expires_at: 100u32.into() };
+		OverrideRequests::<T>::insert((member_id.clone(), id.clone()), request);
+		for block in 100..100 + MAX_OVERRIDE_EXPIRY_DELAY {
+			for i in 0..T::MaxOverrideExpiries::get() {
+				let other: T::AccountId = account("other", block * 1_000 + i, SEED);
+				OverrideExpiries::<T>::try_mutate(BlockNumberFor::<T>::from(block), |expiring| {
+					expiring.try_push((other, id.clone()))
+				})
+				.expect("bucket has room");
+			}
+		}
+		#[extrinsic_call]
+		approve_override(RawOrigin::Signed(curator), member_id.clone(), id.clone());
+
+		let expires_at: BlockNumberFor<T> = (100 + MAX_OVERRIDE_EXPIRY_DELAY).into();
+		assert_eq!(OverrideExpiresAt::<T>::get((member_id.clone(), id.clone())), Some(expires_at));
+		assert!(ServiceOverrides::<T>::contains_key((member_id, id)));
+	}
+
+	#[benchmark]
+	fn reject_override() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		let request = OverrideRequest { reason: Default::default(), expires_at: 100u32.into() };
+		OverrideRequests::<T>::insert((member_id.clone(), id.clone()), request);
+		#[extrinsic_call]
+		reject_override(RawOrigin::Signed(curator), member_id.clone(), id.clone());
+
+		assert!(!OverrideRequests::<T>::contains_key((member_id, id)));
+	}
+
+	#[benchmark]
+	fn unchill_assignment() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_service::<T>(ServiceStatus::Active);
+		ChilledAssignments::<T>::insert(&member_id, &id, reason());
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		unchill_assignment(RawOrigin::Signed(curator), member_id.clone(), id.clone());
+
+		assert!(ServiceAssignments::<T>::contains_key(member_id, id));
+	}
+
+	#[benchmark]
+	fn expire_overrides(n: Linear<0, { T::MaxOverrideExpiries::get() }>) {
+		let id = add_service::<T>(ServiceStatus::Active);
+		let expires_at: BlockNumberFor<T> = 100u32.into();
+		for i in 0..n {
+			let member_id: T::AccountId = account("member", i, SEED);
+			let key = (member_id, id.clone());
+			ServiceOverrides::<T>::insert(&key, 1);
+			OverrideExpiresAt::<T>::insert(&key, expires_at);
+			OverrideExpiries::<T>::try_mutate(expires_at, |expiring| expiring.try_push(key))
+				.expect("n is at most MaxOverrideExpiries");
+		}
+		#[block]
+		{
+			IbpService::<T>::expire_overrides(expires_at);
+		}
+
+		assert_eq!(ServiceOverrides::<T>::iter().count(), 0);
+	}
+
+	#[benchmark]
+	fn register_domain() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = domain_id(b"dotters.network");
+		#[extrinsic_call]
+		register_domain(RawOrigin::Signed(curator), id.clone(), DomainOwner::Member(member_id));
+
+		assert!(Domains::<T>::contains_key(&id));
+	}
+
+	#[benchmark]
+	fn transfer_domain() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_domain::<T>(DomainOwner::Programme);
+		#[extrinsic_call]
+		transfer_domain(RawOrigin::Signed(curator), id.clone(), DomainOwner::Member(member_id.clone()));
+
+		assert_eq!(Domains::<T>::get(&id).map(|domain| domain.owner), Some(DomainOwner::Member(member_id)));
+	}
+
+	#[benchmark]
+	fn retire_domain() {
+		let curator = add_curator::<T>();
+		let id = add_domain::<T>(DomainOwner::Programme);
+		#[extrinsic_call]
+		retire_domain(RawOrigin::Signed(curator), id.clone());
+
+		assert_eq!(Domains::<T>::get(&id).map(|domain| domain.status), Some(DomainStatus::Retired));
+	}
+
+	/// Worst case: a curator managing a domain owned by a member
+	#[benchmark]
+	fn set_domain_service() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_domain::<T>(DomainOwner::Member(member_id));
+		let service = add_service::<T>(ServiceStatus::Active);
+		#[extrinsic_call]
+		set_domain_service(RawOrigin::Signed(curator), id.clone(), service.clone(), hostname(b"polkadot.dotters.network"));
+
+		assert!(DomainServices::<T>::contains_key(&id, &service));
+	}
+
+	#[benchmark]
+	fn remove_domain_service() {
+		let curator = add_curator::<T>();
+		let member_id = add_member::<T>();
+		let id = add_domain::<T>(DomainOwner::Member(member_id));
+		let service = add_service::<T>(ServiceStatus::Active);
+		DomainServices::<T>::insert(&id, &service, hostname(b"polkadot.dotters.network"));
+		#[extrinsic_call]
+		remove_domain_service(RawOrigin::Signed(curator), id.clone(), service.clone());
+
+		assert!(!DomainServices::<T>::contains_key(&id, &service));
+	}
+
+	/// Worst case: every service is required from the member, none is assigned yet
+	#[benchmark]
+	fn refresh_member_assignments(s: Linear<0, { T::MaxServices::get() }>) {
+		let member_id = add_member::<T>();
+		for i in 0..s {
+			let id = service_id(&[b"service-".as_slice(), &i.to_le_bytes()].concat());
+			let service_data = ServiceData::<T> {
+				id: id.clone(),
+				chain_id: service_id(b"polkadot"),
+				service_type: ServiceType::RPC,
+				level: MembershipLevel::None,
+				status: ServiceStatus::Active,
+				_marker: PhantomData,
+			};
+			Services::<T>::insert(&id, service_data);
+		}
+		#[block]
+		{
+			IbpService::<T>::refresh_member_assignments(&member_id);
+		}
+
+		assert_eq!(ServiceAssignments::<T>::iter_prefix(&member_id).count() as u32, s);
+	}
+
+	/// Worst case: every member must provide the service, none is assigned yet
+	#[benchmark]
+	fn refresh_service_assignments(m: Linear<0, { <T as pallet_ibp_member::Config>::MaxMembers::get() }>) {
+		let id = add_service::<T>(ServiceStatus::Active);
+		for i in 0..m {
+			let member_id: T::AccountId = account("member", i, SEED);
+			let member_data = MemberData::<T> {
+				id: Some(member_id.clone()),
+				name: Default::default(),
+				status: MemberStatus::Active,
+				level: MembershipLevel::One,
+			};
+			Members::<T>::insert(&member_id, member_data);
+		}
+		#[block]
+		{
+			IbpService::<T>::refresh_service_assignments(&id);
+		}
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(ServiceAssignments::<T>::iter().count() as u32, m);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(IbpService, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -5,8 +5,10 @@
 
 use codec::{ Decode, Encode, MaxEncodedLen };
 use scale_info::{self, TypeInfo};
+use serde::{Deserialize, Serialize};
 use frame_support::{
     traits::ConstU32,
+    weights::Weight,
     BoundedVec,
 };
 
@@ -27,18 +29,19 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 pub use weights::*;
+pub mod migrations;
 
 pub type ServiceId = BoundedVec<u8, ConstU32<32>>;
 pub type ChainId = BoundedVec<u8, ConstU32<32>>;
 
-#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum ServiceType {
     None = 0,
     RPC = 1,
     BOOT = 2,
 }
 
-#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Copy, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
 pub enum ServiceStatus {
     None = 0,
     Locked = 1,
@@ -47,6 +50,44 @@ pub enum ServiceStatus {
     Deleted = 9,
 }
 
+/// What moves a service from one status to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceAction {
+    Lock,
+    Unlock,
+    Delete,
+    Undelete,
+}
+
+/// Every allowed service status transition, as `(from, action, to)`. The calls changing the
+/// status of a service all go through this table, anything else is an `InvalidStatusTransition`.
+pub const SERVICE_TRANSITIONS: &[(ServiceStatus, ServiceAction, ServiceStatus)] = &[
+    (ServiceStatus::None, ServiceAction::Lock, ServiceStatus::Locked),
+    (ServiceStatus::Active, ServiceAction::Lock, ServiceStatus::Locked),
+    (ServiceStatus::Chilled, ServiceAction::Lock, ServiceStatus::Locked),
+    (ServiceStatus::Locked, ServiceAction::Unlock, ServiceStatus::Active),
+    (ServiceStatus::None, ServiceAction::Delete, ServiceStatus::Deleted),
+    (ServiceStatus::Locked, ServiceAction::Delete, ServiceStatus::Deleted),
+    (ServiceStatus::Active, ServiceAction::Delete, ServiceStatus::Deleted),
+    (ServiceStatus::Chilled, ServiceAction::Delete, ServiceStatus::Deleted),
+    // restored services come back locked, a curator unlocks them once members are ready
+    (ServiceStatus::Deleted, ServiceAction::Undelete, ServiceStatus::Locked),
+];
+
+/// Blocks an override expiry may be pushed back when the requested block is full, see
+/// `MaxOverrideExpiries`
+pub const MAX_OVERRIDE_EXPIRY_DELAY: u32 = 16;
+
+impl ServiceStatus {
+    /// The status `action` leads to from this one, `None` when `SERVICE_TRANSITIONS` does not allow it
+    pub fn transition(self, action: ServiceAction) -> Option<ServiceStatus> {
+        SERVICE_TRANSITIONS
+            .iter()
+            .find(|(from, allowed, _)| *from == self && *allowed == action)
+            .map(|(_, _, to)| *to)
+    }
+}
+
 impl Default for ServiceType {
     fn default() -> Self {
         ServiceType::RPC
@@ -59,6 +100,177 @@ impl Default for ServiceStatus {
     }
 }
 
+pub type TokenSymbol = BoundedVec<u8, ConstU32<8>>;
+
+/// How a chain relates to a relay chain
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum ChainRelation {
+    /// A standalone chain
+    Solo,
+    /// A relay chain, e.g. `polkadot`
+    Relay,
+    /// A parachain of a registered relay chain
+    Parachain { relay_chain: ChainId, para_id: u32 },
+}
+
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum ChainStatus {
+    Active = 0,
+    /// No longer served, its services are deleted and no new services are accepted
+    Deprecated = 1,
+}
+
+/// A chain IBP services are provided for
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub struct ChainData {
+    /// Hash of block 0, monitors check endpoints serve this chain
+    pub genesis_hash: [u8; 32],
+    pub relation: ChainRelation,
+    pub ss58_prefix: u16,
+    /// e.g. `DOT`
+    pub token_symbol: TokenSymbol,
+    pub status: ChainStatus,
+}
+
+pub type DomainId = BoundedVec<u8, ConstU32<32>>;
+pub type Hostname = BoundedVec<u8, ConstU32<64>>;
+
+/// Who owns an IBP domain
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum DomainOwner<AccountId> {
+    /// The IBP programme itself, e.g. `dotters.network`
+    Programme,
+    /// A member operating the domain
+    Member(AccountId),
+}
+
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum DomainStatus {
+    Active = 0,
+    /// No longer accepted for alerts and health checks
+    Retired = 1,
+}
+
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct DomainData<AccountId> {
+    pub owner: DomainOwner<AccountId>,
+    pub status: DomainStatus,
+}
+
+/// A domain name: dot separated labels of lowercase letters, digits and `-`, at least two labels
+pub fn is_valid_domain(name: &[u8]) -> bool {
+    let mut labels = 0;
+    for label in name.split(|c| *c == b'.') {
+        let valid = !label.is_empty()
+            && !label.starts_with(b"-")
+            && !label.ends_with(b"-")
+            && label.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-');
+        if !valid {
+            return false;
+        }
+        labels += 1;
+    }
+    labels >= 2
+}
+
+/// A hostname that is the domain itself or lives under it, e.g. `polkadot.dotters.network`
+pub fn is_host_in_domain(hostname: &[u8], domain_id: &[u8]) -> bool {
+    if !is_valid_domain(hostname) {
+        return false;
+    }
+    hostname == domain_id
+        || (hostname.len() > domain_id.len()
+            && hostname.ends_with(domain_id)
+            && hostname[hostname.len() - domain_id.len() - 1] == b'.')
+}
+
+/// Why a member asks to be exempted from a service
+pub type OverrideReason = BoundedVec<u8, ConstU32<256>>;
+
+/// A member's pending request to be exempted from a service until `expires_at`
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct OverrideRequest<BlockNumber> {
+    pub reason: OverrideReason,
+    pub expires_at: BlockNumber,
+}
+
+pub type EndpointUrl = BoundedVec<u8, ConstU32<256>>;
+pub type Region = BoundedVec<u8, ConstU32<32>>;
+
+/// How an endpoint is reached
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub enum EndpointProtocol {
+    /// `wss://` JSON-RPC endpoint
+    Wss = 0,
+    /// `https://` JSON-RPC endpoint
+    Https = 1,
+    /// libp2p multiaddr of a bootnode, e.g. `/dns/boot.example.com/tcp/30333/wss/p2p/12D3Koo...`
+    P2p = 2,
+}
+
+impl EndpointProtocol {
+    /// The protocols a service type can be served with
+    pub fn supports(&self, service_type: &ServiceType) -> bool {
+        match (service_type, self) {
+            (ServiceType::RPC, EndpointProtocol::Wss | EndpointProtocol::Https) => true,
+            (ServiceType::BOOT, EndpointProtocol::P2p) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether `url` is well formed for this protocol
+    pub fn is_valid_url(&self, url: &[u8]) -> bool {
+        let host_after = |scheme: &[u8]| url.len() > scheme.len() && url.starts_with(scheme);
+        match self {
+            EndpointProtocol::Wss => host_after(b"wss://"),
+            EndpointProtocol::Https => host_after(b"https://"),
+            // a multiaddr that ends with the peer id of the bootnode
+            EndpointProtocol::P2p => {
+                url.starts_with(b"/")
+                    && url
+                        .windows(5)
+                        .rposition(|w| w == b"/p2p/")
+                        .map_or(false, |at| at + 5 < url.len())
+            },
+        }
+    }
+}
+
+/// Where a member serves a service
+#[derive(Clone, Debug, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub url: EndpointUrl,
+    pub protocol: EndpointProtocol,
+    /// free form region, e.g. `eu-central`
+    pub region: Option<Region>,
+}
+
+/// Chills service assignments, e.g. when a member keeps failing a service. Implemented by this
+/// pallet, used by the alert escalation in `pallet-ibp-alert`.
+pub trait AssignmentEnforcement<AccountId> {
+    /// Stop assigning `service_id` to `member_id` until a curator unchills the assignment
+    fn chill(
+        member_id: &AccountId,
+        service_id: &ServiceId,
+        reason: pallet_ibp_member::StatusReason,
+    ) -> frame_support::dispatch::DispatchResult;
+    /// Upper bound of the weight of `chill`, the refresh of the assignment included
+    fn chill_weight() -> Weight;
+}
+
+impl<AccountId> AssignmentEnforcement<AccountId> for () {
+    fn chill(
+        _member_id: &AccountId,
+        _service_id: &ServiceId,
+        _reason: pallet_ibp_member::StatusReason,
+    ) -> frame_support::dispatch::DispatchResult {
+        Ok(())
+    }
+    fn chill_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
 #[frame_support::pallet]
 pub mod pallet {
@@ -69,11 +281,19 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     // use pallet_ibp_member::Pallet as MembersPallet; // Import the Members pallet
     use pallet_ibp_member::Pallet as MemberPallet;
-    use pallet_ibp_member::MembershipLevel;
+    use pallet_ibp_member::{MemberData, MemberStatus, MembershipLevel, OnMemberChanged, StatusReason};
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    /// The in-code storage version.
+    /// v1: added the `ServiceAssignments` of each member
+    /// v2: added `ServiceCount` and the `ChainServices` index
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
     // (`Call`s) in this pallet.
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -88,6 +308,13 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// A type representing the weights required by the dispatchables of this pallet.
         type WeightInfo: WeightInfo;
+        /// Approved overrides allowed to expire in the same block
+        #[pallet::constant]
+        type MaxOverrideExpiries: Get<u32>;
+        /// Maximum number of registered services, deleted ones included.
+        /// Bounds the refresh of a member's assignments.
+        #[pallet::constant]
+        type MaxServices: Get<u32>;
     }
 
     #[derive(Debug, Encode, Decode, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
@@ -142,10 +369,162 @@ pub mod pallet {
     #[pallet::getter(fn service_overrides)]
     pub type ServiceOverrides<T: Config> = StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId), u8, ValueQuery>;
 
+    /// Why the status of each service last changed
+    #[pallet::storage]
+    #[pallet::getter(fn service_status_reason)]
+    pub type ServiceStatusReasons<T: Config> = StorageMap<_, Blake2_128Concat, ServiceId, StatusReason, OptionQuery>;
+
+    /// Override requests waiting for a curator
+    #[pallet::storage]
+    #[pallet::getter(fn override_requests)]
+    pub type OverrideRequests<T: Config> =
+        StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId), OverrideRequest<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The block an approved override expires at
+    #[pallet::storage]
+    #[pallet::getter(fn override_expires_at)]
+    pub type OverrideExpiresAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId), BlockNumberFor<T>, OptionQuery>;
+
+    /// The approved overrides expiring at each block, cleared by `on_initialize`
+    #[pallet::storage]
+    pub type OverrideExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<(T::AccountId, ServiceId), T::MaxOverrideExpiries>,
+        ValueQuery,
+    >;
+
+    /// The endpoint each member declares for a service
+    #[pallet::storage]
+    #[pallet::getter(fn member_endpoints)]
+    pub type MemberEndpoints<T: Config> = StorageMap<_, Blake2_128Concat, (T::AccountId, ServiceId), Endpoint, OptionQuery>;
+
     // read the curators from the members pallet
     // #[pallet::storage]
     // pub type Curators<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
 
+    /// The services each member must provide, kept in line with `is_service_required` whenever a
+    /// member, a service or an override changes
+    #[pallet::storage]
+    #[pallet::getter(fn service_assignments)]
+    pub type ServiceAssignments<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, ServiceId, (), OptionQuery>;
+
+    /// Assignments chilled by `AssignmentEnforcement`, with the reason, until a curator unchills them
+    #[pallet::storage]
+    #[pallet::getter(fn chilled_assignments)]
+    pub type ChilledAssignments<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, ServiceId, StatusReason, OptionQuery>;
+
+    /// The chains services can be registered for
+    #[pallet::storage]
+    #[pallet::getter(fn chains)]
+    pub type Chains<T: Config> = StorageMap<_, Blake2_128Concat, ChainId, ChainData, OptionQuery>;
+
+    /// Number of entries in `Services`, at most `MaxServices`
+    #[pallet::storage]
+    pub type ServiceCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// The services registered for each chain
+    #[pallet::storage]
+    pub type ChainServices<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, ChainId, Blake2_128Concat, ServiceId, (), OptionQuery>;
+
+    /// Number of parachains registered under each relay chain, deprecated ones included
+    #[pallet::storage]
+    pub type ParachainCount<T: Config> = StorageMap<_, Blake2_128Concat, ChainId, u32, ValueQuery>;
+
+    /// Number of active parachains under each relay chain
+    #[pallet::storage]
+    pub type ActiveParachainCount<T: Config> = StorageMap<_, Blake2_128Concat, ChainId, u32, ValueQuery>;
+
+    /// IBP domains
+    #[pallet::storage]
+    #[pallet::getter(fn domains)]
+    pub type Domains<T: Config> = StorageMap<_, Blake2_128Concat, DomainId, DomainData<T::AccountId>, OptionQuery>;
+
+    /// The hostname each service is served on under a domain
+    #[pallet::storage]
+    #[pallet::getter(fn domain_services)]
+    pub type DomainServices<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DomainId, Blake2_128Concat, ServiceId, Hostname, OptionQuery>;
+
+    /// GenesisConfig - the initial service catalog
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Initial chains, relay chains before their parachains
+        pub chains: Vec<(ChainId, ChainData)>,
+        /// Initial services: (id, chain_id, service_type, level, status)
+        pub services: Vec<(ServiceId, ChainId, ServiceType, MembershipLevel, ServiceStatus)>,
+        /// Initial service overrides: (member, service)
+        pub service_overrides: Vec<(T::AccountId, ServiceId)>,
+        /// Initial domains: (domain, owner, [(service, hostname)])
+        pub domains: Vec<(DomainId, DomainOwner<T::AccountId>, Vec<(ServiceId, Hostname)>)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (chain_id, chain) in &self.chains {
+                assert!(!Chains::<T>::contains_key(chain_id), "duplicate chain in genesis");
+                Pallet::<T>::ensure_relay_chain(&chain.relation).expect("parachain of an unknown relay chain in genesis");
+                Chains::<T>::insert(chain_id, chain);
+                if chain.status == ChainStatus::Active {
+                    Pallet::<T>::add_parachain(&chain.relation);
+                } else if let ChainRelation::Parachain { relay_chain, .. } = &chain.relation {
+                    ParachainCount::<T>::mutate(relay_chain, |count| *count = count.saturating_add(1));
+                }
+            }
+            assert!(self.services.len() as u32 <= T::MaxServices::get(), "too many services in genesis");
+            for (id, chain_id, service_type, level, status) in &self.services {
+                assert!(!Services::<T>::contains_key(id), "duplicate service in genesis");
+                assert!(Chains::<T>::contains_key(chain_id), "service for unknown chain in genesis");
+                let service_data = ServiceData::<T> {
+                    id: id.clone(),
+                    chain_id: chain_id.clone(),
+                    service_type: service_type.clone(),
+                    level: *level,
+                    status: *status,
+                    _marker: PhantomData,
+                };
+                Services::<T>::insert(id, service_data);
+                ChainServices::<T>::insert(chain_id, id, ());
+            }
+            ServiceCount::<T>::put(self.services.len() as u32);
+            for (member_id, service_id) in &self.service_overrides {
+                // members are built before services, see construct_runtime
+                assert!(
+                    MemberPallet::<T>::members(member_id).id.is_some(),
+                    "service override for unknown member in genesis"
+                );
+                assert!(Services::<T>::contains_key(service_id), "service override for unknown service in genesis");
+                ServiceOverrides::<T>::insert((member_id.clone(), service_id.clone()), 1);
+            }
+            for (domain_id, owner, services) in &self.domains {
+                assert!(!Domains::<T>::contains_key(domain_id), "duplicate domain in genesis");
+                assert!(is_valid_domain(domain_id), "invalid domain in genesis");
+                if let DomainOwner::Member(member_id) = owner {
+                    assert!(
+                        MemberPallet::<T>::members(member_id).id.is_some(),
+                        "domain owned by unknown member in genesis"
+                    );
+                }
+                Domains::<T>::insert(domain_id, DomainData { owner: owner.clone(), status: DomainStatus::Active });
+                for (service_id, hostname) in services {
+                    assert!(Services::<T>::contains_key(service_id), "domain service for unknown service in genesis");
+                    assert!(is_host_in_domain(hostname, domain_id), "hostname outside its domain in genesis");
+                    DomainServices::<T>::insert(domain_id, service_id, hostname);
+                }
+            }
+            for member_id in pallet_ibp_member::Members::<T>::iter_keys() {
+                Pallet::<T>::refresh_member_assignments(&member_id);
+            }
+        }
+    }
+
     /// Events that functions in this pallet can emit.
     ///
     /// Events are a simple means of indicating to the outside world (such as dApps, chain explorers
@@ -161,15 +540,45 @@ pub mod pallet {
     pub enum Event<T: Config> {
         ServiceRegistered(ServiceId, MembershipLevel),
         // ServiceCreated(ServiceId, ServiceMembershipLevel),
-        ServiceStatusUpdated(ServiceId, ServiceStatus),
+        /// (service, previous status, status, reason)
+        ServiceStatusUpdated(ServiceId, ServiceStatus, ServiceStatus, StatusReason),
         ServiceMembershipLevelUpdated(ServiceId, MembershipLevel),
         ServiceLocked(ServiceId),
         ServiceUnlocked(ServiceId),
+        /// Unused, services are not chilled, their assignments are. Kept for the event indices.
         ServiceChilled(ServiceId),
+        /// Unused, see `AssignmentUnchilled`. Kept for the event indices.
         ServiceUnChilled(ServiceId),
         ServiceDeleted(ServiceId),
         ServiceOverrideSet((T::AccountId, ServiceId)),
         ServiceOverrideCleared((T::AccountId, ServiceId)),
+        /// (member, service, expires_at) a member asks to be exempted from a service
+        OverrideRequested(T::AccountId, ServiceId, BlockNumberFor<T>),
+        /// (member, service, expires_at) a curator approved an override request
+        OverrideApproved(T::AccountId, ServiceId, BlockNumberFor<T>),
+        /// (member, service) a curator rejected an override request
+        OverrideRejected(T::AccountId, ServiceId),
+        /// (member, service) an approved override reached its expiry
+        OverrideExpired(T::AccountId, ServiceId),
+        /// (member, service) the member must now provide the service
+        ServiceAssigned(T::AccountId, ServiceId),
+        /// (member, service) the member no longer has to provide the service
+        ServiceUnassigned(T::AccountId, ServiceId),
+        /// (member, service, reason) the assignment is chilled until a curator unchills it
+        AssignmentChilled(T::AccountId, ServiceId, StatusReason),
+        /// (member, service)
+        AssignmentUnchilled(T::AccountId, ServiceId),
+        EndpointSet(T::AccountId, ServiceId, Endpoint),
+        EndpointUpdated(T::AccountId, ServiceId, Endpoint),
+        EndpointRemoved(T::AccountId, ServiceId),
+        ChainRegistered(ChainId),
+        ChainUpdated(ChainId),
+        ChainDeprecated(ChainId),
+        DomainRegistered(DomainId, DomainOwner<T::AccountId>),
+        DomainTransferred(DomainId, DomainOwner<T::AccountId>),
+        DomainRetired(DomainId),
+        DomainServiceSet(DomainId, ServiceId, Hostname),
+        DomainServiceRemoved(DomainId, ServiceId),
     }
 
     /// Errors that can be returned by this pallet.
@@ -195,11 +604,57 @@ pub mod pallet {
         CannotRemoveLastCurator,
         ServiceOverrideExists,
         ServiceOverrideNotExists,
+        OverrideRequestExists,
+        OverrideRequestNotFound,
+        /// The requested expiry is not in the future
+        OverrideExpiryInPast,
+        /// `MaxOverrideExpiries` overrides already expire at the requested block and the
+        /// `MAX_OVERRIDE_EXPIRY_DELAY` blocks after it
+        TooManyOverrideExpiries,
+        /// Only assigned services can be overridden or chilled
+        ServiceNotAssigned,
+        AssignmentAlreadyChilled,
+        AssignmentNotChilled,
+        EndpointAlreadyExists,
+        EndpointNotFound,
+        /// The protocol cannot serve the service type, e.g. a BOOT service needs a p2p multiaddr
+        UnsupportedProtocol,
+        /// The URL does not match the protocol
+        InvalidEndpointUrl,
+        ChainAlreadyExists,
+        ChainNotFound,
+        ChainDeprecated,
+        /// A parachain must belong to an active relay chain
+        InvalidRelayChain,
+        /// The relay chain has parachains: deprecate them first, and its relation cannot change
+        ChainHasParachains,
+        /// `service_count` is lower than the number of services of the chain
+        BadServiceCount,
+        /// `MaxServices` services are registered
+        TooManyServices,
+        DomainAlreadyExists,
+        DomainNotFound,
+        DomainRetired,
+        /// Domains are lowercase names with at least two labels, e.g. `dotters.network`
+        InvalidDomain,
+        /// The hostname is not the domain or a name under it
+        InvalidHostname,
+        DomainServiceNotFound,
+        // Must be the member owning the domain or a curator
+        NotDomainOwnerOrCurator,
     }
 
     // import MemberNotFound error from the member pallet
     use pallet_ibp_member::Error as MemberError;
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Lift the approved overrides expiring at this block.
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::expire_overrides(block_number)
+        }
+    }
+
     /// The pallet's dispatchable functions ([`Call`]s).
     ///
     /// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -216,7 +671,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
 
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::register_service().saturating_add(Pallet::<T>::refresh_service_weight()))]
         pub fn register_service( origin: OriginFor<T>, 
             id: ServiceId,
             chain_id: ChainId,
@@ -228,10 +683,15 @@ pub mod pallet {
             ensure!(!Services::<T>::contains_key(&id), Error::<T>::ServiceAlreadyExists);
             // only curators can register services
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            let chain = Chains::<T>::get(&chain_id).ok_or(Error::<T>::ChainNotFound)?;
+            ensure!(chain.status == ChainStatus::Active, Error::<T>::ChainDeprecated);
+            let service_count = ServiceCount::<T>::get();
+            ensure!(service_count < T::MaxServices::get(), Error::<T>::TooManyServices);
             let service_id = id.clone();
             // let level = ServiceMembershipLevel::Zero;
             // let status = ServiceStatus::Locked;
             // let service_id = id.clone();
+            ChainServices::<T>::insert(&chain_id, &service_id, ());
             let service_data = ServiceData::<T> {
                 id: service_id.clone(),
                 chain_id,
@@ -241,81 +701,55 @@ pub mod pallet {
                 _marker: PhantomData,
             };
             Services::<T>::insert(service_id, service_data);
-            Self::deposit_event(Event::ServiceRegistered(id, level));
+            ServiceCount::<T>::put(service_count + 1);
+            Self::deposit_event(Event::ServiceRegistered(id.clone(), level));
+            Self::refresh_service_assignments(&id);
             Ok(())
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
-        pub fn lock_service(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::lock_service().saturating_add(Pallet::<T>::refresh_service_weight()))]
+        pub fn lock_service(origin: OriginFor<T>, service_id: ServiceId, reason: StatusReason) -> DispatchResult {
             let sender: T::AccountId = ensure_signed(origin)?;
             // only curators can lock services
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
-            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
-            Services::<T>::try_mutate(&service_id, |data_option| -> DispatchResult {
-                // check if the service exists
-                let data = data_option; // .as_mut(); //.ok_or(Error::<T, I>::ServiceNotFound)?;
-                ensure!(data.status != ServiceStatus::Deleted, Error::<T>::InvalidStatusTransition);
-                data.status = ServiceStatus::Locked;
-                Self::deposit_event(Event::ServiceStatusUpdated(service_id.clone(), ServiceStatus::Locked));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_service(&service_id, ServiceAction::Lock, reason)
         }
         
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
-        pub fn unlock_service(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::unlock_service().saturating_add(Pallet::<T>::refresh_service_weight()))]
+        pub fn unlock_service(origin: OriginFor<T>, service_id: ServiceId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only curators can unlock services
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
-            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
-            Services::<T>::try_mutate(&service_id, |data_opt| -> DispatchResult {
-                //ensure!(status != None, Error::<T>::ServiceNotFound);
-                let data = data_opt; // .as_mut(); //.ok_or(Error::<T>::ServiceNotFound)?;
-                ensure!(data.status == ServiceStatus::Locked, Error::<T>::InvalidStatusTransition);
-                data.status = ServiceStatus::Active;
-                Self::deposit_event(Event::ServiceStatusUpdated(service_id.clone(), ServiceStatus::Active));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_service(&service_id, ServiceAction::Unlock, reason)
         }
 
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
-        pub fn delete_service(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::delete_service().saturating_add(Pallet::<T>::refresh_service_weight()))]
+        pub fn delete_service(origin: OriginFor<T>, service_id: ServiceId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only curators can delete services
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
-            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
-            Services::<T>::try_mutate(&service_id, |data_opt| -> DispatchResult {
-                let data = data_opt; // .as_mut(); // .ok_or(Error::<T>::ServiceNotFound)?;
-                data.status = ServiceStatus::Deleted;
-                Self::deposit_event(Event::ServiceDeleted(service_id.clone()));
-                Ok(())
-            })?;
-            Ok(())
+            Self::transition_service(&service_id, ServiceAction::Delete, reason)
         }
 
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
-        pub fn undelete_service(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
+        #[pallet::weight(T::WeightInfo::undelete_service().saturating_add(Pallet::<T>::refresh_service_weight()))]
+        pub fn undelete_service(origin: OriginFor<T>, service_id: ServiceId, reason: StatusReason) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only curators can undelete services
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
-            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
-            Services::<T>::try_mutate(&service_id, |data_opt| -> DispatchResult {            
-                let data = data_opt; // .as_mut(); // .ok_or(Error::<T>::ServiceNotFound)?;
-                ensure!(data.status == ServiceStatus::Deleted, Error::<T>::InvalidStatusTransition);
-                data.status = ServiceStatus::Deleted;
-                Self::deposit_event(Event::ServiceStatusUpdated(service_id.clone(), ServiceStatus::Deleted));
-                Ok(())
-            })?;
-            Ok(())
+            // services of a deprecated chain stay deleted
+            let chain_id = Services::<T>::get(&service_id).chain_id;
+            if let Some(chain) = Chains::<T>::get(&chain_id) {
+                ensure!(chain.status == ChainStatus::Active, Error::<T>::ChainDeprecated);
+            }
+            Self::transition_service(&service_id, ServiceAction::Undelete, reason)
         }
 
         #[pallet::call_index(5)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_service_level().saturating_add(Pallet::<T>::refresh_service_weight()))]
         pub fn update_service_level(origin: OriginFor<T>, service_id: ServiceId, level: MembershipLevel) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             // only curators can change service levels
@@ -327,23 +761,12 @@ pub mod pallet {
                 Self::deposit_event(Event::ServiceMembershipLevelUpdated(service_id.clone(), level.clone()));
                 Ok(())
             })?;
-            Ok(())
-        }
-
-        #[pallet::call_index(20)]
-        #[pallet::weight(10_000)]
-        pub fn set_service_override(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
-            let key = (sender.clone(), service_id.clone());
-            ensure!(!ServiceOverrides::<T>::contains_key(key.clone()), Error::<T>::ServiceOverrideExists);
-            ServiceOverrides::<T>::insert(key.clone(), 1);
-            Self::deposit_event(Event::ServiceOverrideSet(key));
+            Self::refresh_service_assignments(&service_id);
             Ok(())
         }
 
         #[pallet::call_index(21)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::set_member_service_override())]
         pub fn set_member_service_override(origin: OriginFor<T>, member_id: T::AccountId, service_id: ServiceId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             let member = MemberPallet::<T>::members(&member_id);
@@ -356,24 +779,26 @@ pub mod pallet {
             let key = (member_id.clone(), service_id.clone());
             ensure!(!ServiceOverrides::<T>::contains_key(key.clone()), Error::<T>::ServiceOverrideExists);
             ServiceOverrides::<T>::insert(key.clone(), 1);
-            Self::deposit_event(Event::ServiceOverrideSet(key));
+            Self::deposit_event(Event::ServiceOverrideSet(key.clone()));
+            Self::refresh_assignment(&key.0, &key.1);
             Ok(())
         }
 
         #[pallet::call_index(30)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::clear_service_override())]
         pub fn clear_service_override(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
             let key = (sender.clone(), service_id.clone());
             ensure!(ServiceOverrides::<T>::contains_key(key.clone()), Error::<T>::ServiceOverrideNotExists);
-            ServiceOverrides::<T>::remove(key.clone());
-            Self::deposit_event(Event::ServiceOverrideCleared(key));
+            Self::remove_override(&key);
+            Self::deposit_event(Event::ServiceOverrideCleared(key.clone()));
+            Self::refresh_assignment(&key.0, &key.1);
             Ok(())
         }
 
         #[pallet::call_index(31)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::clear_member_service_override())]
         pub fn clear_member_service_override(origin: OriginFor<T>, member_id: T::AccountId, service_id: ServiceId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             let member = MemberPallet::<T>::members(&member_id);
@@ -386,8 +811,306 @@ pub mod pallet {
             ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
             let key = (member_id.clone(), service_id.clone());
             ensure!(ServiceOverrides::<T>::contains_key(key.clone()), Error::<T>::ServiceOverrideNotExists);
-            ServiceOverrides::<T>::remove(key.clone());
-            Self::deposit_event(Event::ServiceOverrideCleared(key));
+            Self::remove_override(&key);
+            Self::deposit_event(Event::ServiceOverrideCleared(key.clone()));
+            Self::refresh_assignment(&key.0, &key.1);
+            Ok(())
+        }
+
+        /// Ask to be exempted from a service until `expires_at`, pending a curator's approval.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::request_override())]
+        pub fn request_override(
+            origin: OriginFor<T>,
+            service_id: ServiceId,
+            reason: OverrideReason,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let member = MemberPallet::<T>::members(&sender);
+            ensure!(
+                member.id.is_some() && member.status != MemberStatus::Deleted,
+                MemberError::<T>::MemberNotFound
+            );
+            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
+            // only a service the member must provide can be overridden
+            ensure!(ServiceAssignments::<T>::contains_key(&sender, &service_id), Error::<T>::ServiceNotAssigned);
+            let key = (sender.clone(), service_id.clone());
+            ensure!(!ServiceOverrides::<T>::contains_key(&key), Error::<T>::ServiceOverrideExists);
+            ensure!(!OverrideRequests::<T>::contains_key(&key), Error::<T>::OverrideRequestExists);
+            ensure!(expires_at > frame_system::Pallet::<T>::block_number(), Error::<T>::OverrideExpiryInPast);
+            OverrideRequests::<T>::insert(&key, OverrideRequest { reason, expires_at });
+            Self::deposit_event(Event::OverrideRequested(sender, service_id, expires_at));
+            Ok(())
+        }
+
+        /// Approve a member's override request, the member is exempted until the requested expiry,
+        /// or the next block with room in `OverrideExpiries`.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::approve_override())]
+        pub fn approve_override(origin: OriginFor<T>, member_id: T::AccountId, service_id: ServiceId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can approve overrides
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            let key = (member_id.clone(), service_id.clone());
+            let request = OverrideRequests::<T>::get(&key).ok_or(Error::<T>::OverrideRequestNotFound)?;
+            ensure!(!ServiceOverrides::<T>::contains_key(&key), Error::<T>::ServiceOverrideExists);
+            ensure!(
+                request.expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::OverrideExpiryInPast
+            );
+            let expires_at = Self::schedule_override_expiry(&key, request.expires_at)?;
+            OverrideRequests::<T>::remove(&key);
+            OverrideExpiresAt::<T>::insert(&key, expires_at);
+            ServiceOverrides::<T>::insert(&key, 1);
+            Self::deposit_event(Event::OverrideApproved(member_id.clone(), service_id.clone(), expires_at));
+            Self::refresh_assignment(&member_id, &service_id);
+            Ok(())
+        }
+
+        /// Reject a member's override request.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::reject_override())]
+        pub fn reject_override(origin: OriginFor<T>, member_id: T::AccountId, service_id: ServiceId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can reject overrides
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            let key = (member_id.clone(), service_id.clone());
+            ensure!(OverrideRequests::<T>::contains_key(&key), Error::<T>::OverrideRequestNotFound);
+            OverrideRequests::<T>::remove(&key);
+            Self::deposit_event(Event::OverrideRejected(member_id, service_id));
+            Ok(())
+        }
+
+        /// Assign a chilled service to the member again, if the member must still provide it.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::unchill_assignment())]
+        pub fn unchill_assignment(origin: OriginFor<T>, member_id: T::AccountId, service_id: ServiceId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can unchill assignments
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            ensure!(ChilledAssignments::<T>::contains_key(&member_id, &service_id), Error::<T>::AssignmentNotChilled);
+            ChilledAssignments::<T>::remove(&member_id, &service_id);
+            Self::deposit_event(Event::AssignmentUnchilled(member_id.clone(), service_id.clone()));
+            Self::refresh_assignment(&member_id, &service_id);
+            Ok(())
+        }
+
+        /// Declare the endpoint the sender serves a service on.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::set_endpoint())]
+        pub fn set_endpoint(
+            origin: OriginFor<T>,
+            service_id: ServiceId,
+            url: EndpointUrl,
+            protocol: EndpointProtocol,
+            region: Option<Region>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let key = (sender.clone(), service_id.clone());
+            ensure!(!MemberEndpoints::<T>::contains_key(&key), Error::<T>::EndpointAlreadyExists);
+            let endpoint = Self::validate_endpoint(&sender, &service_id, url, protocol, region)?;
+            MemberEndpoints::<T>::insert(&key, endpoint.clone());
+            Self::deposit_event(Event::EndpointSet(sender, service_id, endpoint));
+            Ok(())
+        }
+
+        /// Replace the endpoint the sender serves a service on.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::WeightInfo::update_endpoint())]
+        pub fn update_endpoint(
+            origin: OriginFor<T>,
+            service_id: ServiceId,
+            url: EndpointUrl,
+            protocol: EndpointProtocol,
+            region: Option<Region>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let key = (sender.clone(), service_id.clone());
+            ensure!(MemberEndpoints::<T>::contains_key(&key), Error::<T>::EndpointNotFound);
+            let endpoint = Self::validate_endpoint(&sender, &service_id, url, protocol, region)?;
+            MemberEndpoints::<T>::insert(&key, endpoint.clone());
+            Self::deposit_event(Event::EndpointUpdated(sender, service_id, endpoint));
+            Ok(())
+        }
+
+        /// Remove the endpoint the sender serves a service on. The service itself may already be
+        /// deleted.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::WeightInfo::remove_endpoint())]
+        pub fn remove_endpoint(origin: OriginFor<T>, service_id: ServiceId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let key = (sender.clone(), service_id.clone());
+            ensure!(MemberEndpoints::<T>::take(&key).is_some(), Error::<T>::EndpointNotFound);
+            Self::deposit_event(Event::EndpointRemoved(sender, service_id));
+            Ok(())
+        }
+
+        /// Register a chain services can be provided for.
+        #[pallet::call_index(60)]
+        #[pallet::weight(T::WeightInfo::register_chain())]
+        pub fn register_chain(
+            origin: OriginFor<T>,
+            chain_id: ChainId,
+            genesis_hash: [u8; 32],
+            relation: ChainRelation,
+            ss58_prefix: u16,
+            token_symbol: TokenSymbol,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can register chains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            ensure!(!Chains::<T>::contains_key(&chain_id), Error::<T>::ChainAlreadyExists);
+            Self::ensure_relay_chain(&relation)?;
+            Self::add_parachain(&relation);
+            let chain = ChainData { genesis_hash, relation, ss58_prefix, token_symbol, status: ChainStatus::Active };
+            Chains::<T>::insert(&chain_id, chain);
+            Self::deposit_event(Event::ChainRegistered(chain_id));
+            Ok(())
+        }
+
+        /// Correct the details of an active chain.
+        /// The relation of a relay chain with parachains cannot change.
+        #[pallet::call_index(61)]
+        #[pallet::weight(T::WeightInfo::update_chain())]
+        pub fn update_chain(
+            origin: OriginFor<T>,
+            chain_id: ChainId,
+            genesis_hash: [u8; 32],
+            relation: ChainRelation,
+            ss58_prefix: u16,
+            token_symbol: TokenSymbol,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can update chains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            Self::ensure_relay_chain(&relation)?;
+            // a chain cannot be its own relay chain
+            if let ChainRelation::Parachain { relay_chain, .. } = &relation {
+                ensure!(*relay_chain != chain_id, Error::<T>::InvalidRelayChain);
+            }
+            Chains::<T>::try_mutate(&chain_id, |chain_opt| -> DispatchResult {
+                let chain = chain_opt.as_mut().ok_or(Error::<T>::ChainNotFound)?;
+                ensure!(chain.status == ChainStatus::Active, Error::<T>::ChainDeprecated);
+                if chain.relation != relation {
+                    ensure!(ParachainCount::<T>::get(&chain_id) == 0, Error::<T>::ChainHasParachains);
+                    Self::remove_parachain(&chain.relation, true);
+                    Self::add_parachain(&relation);
+                }
+                *chain = ChainData { genesis_hash, relation, ss58_prefix, token_symbol, status: ChainStatus::Active };
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ChainUpdated(chain_id));
+            Ok(())
+        }
+
+        /// Deprecate a chain and delete all its services.
+        /// A relay chain can only be deprecated once its parachains are.
+        /// `service_count` must be at least the number of services of the chain, it bounds the weight.
+        #[pallet::call_index(62)]
+        #[pallet::weight(Pallet::<T>::deprecate_chain_weight(*service_count))]
+        pub fn deprecate_chain(origin: OriginFor<T>, chain_id: ChainId, service_count: u32) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            // only curators can deprecate chains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            let services: Vec<ServiceId> = ChainServices::<T>::iter_key_prefix(&chain_id).collect();
+            let total = services.len() as u32;
+            ensure!(total <= service_count, Error::<T>::BadServiceCount);
+            Chains::<T>::try_mutate(&chain_id, |chain_opt| -> DispatchResult {
+                let chain = chain_opt.as_mut().ok_or(Error::<T>::ChainNotFound)?;
+                ensure!(chain.status == ChainStatus::Active, Error::<T>::ChainDeprecated);
+                ensure!(ActiveParachainCount::<T>::get(&chain_id) == 0, Error::<T>::ChainHasParachains);
+                Self::remove_parachain(&chain.relation, false);
+                chain.status = ChainStatus::Deprecated;
+                Ok(())
+            })?;
+            let reason = StatusReason::truncate_from(b"chain-deprecated".to_vec());
+            for service_id in services {
+                if Services::<T>::get(&service_id).status != ServiceStatus::Deleted {
+                    Self::transition_service(&service_id, ServiceAction::Delete, reason.clone())?;
+                }
+            }
+            Self::deposit_event(Event::ChainDeprecated(chain_id));
+            Ok(Some(Self::deprecate_chain_weight(total)).into())
+        }
+
+        /// Register an IBP domain.
+        #[pallet::call_index(50)]
+        #[pallet::weight(T::WeightInfo::register_domain())]
+        pub fn register_domain(origin: OriginFor<T>, domain_id: DomainId, owner: DomainOwner<T::AccountId>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can register domains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            ensure!(!Domains::<T>::contains_key(&domain_id), Error::<T>::DomainAlreadyExists);
+            ensure!(is_valid_domain(&domain_id), Error::<T>::InvalidDomain);
+            Self::ensure_owner_exists(&owner)?;
+            Domains::<T>::insert(&domain_id, DomainData { owner: owner.clone(), status: DomainStatus::Active });
+            Self::deposit_event(Event::DomainRegistered(domain_id, owner));
+            Ok(())
+        }
+
+        /// Hand a domain over to another member or to the programme.
+        #[pallet::call_index(51)]
+        #[pallet::weight(T::WeightInfo::transfer_domain())]
+        pub fn transfer_domain(origin: OriginFor<T>, domain_id: DomainId, owner: DomainOwner<T::AccountId>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can transfer domains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            Self::ensure_owner_exists(&owner)?;
+            Domains::<T>::try_mutate(&domain_id, |data_opt| -> DispatchResult {
+                let data = data_opt.as_mut().ok_or(Error::<T>::DomainNotFound)?;
+                ensure!(data.status == DomainStatus::Active, Error::<T>::DomainRetired);
+                data.owner = owner.clone();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DomainTransferred(domain_id, owner));
+            Ok(())
+        }
+
+        /// Retire a domain, alerts and health checks are no longer accepted for it.
+        #[pallet::call_index(52)]
+        #[pallet::weight(T::WeightInfo::retire_domain())]
+        pub fn retire_domain(origin: OriginFor<T>, domain_id: DomainId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // only curators can retire domains
+            ensure!(MemberPallet::<T>::curators(&sender), Error::<T>::NotACurator);
+            Domains::<T>::try_mutate(&domain_id, |data_opt| -> DispatchResult {
+                let data = data_opt.as_mut().ok_or(Error::<T>::DomainNotFound)?;
+                ensure!(data.status == DomainStatus::Active, Error::<T>::DomainRetired);
+                data.status = DomainStatus::Retired;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DomainRetired(domain_id));
+            Ok(())
+        }
+
+        /// Set the hostname a service is served on under a domain.
+        #[pallet::call_index(53)]
+        #[pallet::weight(T::WeightInfo::set_domain_service())]
+        pub fn set_domain_service(
+            origin: OriginFor<T>,
+            domain_id: DomainId,
+            service_id: ServiceId,
+            hostname: Hostname,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let domain = Self::ensure_domain_manager(&sender, &domain_id)?;
+            ensure!(domain.status == DomainStatus::Active, Error::<T>::DomainRetired);
+            ensure!(Services::<T>::contains_key(&service_id), Error::<T>::ServiceNotFound);
+            ensure!(is_host_in_domain(&hostname, &domain_id), Error::<T>::InvalidHostname);
+            DomainServices::<T>::insert(&domain_id, &service_id, &hostname);
+            Self::deposit_event(Event::DomainServiceSet(domain_id, service_id, hostname));
+            Ok(())
+        }
+
+        /// Stop serving a service under a domain.
+        #[pallet::call_index(54)]
+        #[pallet::weight(T::WeightInfo::remove_domain_service())]
+        pub fn remove_domain_service(origin: OriginFor<T>, domain_id: DomainId, service_id: ServiceId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_domain_manager(&sender, &domain_id)?;
+            ensure!(DomainServices::<T>::take(&domain_id, &service_id).is_some(), Error::<T>::DomainServiceNotFound);
+            Self::deposit_event(Event::DomainServiceRemoved(domain_id, service_id));
             Ok(())
         }
 
@@ -425,6 +1148,282 @@ pub mod pallet {
         // }
 
     }
+
+    impl<T: Config> Pallet<T> {
+        /// A member must provide a service when the service is active, the member is not deleted,
+        /// the member level is at least the service level and the member has no override or
+        /// chilled assignment for it
+        pub fn is_service_required(member_id: &T::AccountId, member: &MemberData<T>, service: &ServiceData<T>) -> bool {
+            service.status == ServiceStatus::Active
+                && member.status != MemberStatus::Deleted
+                && member.level >= service.level
+                && !ServiceOverrides::<T>::contains_key((member_id, &service.id))
+                && !ChilledAssignments::<T>::contains_key(member_id, &service.id)
+        }
+
+        /// The services a member must provide
+        pub fn services_for_member(member_id: &T::AccountId) -> Vec<ServiceData<T>> {
+            Self::assigned_services(member_id)
+                .into_iter()
+                .map(|service_id| Services::<T>::get(&service_id))
+                .collect()
+        }
+
+        /// Move a service along `SERVICE_TRANSITIONS`, recording why
+        fn transition_service(service_id: &ServiceId, action: ServiceAction, reason: StatusReason) -> DispatchResult {
+            ensure!(Services::<T>::contains_key(service_id), Error::<T>::ServiceNotFound);
+            Services::<T>::try_mutate(service_id, |data| -> DispatchResult {
+                let prev_status = data.status;
+                data.status = prev_status.transition(action).ok_or(Error::<T>::InvalidStatusTransition)?;
+                match action {
+                    ServiceAction::Lock => Self::deposit_event(Event::ServiceLocked(service_id.clone())),
+                    ServiceAction::Unlock => Self::deposit_event(Event::ServiceUnlocked(service_id.clone())),
+                    ServiceAction::Delete => Self::deposit_event(Event::ServiceDeleted(service_id.clone())),
+                    ServiceAction::Undelete => {},
+                }
+                Self::deposit_event(Event::ServiceStatusUpdated(
+                    service_id.clone(),
+                    prev_status,
+                    data.status,
+                    reason.clone(),
+                ));
+                Ok(())
+            })?;
+            ServiceStatusReasons::<T>::insert(service_id, reason);
+            Self::refresh_service_assignments(service_id);
+            Ok(())
+        }
+
+        /// Queue the expiry of an override in the first block from `from` with room left, at most
+        /// `MAX_OVERRIDE_EXPIRY_DELAY` blocks later. Returns the block it expires in.
+        fn schedule_override_expiry(
+            key: &(T::AccountId, ServiceId),
+            from: BlockNumberFor<T>,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            let max_expiries = T::MaxOverrideExpiries::get() as usize;
+            let mut block_number = from;
+            for _ in 0..=MAX_OVERRIDE_EXPIRY_DELAY {
+                if OverrideExpiries::<T>::decode_len(block_number).unwrap_or(0) < max_expiries {
+                    OverrideExpiries::<T>::try_mutate(block_number, |expiring| expiring.try_push(key.clone()))
+                        .map_err(|_| Error::<T>::TooManyOverrideExpiries)?;
+                    return Ok(block_number);
+                }
+                block_number += 1u32.into();
+            }
+            Err(Error::<T>::TooManyOverrideExpiries.into())
+        }
+
+        /// Remove an override along with its scheduled expiry, if any
+        fn remove_override(key: &(T::AccountId, ServiceId)) {
+            ServiceOverrides::<T>::remove(key);
+            if let Some(expires_at) = OverrideExpiresAt::<T>::take(key) {
+                OverrideExpiries::<T>::mutate(expires_at, |expiring| expiring.retain(|k| k != key));
+            }
+        }
+
+        /// Lift the overrides expiring at `block_number`, returns the weight used
+        pub(crate) fn expire_overrides(block_number: BlockNumberFor<T>) -> Weight {
+            let expiring = OverrideExpiries::<T>::take(block_number);
+            let count = expiring.len() as u32;
+            for (member_id, service_id) in expiring {
+                let key = (member_id.clone(), service_id.clone());
+                ServiceOverrides::<T>::remove(&key);
+                OverrideExpiresAt::<T>::remove(&key);
+                Self::deposit_event(Event::OverrideExpired(member_id.clone(), service_id.clone()));
+                Self::refresh_assignment(&member_id, &service_id);
+            }
+            T::WeightInfo::expire_overrides(count)
+        }
+
+        /// The ids of the services assigned to a member
+        pub fn assigned_services(member_id: &T::AccountId) -> Vec<ServiceId> {
+            ServiceAssignments::<T>::iter_key_prefix(member_id).collect()
+        }
+
+        /// Bring the assignment of a service to a member in line with `is_service_required`
+        pub(crate) fn refresh_assignment(member_id: &T::AccountId, service_id: &ServiceId) {
+            let member = MemberPallet::<T>::members(member_id);
+            let service = Services::<T>::get(service_id);
+            Self::apply_assignment(member_id, &member, &service);
+        }
+
+        /// Upper bound of the weight of `refresh_service_assignments`, at `MaxMembers` members
+        pub(crate) fn refresh_service_weight() -> Weight {
+            T::WeightInfo::refresh_service_assignments(T::MaxMembers::get())
+        }
+
+        /// Deleting each service of the chain refreshes its assignments
+        pub(crate) fn deprecate_chain_weight(service_count: u32) -> Weight {
+            T::WeightInfo::deprecate_chain(service_count)
+                .saturating_add(Self::refresh_service_weight().saturating_mul(service_count.into()))
+        }
+
+        /// Recompute every assignment of a member, e.g. after a level or status change
+        pub(crate) fn refresh_member_assignments(member_id: &T::AccountId) {
+            let member = MemberPallet::<T>::members(member_id);
+            for service in Services::<T>::iter_values() {
+                Self::apply_assignment(member_id, &member, &service);
+            }
+        }
+
+        /// Recompute the assignment of a service to every member
+        pub(crate) fn refresh_service_assignments(service_id: &ServiceId) {
+            let service = Services::<T>::get(service_id);
+            for (member_id, member) in pallet_ibp_member::Members::<T>::iter() {
+                Self::apply_assignment(&member_id, &member, &service);
+            }
+        }
+
+        fn apply_assignment(member_id: &T::AccountId, member: &MemberData<T>, service: &ServiceData<T>) {
+            let required = member.id.is_some() && Self::is_service_required(member_id, member, service);
+            let assigned = ServiceAssignments::<T>::contains_key(member_id, &service.id);
+            if required && !assigned {
+                ServiceAssignments::<T>::insert(member_id, &service.id, ());
+                Self::deposit_event(Event::ServiceAssigned(member_id.clone(), service.id.clone()));
+            } else if !required && assigned {
+                ServiceAssignments::<T>::remove(member_id, &service.id);
+                Self::deposit_event(Event::ServiceUnassigned(member_id.clone(), service.id.clone()));
+            }
+        }
+
+        /// The endpoints a member has declared, by service
+        pub fn endpoints_for_member(member_id: &T::AccountId) -> Vec<(ServiceId, Endpoint)> {
+            Services::<T>::iter_keys()
+                .filter_map(|service_id| {
+                    MemberEndpoints::<T>::get((member_id, &service_id)).map(|endpoint| (service_id, endpoint))
+                })
+                .collect()
+        }
+
+        /// Check an endpoint against the member and the service it is declared for
+        fn validate_endpoint(
+            member_id: &T::AccountId,
+            service_id: &ServiceId,
+            url: EndpointUrl,
+            protocol: EndpointProtocol,
+            region: Option<Region>,
+        ) -> Result<Endpoint, DispatchError> {
+            let member = MemberPallet::<T>::members(member_id);
+            ensure!(member.id.is_some() && member.status != MemberStatus::Deleted, MemberError::<T>::MemberNotFound);
+            ensure!(Services::<T>::contains_key(service_id), Error::<T>::ServiceNotFound);
+            let service = Services::<T>::get(service_id);
+            ensure!(service.status != ServiceStatus::Deleted, Error::<T>::ServiceNotFound);
+            ensure!(protocol.supports(&service.service_type), Error::<T>::UnsupportedProtocol);
+            ensure!(protocol.is_valid_url(&url), Error::<T>::InvalidEndpointUrl);
+            Ok(Endpoint { url, protocol, region })
+        }
+
+        /// Whether alerts and health checks may be reported for a service under a domain
+        pub fn is_domain_service(domain_id: &DomainId, service_id: &ServiceId) -> bool {
+            Domains::<T>::get(domain_id).map_or(false, |domain| domain.status == DomainStatus::Active)
+                && DomainServices::<T>::contains_key(domain_id, service_id)
+        }
+
+        /// The active domain a service hostname is registered under
+        pub fn domain_for_host(service_id: &ServiceId, hostname: &[u8]) -> Option<DomainId> {
+            DomainServices::<T>::iter()
+                .find(|(_, id, host)| id == service_id && host.as_slice() == hostname)
+                .map(|(domain_id, _, _)| domain_id)
+                .filter(|domain_id| Self::is_domain_service(domain_id, service_id))
+        }
+
+        /// The genesis hash of the chain a service is provided for
+        pub fn genesis_hash(service_id: &ServiceId) -> Option<[u8; 32]> {
+            Chains::<T>::get(&Services::<T>::get(service_id).chain_id).map(|chain| chain.genesis_hash)
+        }
+
+        /// Count a new active parachain under its relay chain
+        fn add_parachain(relation: &ChainRelation) {
+            if let ChainRelation::Parachain { relay_chain, .. } = relation {
+                ParachainCount::<T>::mutate(relay_chain, |count| *count = count.saturating_add(1));
+                ActiveParachainCount::<T>::mutate(relay_chain, |count| *count = count.saturating_add(1));
+            }
+        }
+
+        /// Stop counting an active parachain, `unregister` when it leaves its relay chain
+        /// rather than being deprecated
+        fn remove_parachain(relation: &ChainRelation, unregister: bool) {
+            if let ChainRelation::Parachain { relay_chain, .. } = relation {
+                if unregister {
+                    ParachainCount::<T>::mutate(relay_chain, |count| *count = count.saturating_sub(1));
+                }
+                ActiveParachainCount::<T>::mutate(relay_chain, |count| *count = count.saturating_sub(1));
+            }
+        }
+
+        /// Parachains must name an active relay chain
+        fn ensure_relay_chain(relation: &ChainRelation) -> DispatchResult {
+            if let ChainRelation::Parachain { relay_chain, .. } = relation {
+                let relay = Chains::<T>::get(relay_chain).ok_or(Error::<T>::InvalidRelayChain)?;
+                ensure!(
+                    relay.relation == ChainRelation::Relay && relay.status == ChainStatus::Active,
+                    Error::<T>::InvalidRelayChain
+                );
+            }
+            Ok(())
+        }
+
+        fn ensure_owner_exists(owner: &DomainOwner<T::AccountId>) -> DispatchResult {
+            if let DomainOwner::Member(member_id) = owner {
+                let member = MemberPallet::<T>::members(member_id);
+                ensure!(member.id.is_some() && member.status != MemberStatus::Deleted, MemberError::<T>::MemberNotFound);
+            }
+            Ok(())
+        }
+
+        /// Curators manage every domain, members the domains they own
+        fn ensure_domain_manager(
+            sender: &T::AccountId,
+            domain_id: &DomainId,
+        ) -> Result<DomainData<T::AccountId>, DispatchError> {
+            let domain = Domains::<T>::get(domain_id).ok_or(Error::<T>::DomainNotFound)?;
+            let is_owner = matches!(&domain.owner, DomainOwner::Member(owner) if owner == sender);
+            ensure!(is_owner || MemberPallet::<T>::curators(sender), Error::<T>::NotDomainOwnerOrCurator);
+            Ok(domain)
+        }
+
+        /// The members that must provide a service
+        pub fn members_for_service(service_id: &ServiceId) -> Vec<MemberData<T>> {
+            if !Services::<T>::contains_key(service_id) {
+                return Vec::new();
+            }
+            let service = Services::<T>::get(service_id);
+            pallet_ibp_member::Members::<T>::iter()
+                .filter(|(member_id, member)| Self::is_service_required(member_id, member, &service))
+                .map(|(_, member)| member)
+                .collect()
+        }
+    }
+
+    /// Member registration, status and level changes move the member's service assignments
+    impl<T: Config> OnMemberChanged<T::AccountId> for Pallet<T> {
+        fn on_member_changed(member_id: &T::AccountId) {
+            Self::refresh_member_assignments(member_id);
+        }
+
+        fn on_member_changed_weight() -> Weight {
+            T::WeightInfo::refresh_member_assignments(T::MaxServices::get())
+        }
+    }
+
+    impl<T: Config> AssignmentEnforcement<T::AccountId> for Pallet<T> {
+        fn chill(member_id: &T::AccountId, service_id: &ServiceId, reason: StatusReason) -> DispatchResult {
+            ensure!(ServiceAssignments::<T>::contains_key(member_id, service_id), Error::<T>::ServiceNotAssigned);
+            ensure!(
+                !ChilledAssignments::<T>::contains_key(member_id, service_id),
+                Error::<T>::AssignmentAlreadyChilled
+            );
+            ChilledAssignments::<T>::insert(member_id, service_id, reason.clone());
+            Self::deposit_event(Event::AssignmentChilled(member_id.clone(), service_id.clone(), reason));
+            Self::refresh_assignment(member_id, service_id);
+            Ok(())
+        }
+
+        // unchilling reads and writes the same, the refresh included
+        fn chill_weight() -> Weight {
+            T::WeightInfo::unchill_assignment()
+        }
+    }
 }
 
 pub use pallet::*;
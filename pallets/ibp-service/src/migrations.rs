@@ -0,0 +1,95 @@
+//! Storage migrations for the service pallet.
+
+use super::*;
+use frame_support::{
+    traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+#[cfg(feature = "try-runtime")]
+use frame_support::{ensure, sp_runtime::TryRuntimeError};
+#[cfg(feature = "try-runtime")]
+extern crate alloc;
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+
+pub mod v1 {
+    use super::*;
+
+    /// Build the `ServiceAssignments` of every member from the existing members, services and
+    /// overrides.
+    pub struct BuildServiceAssignments<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for BuildServiceAssignments<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+            let services = Services::<T>::iter_keys().count() as u64;
+            let mut reads = 1 + services;
+            let mut writes = 1u64;
+            for member_id in pallet_ibp_member::Members::<T>::iter_keys() {
+                // the member, then every service with its override and assignment
+                reads += 1 + services * 3;
+                writes += services;
+                Pallet::<T>::refresh_member_assignments(&member_id);
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            for (member_id, member) in pallet_ibp_member::Members::<T>::iter() {
+                for service in Services::<T>::iter_values() {
+                    ensure!(
+                        ServiceAssignments::<T>::contains_key(&member_id, &service.id)
+                            == Pallet::<T>::is_service_required(&member_id, &member, &service),
+                        "service assignment does not match the member and service"
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+
+    /// Count the existing services into `ServiceCount` and index them by chain in `ChainServices`.
+    pub struct IndexChainServices<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for IndexChainServices<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut count = 0u32;
+            for service in Services::<T>::iter_values() {
+                ChainServices::<T>::insert(&service.chain_id, &service.id, ());
+                count += 1;
+            }
+            ServiceCount::<T>::put(count);
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1 + count as u64, 2 + count as u64)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "storage version not updated");
+            let mut count = 0u32;
+            for service in Services::<T>::iter_values() {
+                ensure!(
+                    ChainServices::<T>::contains_key(&service.chain_id, &service.id),
+                    "service not indexed under its chain"
+                );
+                count += 1;
+            }
+            ensure!(ServiceCount::<T>::get() == count, "service count does not match the services");
+            Ok(())
+        }
+    }
+}
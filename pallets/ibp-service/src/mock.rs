@@ -1,7 +1,7 @@
-use crate::{self as pallet_template, MemberStatus};
+use crate as pallet_ibp_service;
 use frame_support::{
 	derive_impl,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -16,7 +16,9 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
-		TemplateModule: pallet_template,
+		Balances: pallet_balances,
+		IbpMember: pallet_ibp_member,
+		IbpService: pallet_ibp_service,
 	}
 );
 
@@ -38,7 +40,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -47,13 +49,39 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
 	type WeightInfo = ();
-  // type AccountId = u64;
-  type MemberStatus = u64;
-  type MembershipLevel = u64;
-  type MemberData = (MemberStatus, MembershipLevel);
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+impl pallet_ibp_member::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type DefaultSessionLength = ConstU64<10>;
+	type OnMemberChanged = IbpService;
+	type MaxAuditEntries = ConstU32<3>;
+	type Currency = Balances;
+	type UnbondingPeriod = ConstU64<10>;
+	type Slashed = ();
+	type MaxMembers = ConstU32<16>;
+}
+
+impl pallet_ibp_service::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxOverrideExpiries = ConstU32<4>;
+	type MaxServices = ConstU32<16>;
 }
 
 // Build genesis storage according to the mock runtime.
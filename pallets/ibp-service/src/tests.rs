@@ -1,27 +1,864 @@
-use crate::{mock::*, Error, Event, Something};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, AssignmentEnforcement, ChainRelation, ChainStatus, Chains, ChilledAssignments, DomainId, DomainOwner, DomainStatus, Domains, Endpoint, EndpointProtocol, EndpointUrl, Error, Event,
+	Hostname, MemberEndpoints, OverrideExpiries, OverrideReason, OverrideRequests, ServiceAction, ServiceAssignments, ServiceId, ServiceOverrides, ServiceStatus, ServiceStatusReasons,
+	ServiceType, Services, TokenSymbol, ServiceCount, ChainServices, WeightInfo,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchResult, GetDispatchInfo},
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use pallet_ibp_member::{MemberName, MembershipLevel, StatusReason};
+
+fn id(value: &str) -> ServiceId {
+	ServiceId::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+fn register_service(level: MembershipLevel) -> ServiceId {
+	assert_ok!(IbpService::register_service(
+		RuntimeOrigin::signed(1),
+		id("polkadot-rpc"),
+		id("polkadot"),
+		ServiceType::RPC,
+		level,
+		ServiceStatus::Active,
+	));
+	id("polkadot-rpc")
+}
+
+fn status_reason(value: &str) -> StatusReason {
+	StatusReason::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+fn setup_member(account_id: u64, level: MembershipLevel) {
+	let name = MemberName::try_from(account_id.to_be_bytes().to_vec()).unwrap();
+	assert_ok!(IbpMember::register_member(RuntimeOrigin::signed(account_id), name));
+	assert_ok!(IbpMember::unlock_member(RuntimeOrigin::signed(1), account_id, status_reason("onboarded")));
+	assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(1), account_id, level));
+}
+
+fn register_chain(chain_id: &str, relation: ChainRelation) -> DispatchResult {
+	IbpService::register_chain(
+		RuntimeOrigin::signed(1),
+		id(chain_id),
+		[1u8; 32],
+		relation,
+		0,
+		TokenSymbol::try_from(b"DOT".to_vec()).unwrap(),
+	)
+}
+
+/// A curator and the `polkadot` relay chain
+fn new_test_ext_with_curator() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(IbpMember::assign_curator(RuntimeOrigin::root(), 1));
+		assert_ok!(register_chain("polkadot", ChainRelation::Relay));
+	});
+	ext
+}
 
 #[test]
-fn it_works_for_default_value() {
-	new_test_ext().execute_with(|| {
+fn register_service_works() {
+	new_test_ext_with_curator().execute_with(|| {
 		// Go past genesis block so events get deposited
 		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(Something::<Test>::get(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		let service_id = register_service(MembershipLevel::Two);
+		assert_eq!(Services::<Test>::get(&service_id).level, MembershipLevel::Two);
+		System::assert_last_event(Event::ServiceRegistered(service_id, MembershipLevel::Two).into());
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
-	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+fn only_curators_can_register_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		assert_noop!(
+			IbpService::register_service(
+				RuntimeOrigin::signed(2),
+				id("polkadot-rpc"),
+				id("polkadot"),
+				ServiceType::RPC,
+				MembershipLevel::One,
+				ServiceStatus::Active,
+			),
+			Error::<Test>::NotACurator
+		);
+	});
+}
+
+#[test]
+fn services_for_member_follow_level_and_overrides() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::Two);
+		setup_member(2, MembershipLevel::One);
+		setup_member(3, MembershipLevel::Three);
+		assert!(IbpService::services_for_member(&2).is_empty());
+		assert_eq!(IbpService::services_for_member(&3).len(), 1);
+
+		assert_ok!(IbpService::set_member_service_override(RuntimeOrigin::signed(1), 3, service_id.clone()));
+		assert!(ServiceOverrides::<Test>::contains_key((3, service_id.clone())));
+		assert!(IbpService::services_for_member(&3).is_empty());
+		assert!(IbpService::members_for_service(&service_id).is_empty());
+	});
+}
+
+fn reason(value: &str) -> OverrideReason {
+	OverrideReason::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn override_requests_need_curator_approval() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		assert_ok!(IbpService::request_override(RuntimeOrigin::signed(2), service_id.clone(), reason("maintenance"), 10));
+		System::assert_last_event(Event::OverrideRequested(2, service_id.clone(), 10).into());
+		// the member still provides the service until the request is approved
+		assert!(ServiceAssignments::<Test>::contains_key(2, &service_id));
+
+		assert_noop!(
+			IbpService::approve_override(RuntimeOrigin::signed(2), 2, service_id.clone()),
+			Error::<Test>::NotACurator
+		);
+		assert_ok!(IbpService::approve_override(RuntimeOrigin::signed(1), 2, service_id.clone()));
+		System::assert_has_event(Event::OverrideApproved(2, service_id.clone(), 10).into());
+		System::assert_last_event(Event::ServiceUnassigned(2, service_id.clone()).into());
+		assert!(ServiceOverrides::<Test>::contains_key((2, service_id.clone())));
+		assert!(OverrideRequests::<Test>::get((2, service_id.clone())).is_none());
+	});
+}
+
+#[test]
+fn override_requests_are_validated() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(5);
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		assert_noop!(
+			IbpService::request_override(RuntimeOrigin::signed(4), service_id.clone(), reason("maintenance"), 10),
+			pallet_ibp_member::Error::<Test>::MemberNotFound
+		);
+		assert_noop!(
+			IbpService::request_override(RuntimeOrigin::signed(2), id("kusama-rpc"), reason("maintenance"), 10),
+			Error::<Test>::ServiceNotFound
+		);
+		// a member can only be exempted from a service it must provide
+		assert_ok!(IbpService::register_service(
+			RuntimeOrigin::signed(1),
+			id("polkadot-boot"),
+			id("polkadot"),
+			ServiceType::BOOT,
+			MembershipLevel::Two,
+			ServiceStatus::Active,
+		));
+		assert_noop!(
+			IbpService::request_override(RuntimeOrigin::signed(2), id("polkadot-boot"), reason("maintenance"), 10),
+			Error::<Test>::ServiceNotAssigned
+		);
+		assert_noop!(
+			IbpService::request_override(RuntimeOrigin::signed(2), service_id.clone(), reason("maintenance"), 5),
+			Error::<Test>::OverrideExpiryInPast
+		);
+		assert_ok!(IbpService::request_override(RuntimeOrigin::signed(2), service_id.clone(), reason("maintenance"), 10));
+		assert_noop!(
+			IbpService::request_override(RuntimeOrigin::signed(2), service_id.clone(), reason("again"), 20),
+			Error::<Test>::OverrideRequestExists
+		);
+
+		// the requested expiry passed before a curator got to it
+		System::set_block_number(10);
+		assert_noop!(
+			IbpService::approve_override(RuntimeOrigin::signed(1), 2, service_id.clone()),
+			Error::<Test>::OverrideExpiryInPast
+		);
+	});
+}
+
+#[test]
+fn rejected_override_requests_are_dropped() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		assert_noop!(
+			IbpService::reject_override(RuntimeOrigin::signed(1), 2, service_id.clone()),
+			Error::<Test>::OverrideRequestNotFound
+		);
+		assert_ok!(IbpService::request_override(RuntimeOrigin::signed(2), service_id.clone(), reason("maintenance"), 10));
+		assert_ok!(IbpService::reject_override(RuntimeOrigin::signed(1), 2, service_id.clone()));
+		System::assert_last_event(Event::OverrideRejected(2, service_id.clone()).into());
+		assert!(OverrideRequests::<Test>::get((2, service_id.clone())).is_none());
+		assert!(!ServiceOverrides::<Test>::contains_key((2, service_id.clone())));
+		assert!(ServiceAssignments::<Test>::contains_key(2, &service_id));
+	});
+}
+
+#[test]
+fn approved_overrides_expire_on_initialize() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		setup_member(3, MembershipLevel::One);
+		for member_id in [2, 3] {
+			assert_ok!(IbpService::request_override(
+				RuntimeOrigin::signed(member_id),
+				service_id.clone(),
+				reason("maintenance"),
+				10
+			));
+			assert_ok!(IbpService::approve_override(RuntimeOrigin::signed(1), member_id, service_id.clone()));
+		}
+		// a cleared override leaves the expiry queue
+		assert_ok!(IbpService::clear_service_override(RuntimeOrigin::signed(3), service_id.clone()));
+		assert_eq!(OverrideExpiries::<Test>::get(10).into_inner(), vec![(2, service_id.clone())]);
+
+		IbpService::on_initialize(9);
+		assert!(ServiceOverrides::<Test>::contains_key((2, service_id.clone())));
+
+		System::set_block_number(10);
+		IbpService::on_initialize(10);
+		System::assert_has_event(Event::OverrideExpired(2, service_id.clone()).into());
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+		assert!(!ServiceOverrides::<Test>::contains_key((2, service_id.clone())));
+		assert!(OverrideExpiries::<Test>::get(10).is_empty());
+	});
+}
+
+#[test]
+fn override_expiries_spread_to_the_next_free_block() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		// MaxOverrideExpiries is 4 in the mock
+		for member_id in 2..8 {
+			setup_member(member_id, MembershipLevel::One);
+			assert_ok!(IbpService::request_override(
+				RuntimeOrigin::signed(member_id),
+				service_id.clone(),
+				reason("maintenance"),
+				10
+			));
+		}
+		for member_id in 2..6 {
+			assert_ok!(IbpService::approve_override(RuntimeOrigin::signed(1), member_id, service_id.clone()));
+		}
+		assert_ok!(IbpService::approve_override(RuntimeOrigin::signed(1), 6, service_id.clone()));
+		System::assert_has_event(Event::OverrideApproved(6, service_id.clone(), 11).into());
+		assert_ok!(IbpService::approve_override(RuntimeOrigin::signed(1), 7, service_id.clone()));
+		assert_eq!(OverrideExpiries::<Test>::get(10).len(), 4);
+		assert_eq!(OverrideExpiries::<Test>::get(11).into_inner(), vec![(6, service_id.clone()), (7, service_id.clone())]);
+		assert_eq!(IbpService::override_expires_at((7, service_id.clone())), Some(11));
+
+		System::set_block_number(11);
+		IbpService::on_initialize(11);
+		System::assert_has_event(Event::OverrideExpired(6, service_id.clone()).into());
+		System::assert_has_event(Event::OverrideExpired(7, service_id.clone()).into());
+		assert!(ServiceAssignments::<Test>::contains_key(7, &service_id));
+	});
+}
+
+fn url(value: &str) -> EndpointUrl {
+	EndpointUrl::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn members_set_update_and_remove_endpoints() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		let endpoint =
+			Endpoint { url: url("wss://rpc.example.com/polkadot"), protocol: EndpointProtocol::Wss, region: None };
+		assert_ok!(IbpService::set_endpoint(
+			RuntimeOrigin::signed(2),
+			service_id.clone(),
+			endpoint.url.clone(),
+			endpoint.protocol.clone(),
+			None,
+		));
+		assert_eq!(MemberEndpoints::<Test>::get((2, service_id.clone())), Some(endpoint.clone()));
+		System::assert_last_event(Event::EndpointSet(2, service_id.clone(), endpoint.clone()).into());
+		assert_noop!(
+			IbpService::set_endpoint(RuntimeOrigin::signed(2), service_id.clone(), endpoint.url, endpoint.protocol, None),
+			Error::<Test>::EndpointAlreadyExists
+		);
+
+		let updated =
+			Endpoint { url: url("https://rpc.example.com/polkadot"), protocol: EndpointProtocol::Https, region: None };
+		assert_ok!(IbpService::update_endpoint(
+			RuntimeOrigin::signed(2),
+			service_id.clone(),
+			updated.url.clone(),
+			updated.protocol.clone(),
+			None,
+		));
+		assert_eq!(IbpService::endpoints_for_member(&2), vec![(service_id.clone(), updated)]);
+
+		assert_ok!(IbpService::remove_endpoint(RuntimeOrigin::signed(2), service_id.clone()));
+		assert!(!MemberEndpoints::<Test>::contains_key((2, service_id.clone())));
+		assert_noop!(
+			IbpService::remove_endpoint(RuntimeOrigin::signed(2), service_id),
+			Error::<Test>::EndpointNotFound
+		);
+	});
+}
+
+#[test]
+fn endpoints_must_match_the_service_type() {
+	new_test_ext_with_curator().execute_with(|| {
+		let rpc_service = register_service(MembershipLevel::One);
+		assert_ok!(IbpService::register_service(
+			RuntimeOrigin::signed(1),
+			id("polkadot-boot"),
+			id("polkadot"),
+			ServiceType::BOOT,
+			MembershipLevel::One,
+			ServiceStatus::Active,
+		));
+		setup_member(2, MembershipLevel::One);
+
+		assert_noop!(
+			IbpService::set_endpoint(
+				RuntimeOrigin::signed(2),
+				id("polkadot-boot"),
+				url("wss://boot.example.com"),
+				EndpointProtocol::Wss,
+				None,
+			),
+			Error::<Test>::UnsupportedProtocol
+		);
+		// the multiaddr must end with a peer id
+		assert_noop!(
+			IbpService::set_endpoint(
+				RuntimeOrigin::signed(2),
+				id("polkadot-boot"),
+				url("/dns/boot.example.com/tcp/30333/wss/p2p/"),
+				EndpointProtocol::P2p,
+				None,
+			),
+			Error::<Test>::InvalidEndpointUrl
+		);
+		assert_ok!(IbpService::set_endpoint(
+			RuntimeOrigin::signed(2),
+			id("polkadot-boot"),
+			url("/dns/boot.example.com/tcp/30333/wss/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp"),
+			EndpointProtocol::P2p,
+			None,
+		));
+		assert_noop!(
+			IbpService::set_endpoint(
+				RuntimeOrigin::signed(2),
+				rpc_service,
+				url("rpc.example.com"),
+				EndpointProtocol::Https,
+				None,
+			),
+			Error::<Test>::InvalidEndpointUrl
+		);
+	});
+}
+
+#[test]
+fn only_members_declare_endpoints() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::One);
+		assert_noop!(
+			IbpService::set_endpoint(
+				RuntimeOrigin::signed(2),
+				service_id,
+				url("wss://rpc.example.com"),
+				EndpointProtocol::Wss,
+				None,
+			),
+			pallet_ibp_member::Error::<Test>::MemberNotFound
+		);
+	});
+}
+
+fn domain(value: &str) -> DomainId {
+	DomainId::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+fn host(value: &str) -> Hostname {
+	Hostname::try_from(value.as_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn curators_register_transfer_and_retire_domains() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		setup_member(2, MembershipLevel::One);
+		assert_noop!(
+			IbpService::register_domain(RuntimeOrigin::signed(2), domain("dotters.network"), DomainOwner::Programme),
+			Error::<Test>::NotACurator
+		);
+		for invalid in ["dotters", "Dotters.network", "-dotters.network", "dotters..network"] {
+			assert_noop!(
+				IbpService::register_domain(RuntimeOrigin::signed(1), domain(invalid), DomainOwner::Programme),
+				Error::<Test>::InvalidDomain
+			);
+		}
+		assert_noop!(
+			IbpService::register_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Member(9)),
+			pallet_ibp_member::Error::<Test>::MemberNotFound
+		);
+
+		assert_ok!(IbpService::register_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Programme));
+		assert_noop!(
+			IbpService::register_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Programme),
+			Error::<Test>::DomainAlreadyExists
+		);
+		assert_ok!(IbpService::transfer_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Member(2)));
+		System::assert_last_event(Event::DomainTransferred(domain("dotters.network"), DomainOwner::Member(2)).into());
+		assert_eq!(Domains::<Test>::get(domain("dotters.network")).unwrap().owner, DomainOwner::Member(2));
+
+		assert_ok!(IbpService::retire_domain(RuntimeOrigin::signed(1), domain("dotters.network")));
+		assert_eq!(Domains::<Test>::get(domain("dotters.network")).unwrap().status, DomainStatus::Retired);
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			IbpService::transfer_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Programme),
+			Error::<Test>::DomainRetired
 		);
 	});
 }
+
+#[test]
+fn domain_owners_and_curators_manage_domain_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::One);
+		setup_member(2, MembershipLevel::One);
+		setup_member(3, MembershipLevel::One);
+		assert_ok!(IbpService::register_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Member(2)));
+
+		assert_noop!(
+			IbpService::set_domain_service(
+				RuntimeOrigin::signed(3),
+				domain("dotters.network"),
+				service_id.clone(),
+				host("polkadot.dotters.network"),
+			),
+			Error::<Test>::NotDomainOwnerOrCurator
+		);
+		for outside in ["polkadot.example.com", "polkadotdotters.network"] {
+			assert_noop!(
+				IbpService::set_domain_service(
+					RuntimeOrigin::signed(2),
+					domain("dotters.network"),
+					service_id.clone(),
+					host(outside),
+				),
+				Error::<Test>::InvalidHostname
+			);
+		}
+		assert_ok!(IbpService::set_domain_service(
+			RuntimeOrigin::signed(2),
+			domain("dotters.network"),
+			service_id.clone(),
+			host("polkadot.dotters.network"),
+		));
+		assert!(IbpService::is_domain_service(&domain("dotters.network"), &service_id));
+		assert_eq!(
+			IbpService::domain_for_host(&service_id, b"polkadot.dotters.network"),
+			Some(domain("dotters.network"))
+		);
+
+		assert_ok!(IbpService::remove_domain_service(RuntimeOrigin::signed(1), domain("dotters.network"), service_id.clone()));
+		assert!(!IbpService::is_domain_service(&domain("dotters.network"), &service_id));
+		assert_noop!(
+			IbpService::remove_domain_service(RuntimeOrigin::signed(2), domain("dotters.network"), service_id),
+			Error::<Test>::DomainServiceNotFound
+		);
+	});
+}
+
+#[test]
+fn retired_domains_reject_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::One);
+		assert_ok!(IbpService::register_domain(RuntimeOrigin::signed(1), domain("dotters.network"), DomainOwner::Programme));
+		assert_ok!(IbpService::set_domain_service(
+			RuntimeOrigin::signed(1),
+			domain("dotters.network"),
+			service_id.clone(),
+			host("polkadot.dotters.network"),
+		));
+		assert_ok!(IbpService::retire_domain(RuntimeOrigin::signed(1), domain("dotters.network")));
+		assert!(!IbpService::is_domain_service(&domain("dotters.network"), &service_id));
+		assert_eq!(IbpService::domain_for_host(&service_id, b"polkadot.dotters.network"), None);
+		assert_noop!(
+			IbpService::set_domain_service(
+				RuntimeOrigin::signed(1),
+				domain("dotters.network"),
+				service_id,
+				host("rpc.dotters.network"),
+			),
+			Error::<Test>::DomainRetired
+		);
+	});
+}
+
+#[test]
+fn services_need_an_active_chain() {
+	new_test_ext_with_curator().execute_with(|| {
+		assert_noop!(
+			IbpService::register_service(
+				RuntimeOrigin::signed(1),
+				id("moonbeam-rpc"),
+				id("moonbeam"),
+				ServiceType::RPC,
+				MembershipLevel::One,
+				ServiceStatus::Active,
+			),
+			Error::<Test>::ChainNotFound
+		);
+		assert_ok!(IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("polkadot"), 0));
+		assert_noop!(
+			IbpService::register_service(
+				RuntimeOrigin::signed(1),
+				id("polkadot-rpc"),
+				id("polkadot"),
+				ServiceType::RPC,
+				MembershipLevel::One,
+				ServiceStatus::Active,
+			),
+			Error::<Test>::ChainDeprecated
+		);
+	});
+}
+
+#[test]
+fn parachains_need_an_active_relay_chain() {
+	new_test_ext_with_curator().execute_with(|| {
+		let parachain = |relay_chain: &str| ChainRelation::Parachain { relay_chain: id(relay_chain), para_id: 1000 };
+		assert_noop!(register_chain("asset-hub-kusama", parachain("kusama")), Error::<Test>::InvalidRelayChain);
+		assert_ok!(register_chain("asset-hub-polkadot", parachain("polkadot")));
+		// a parachain is not a relay chain
+		assert_noop!(register_chain("other", parachain("asset-hub-polkadot")), Error::<Test>::InvalidRelayChain);
+		assert_noop!(register_chain("polkadot", ChainRelation::Relay), Error::<Test>::ChainAlreadyExists);
+		assert_noop!(
+			IbpService::register_chain(
+				RuntimeOrigin::signed(2),
+				id("kusama"),
+				[2u8; 32],
+				ChainRelation::Relay,
+				2,
+				TokenSymbol::try_from(b"KSM".to_vec()).unwrap(),
+			),
+			Error::<Test>::NotACurator
+		);
+	});
+}
+
+#[test]
+fn relay_chains_with_parachains_keep_their_relation() {
+	new_test_ext_with_curator().execute_with(|| {
+		let parachain = |relay_chain: &str| ChainRelation::Parachain { relay_chain: id(relay_chain), para_id: 1000 };
+		let update_chain = |chain_id: &str, relation: ChainRelation| {
+			IbpService::update_chain(
+				RuntimeOrigin::signed(1),
+				id(chain_id),
+				[1u8; 32],
+				relation,
+				0,
+				TokenSymbol::try_from(b"DOT".to_vec()).unwrap(),
+			)
+		};
+		assert_ok!(register_chain("kusama", ChainRelation::Relay));
+		assert_ok!(register_chain("asset-hub", parachain("polkadot")));
+		assert_noop!(update_chain("polkadot", ChainRelation::Solo), Error::<Test>::ChainHasParachains);
+		assert_noop!(update_chain("kusama", parachain("kusama")), Error::<Test>::InvalidRelayChain);
+		// the relay chain cannot be deprecated under an active parachain
+		assert_noop!(
+			IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("polkadot"), 0),
+			Error::<Test>::ChainHasParachains
+		);
+
+		// moving the parachain frees the old relay chain
+		assert_ok!(update_chain("asset-hub", parachain("kusama")));
+		assert_ok!(update_chain("polkadot", ChainRelation::Solo));
+		assert_ok!(update_chain("polkadot", ChainRelation::Relay));
+
+		// a deprecated parachain still pins the relation, but not the deprecation
+		assert_ok!(IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("asset-hub"), 0));
+		assert_noop!(update_chain("kusama", ChainRelation::Solo), Error::<Test>::ChainHasParachains);
+		assert_ok!(IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("kusama"), 0));
+	});
+}
+
+#[test]
+fn deprecating_a_chain_deletes_its_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::One);
+		assert_ok!(register_chain("kusama", ChainRelation::Relay));
+		assert_ok!(IbpService::register_service(
+			RuntimeOrigin::signed(1),
+			id("kusama-rpc"),
+			id("kusama"),
+			ServiceType::RPC,
+			MembershipLevel::One,
+			ServiceStatus::Active,
+		));
+
+		// only the services of the chain count
+		assert_noop!(
+			IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("polkadot"), 0),
+			Error::<Test>::BadServiceCount
+		);
+		assert_ok!(IbpService::deprecate_chain(RuntimeOrigin::signed(1), id("polkadot"), 1));
+		assert_eq!(Chains::<Test>::get(id("polkadot")).unwrap().status, ChainStatus::Deprecated);
+		assert_eq!(Services::<Test>::get(&service_id).status, ServiceStatus::Deleted);
+		assert_eq!(Services::<Test>::get(id("kusama-rpc")).status, ServiceStatus::Active);
+		System::assert_has_event(
+			Event::ServiceStatusUpdated(
+				service_id.clone(),
+				ServiceStatus::Active,
+				ServiceStatus::Deleted,
+				status_reason("chain-deprecated"),
+			)
+			.into(),
+		);
+		System::assert_last_event(Event::ChainDeprecated(id("polkadot")).into());
+		assert_noop!(
+			IbpService::update_chain(
+				RuntimeOrigin::signed(1),
+				id("polkadot"),
+				[1u8; 32],
+				ChainRelation::Relay,
+				0,
+				TokenSymbol::try_from(b"DOT".to_vec()).unwrap(),
+			),
+			Error::<Test>::ChainDeprecated
+		);
+		// services of a deprecated chain stay deleted
+		assert_noop!(
+			IbpService::undelete_service(RuntimeOrigin::signed(1), service_id, status_reason("restore")),
+			Error::<Test>::ChainDeprecated
+		);
+	});
+}
+
+#[test]
+fn assignments_follow_member_level_and_status() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		let service_id = register_service(MembershipLevel::Two);
+		setup_member(2, MembershipLevel::One);
+		assert!(!ServiceAssignments::<Test>::contains_key(2, &service_id));
+
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(1), 2, MembershipLevel::Two));
+		assert!(ServiceAssignments::<Test>::contains_key(2, &service_id));
+		assert_eq!(IbpService::assigned_services(&2), vec![service_id.clone()]);
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+
+		assert_ok!(IbpMember::delete_member(RuntimeOrigin::signed(1), 2, status_reason("left")));
+		assert!(IbpService::assigned_services(&2).is_empty());
+		System::assert_last_event(Event::ServiceUnassigned(2, service_id.clone()).into());
+	});
+}
+
+#[test]
+fn assignments_follow_service_level_status_and_overrides() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		setup_member(2, MembershipLevel::Two);
+		let service_id = register_service(MembershipLevel::Two);
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+
+		assert_ok!(IbpService::update_service_level(RuntimeOrigin::signed(1), service_id.clone(), MembershipLevel::Three));
+		assert!(!ServiceAssignments::<Test>::contains_key(2, &service_id));
+		assert_ok!(IbpService::update_service_level(RuntimeOrigin::signed(1), service_id.clone(), MembershipLevel::One));
+		assert!(ServiceAssignments::<Test>::contains_key(2, &service_id));
+
+		assert_ok!(IbpService::lock_service(RuntimeOrigin::signed(1), service_id.clone(), status_reason("maintenance")));
+		System::assert_last_event(Event::ServiceUnassigned(2, service_id.clone()).into());
+		assert_ok!(IbpService::unlock_service(RuntimeOrigin::signed(1), service_id.clone(), status_reason("maintenance-done")));
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+
+		assert_ok!(IbpService::set_member_service_override(RuntimeOrigin::signed(1), 2, service_id.clone()));
+		System::assert_last_event(Event::ServiceUnassigned(2, service_id.clone()).into());
+		assert_ok!(IbpService::clear_member_service_override(RuntimeOrigin::signed(1), 2, service_id.clone()));
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+	});
+}
+
+#[test]
+fn chilled_assignments_wait_for_a_curator() {
+	new_test_ext_with_curator().execute_with(|| {
+		System::set_block_number(1);
+		setup_member(2, MembershipLevel::Two);
+		let service_id = register_service(MembershipLevel::Two);
+		assert_noop!(
+			IbpService::chill(&3, &service_id, status_reason("ServiceDown")),
+			Error::<Test>::ServiceNotAssigned
+		);
+
+		assert_ok!(IbpService::chill(&2, &service_id, status_reason("ServiceDown")));
+		assert!(!ServiceAssignments::<Test>::contains_key(2, &service_id));
+		assert_eq!(ChilledAssignments::<Test>::get(2, &service_id), Some(status_reason("ServiceDown")));
+		System::assert_has_event(Event::AssignmentChilled(2, service_id.clone(), status_reason("ServiceDown")).into());
+		// the chill outlasts member changes
+		assert_ok!(IbpMember::update_member_level(RuntimeOrigin::signed(1), 2, MembershipLevel::Three));
+		assert!(IbpService::assigned_services(&2).is_empty());
+
+		assert_noop!(
+			IbpService::unchill_assignment(RuntimeOrigin::signed(2), 2, service_id.clone()),
+			Error::<Test>::NotACurator
+		);
+		assert_ok!(IbpService::unchill_assignment(RuntimeOrigin::signed(1), 2, service_id.clone()));
+		System::assert_last_event(Event::ServiceAssigned(2, service_id.clone()).into());
+		assert_noop!(
+			IbpService::unchill_assignment(RuntimeOrigin::signed(1), 2, service_id),
+			Error::<Test>::AssignmentNotChilled
+		);
+	});
+}
+
+#[test]
+fn migration_builds_service_assignments() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::Two);
+		setup_member(2, MembershipLevel::One);
+		setup_member(3, MembershipLevel::Three);
+		// storage as it was before assignments were materialized
+		let _ = ServiceAssignments::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(0).put::<IbpService>();
+
+		migrations::v1::BuildServiceAssignments::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpService::on_chain_storage_version(), 1);
+		assert!(!ServiceAssignments::<Test>::contains_key(2, &service_id));
+		assert!(ServiceAssignments::<Test>::contains_key(3, &service_id));
+		assert_eq!(ServiceAssignments::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn migration_indexes_chain_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		let service_id = register_service(MembershipLevel::One);
+		// storage as it was before services were counted and indexed by chain
+		ServiceCount::<Test>::kill();
+		let _ = ChainServices::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(1).put::<IbpService>();
+
+		migrations::v2::IndexChainServices::<Test>::on_runtime_upgrade();
+
+		assert_eq!(IbpService::on_chain_storage_version(), 2);
+		assert_eq!(ServiceCount::<Test>::get(), 1);
+		assert!(ChainServices::<Test>::contains_key(id("polkadot"), &service_id));
+	});
+}
+
+#[test]
+fn register_service_is_bounded_by_max_services() {
+	new_test_ext_with_curator().execute_with(|| {
+		// MaxServices is 16 in the mock
+		ServiceCount::<Test>::put(15);
+		register_service(MembershipLevel::One);
+		assert_eq!(ServiceCount::<Test>::get(), 16);
+		assert_noop!(
+			IbpService::register_service(
+				RuntimeOrigin::signed(1),
+				id("polkadot-boot"),
+				id("polkadot"),
+				ServiceType::BOOT,
+				MembershipLevel::One,
+				ServiceStatus::Active,
+			),
+			Error::<Test>::TooManyServices
+		);
+	});
+}
+
+#[test]
+fn assignment_refresh_is_charged_at_the_bounds() {
+	new_test_ext().execute_with(|| {
+		let refresh_member = <() as WeightInfo>::refresh_member_assignments(16);
+		let level_change = pallet_ibp_member::Call::<Test>::update_member_level { account_id: 2, level: MembershipLevel::One };
+		assert_eq!(
+			level_change.get_dispatch_info().weight,
+			<() as pallet_ibp_member::WeightInfo>::update_member_level().saturating_add(refresh_member)
+		);
+		let refresh_service = <() as WeightInfo>::refresh_service_assignments(16);
+		let lock = crate::Call::<Test>::lock_service { service_id: id("polkadot-rpc"), reason: status_reason("maintenance") };
+		assert_eq!(
+			lock.get_dispatch_info().weight,
+			<() as WeightInfo>::lock_service().saturating_add(refresh_service)
+		);
+		let deprecate = crate::Call::<Test>::deprecate_chain { chain_id: id("polkadot"), service_count: 3 };
+		assert_eq!(
+			deprecate.get_dispatch_info().weight,
+			<() as WeightInfo>::deprecate_chain(3).saturating_add(refresh_service.saturating_mul(3))
+		);
+	});
+}
+
+const SERVICE_STATUSES: [ServiceStatus; 5] =
+	[ServiceStatus::None, ServiceStatus::Locked, ServiceStatus::Active, ServiceStatus::Chilled, ServiceStatus::Deleted];
+
+const SERVICE_ACTIONS: [ServiceAction; 4] =
+	[ServiceAction::Lock, ServiceAction::Unlock, ServiceAction::Delete, ServiceAction::Undelete];
+
+/// The outcome of every action from every status, spelled out apart from `SERVICE_TRANSITIONS`
+fn expected_transition(from: ServiceStatus, action: ServiceAction) -> Option<ServiceStatus> {
+	match (from, action) {
+		(ServiceStatus::None | ServiceStatus::Active | ServiceStatus::Chilled, ServiceAction::Lock) =>
+			Some(ServiceStatus::Locked),
+		(ServiceStatus::Locked, ServiceAction::Unlock) => Some(ServiceStatus::Active),
+		(ServiceStatus::Deleted, ServiceAction::Delete) => None,
+		(_, ServiceAction::Delete) => Some(ServiceStatus::Deleted),
+		(ServiceStatus::Deleted, ServiceAction::Undelete) => Some(ServiceStatus::Locked),
+		_ => None,
+	}
+}
+
+/// Apply `action` to `service_id` through its call, as curator 1
+fn apply_action(service_id: ServiceId, action: ServiceAction) -> DispatchResult {
+	let origin = RuntimeOrigin::signed(1);
+	let reason = status_reason("matrix");
+	match action {
+		ServiceAction::Lock => IbpService::lock_service(origin, service_id, reason),
+		ServiceAction::Unlock => IbpService::unlock_service(origin, service_id, reason),
+		ServiceAction::Delete => IbpService::delete_service(origin, service_id, reason),
+		ServiceAction::Undelete => IbpService::undelete_service(origin, service_id, reason),
+	}
+}
+
+#[test]
+fn service_status_transition_matrix() {
+	for from in SERVICE_STATUSES {
+		for action in SERVICE_ACTIONS {
+			assert_eq!(from.transition(action), expected_transition(from, action), "{:?} {:?}", from, action);
+			new_test_ext_with_curator().execute_with(|| {
+				System::set_block_number(1);
+				let service_id = register_service(MembershipLevel::One);
+				Services::<Test>::mutate(&service_id, |service| service.status = from);
+
+				let result = apply_action(service_id.clone(), action);
+				match expected_transition(from, action) {
+					Some(to) => {
+						assert_ok!(result);
+						assert_eq!(Services::<Test>::get(&service_id).status, to);
+						assert_eq!(ServiceStatusReasons::<Test>::get(&service_id), Some(status_reason("matrix")));
+						System::assert_last_event(
+							Event::ServiceStatusUpdated(service_id.clone(), from, to, status_reason("matrix")).into(),
+						);
+						match action {
+							ServiceAction::Lock => System::assert_has_event(Event::ServiceLocked(service_id).into()),
+							ServiceAction::Unlock => System::assert_has_event(Event::ServiceUnlocked(service_id).into()),
+							ServiceAction::Delete => System::assert_has_event(Event::ServiceDeleted(service_id).into()),
+							ServiceAction::Undelete => {},
+						}
+					},
+					None => {
+						assert_eq!(result, Err(Error::<Test>::InvalidStatusTransition.into()), "{:?} {:?}", from, action);
+						assert_eq!(Services::<Test>::get(&service_id).status, from);
+						assert_eq!(ServiceStatusReasons::<Test>::get(&service_id), None);
+					},
+				}
+			});
+		}
+	}
+}
@@ -1,29 +1,28 @@
 
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_ibp_service
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! THESE ARE HAND-WRITTEN ESTIMATES, NOT BENCHMARK RESULTS.
+//! Storage access counts follow the dispatchables; execution times are conservative placeholders.
+//! Regenerate on reference hardware before relying on them, with `scripts/benchmark-weights.sh`,
+//! which builds with `--features runtime-benchmarks` and runs for each IBP pallet:
 
-// Executed Command:
-// ../../target/release/node-template
+// Command:
+// ./target/release/ibp-node
 // benchmark
 // pallet
 // --chain
 // dev
 // --pallet
-// pallet_template
+// pallet_ibp_service
 // --extrinsic
 // *
 // --steps=50
 // --repeat=20
 // --wasm-execution=compiled
 // --output
-// pallets/template/src/weights.rs
+// pallets/ibp-service/src/weights.rs
 // --template
-// ../../.maintain/frame-weight-template.hbs
+// ./.maintain/frame-weight-template.hbs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -32,67 +31,558 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_ibp_service.
 pub trait WeightInfo {
-	fn do_something() -> Weight;
-	fn cause_error() -> Weight;
+	fn register_service() -> Weight;
+	fn lock_service() -> Weight;
+	fn unlock_service() -> Weight;
+	fn delete_service() -> Weight;
+	fn undelete_service() -> Weight;
+	fn update_service_level() -> Weight;
+	fn set_member_service_override() -> Weight;
+	fn clear_service_override() -> Weight;
+	fn clear_member_service_override() -> Weight;
+	fn set_endpoint() -> Weight;
+	fn update_endpoint() -> Weight;
+	fn remove_endpoint() -> Weight;
+	fn register_domain() -> Weight;
+	fn transfer_domain() -> Weight;
+	fn retire_domain() -> Weight;
+	fn set_domain_service() -> Weight;
+	fn remove_domain_service() -> Weight;
+	fn register_chain() -> Weight;
+	fn update_chain() -> Weight;
+	fn deprecate_chain(s: u32, ) -> Weight;
+	fn request_override() -> Weight;
+	fn approve_override() -> Weight;
+	fn reject_override() -> Weight;
+	fn unchill_assignment() -> Weight;
+	fn expire_overrides(n: u32, ) -> Weight;
+	fn refresh_member_assignments(s: u32, ) -> Weight;
+	fn refresh_service_assignments(m: u32, ) -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_ibp_service using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	// fn register_member() -> Weight {
-	// 	// Proof Size summary in bytes:
-	// 	//  Measured:  `0`
-	// 	//  Estimated: `0`
-	// 	// Minimum execution time: 8_000_000 picoseconds.
-	// 	Weight::from_parts(10_000, 0)
-	// 		.saturating_add(T::DbWeight::get().writes(1_u64))
-	// }
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:1 w:0)
+	/// Storage: IbpService ServiceCount (r:1 w:1)
+	/// Storage: IbpService ChainServices (r:0 w:1)
+	fn register_service() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn lock_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn unlock_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn delete_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService Chains (r:1 w:0)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn undelete_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	fn update_service_level() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	fn set_member_service_override() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:1 w:1)
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	fn clear_service_override() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:1 w:1)
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	fn clear_member_service_override() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	fn set_endpoint() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	fn update_endpoint() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	fn remove_endpoint() -> Weight {
+		Weight::from_parts(12_000_000, 3600)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	fn register_domain() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	fn transfer_domain() -> Weight {
+		Weight::from_parts(16_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	fn retire_domain() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:0 w:1)
+	fn set_domain_service() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:1)
+	fn remove_domain_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:2 w:1), the relay chain of a parachain
+	/// Storage: IbpService ParachainCount (r:1 w:1)
+	/// Storage: IbpService ActiveParachainCount (r:1 w:1)
+	fn register_chain() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:2 w:1), the relay chain of a parachain
+	/// Storage: IbpService ParachainCount (r:3 w:2), the chain and the old and new relay chains
+	/// Storage: IbpService ActiveParachainCount (r:2 w:2)
+	fn update_chain() -> Weight {
+		Weight::from_parts(20_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:1 w:1)
+	/// Storage: IbpService ActiveParachainCount (r:2 w:1), the chain and its relay chain
+	/// Storage: IbpService ChainServices (r:s w:0)
+	/// Storage: IbpService Services (r:s w:s)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:s)
+	/// The range of component `s` is `[0, 100]`.
+	fn deprecate_chain(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(s.into()))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	fn request_override() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	/// Storage: IbpService ServiceOverrides (r:2 w:1)
+	/// Storage: IbpService OverrideExpiries (r:17 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:0 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:1)
+	fn approve_override() -> Weight {
+		Weight::from_parts(40_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	fn reject_override() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ChilledAssignments (r:2 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:1)
+	fn unchill_assignment() -> Weight {
+		Weight::from_parts(22_000_000, 3600)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	/// Storage: IbpService ServiceOverrides (r:n w:n)
+	/// Storage: IbpService OverrideExpiresAt (r:0 w:n)
+	/// Storage: IbpMember Members (r:n w:0)
+	/// Storage: IbpService Services (r:n w:0)
+	/// Storage: IbpService ServiceAssignments (r:n w:n)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_overrides(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(n.into()))
+	}
+	/// Storage: IbpService Services (r:s w:0)
+	/// Storage: IbpService ServiceOverrides (r:s w:0)
+	/// Storage: IbpService ChilledAssignments (r:s w:0)
+	/// Storage: IbpService ServiceAssignments (r:s w:s)
+	/// The range of component `s` is `[0, 256]`.
+	fn refresh_member_assignments(s: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(s.into()))
+	}
+	/// Storage: IbpMember Members (r:m w:0)
+	/// Storage: IbpService ServiceOverrides (r:m w:0)
+	/// Storage: IbpService ChilledAssignments (r:m w:0)
+	/// Storage: IbpService ServiceAssignments (r:m w:m)
+	/// The range of component `m` is `[0, 256]`.
+	fn refresh_service_assignments(m: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(m.into()))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:1 w:0)
+	/// Storage: IbpService ServiceCount (r:1 w:1)
+	/// Storage: IbpService ChainServices (r:0 w:1)
+	fn register_service() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn lock_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn unlock_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn delete_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	/// Storage: IbpService Chains (r:1 w:0)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:1)
+	fn undelete_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:1)
+	fn update_service_level() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	fn set_member_service_override() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:1 w:1)
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	fn clear_service_override() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:1 w:1)
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	fn clear_member_service_override() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	fn set_endpoint() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	fn update_endpoint() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
-	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+	/// Storage: IbpService MemberEndpoints (r:1 w:1)
+	fn remove_endpoint() -> Weight {
+		Weight::from_parts(12_000_000, 3600)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	fn register_domain() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	fn transfer_domain() -> Weight {
+		Weight::from_parts(16_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Domains (r:1 w:1)
+	fn retire_domain() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:0 w:1)
+	fn set_domain_service() -> Weight {
+		Weight::from_parts(17_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpService Domains (r:1 w:0)
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService DomainServices (r:1 w:1)
+	fn remove_domain_service() -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:2 w:1), the relay chain of a parachain
+	/// Storage: IbpService ParachainCount (r:1 w:1)
+	/// Storage: IbpService ActiveParachainCount (r:1 w:1)
+	fn register_chain() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:2 w:1), the relay chain of a parachain
+	/// Storage: IbpService ParachainCount (r:3 w:2), the chain and the old and new relay chains
+	/// Storage: IbpService ActiveParachainCount (r:2 w:2)
+	fn update_chain() -> Weight {
+		Weight::from_parts(20_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService Chains (r:1 w:1)
+	/// Storage: IbpService ActiveParachainCount (r:2 w:1), the chain and its relay chain
+	/// Storage: IbpService ChainServices (r:s w:0)
+	/// Storage: IbpService Services (r:s w:s)
+	/// Storage: IbpService ServiceStatusReasons (r:0 w:s)
+	/// The range of component `s` is `[0, 100]`.
+	fn deprecate_chain(s: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 3600)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(s.into()))
+	}
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	fn request_override() -> Weight {
+		Weight::from_parts(18_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	/// Storage: IbpService ServiceOverrides (r:2 w:1)
+	/// Storage: IbpService OverrideExpiries (r:17 w:1)
+	/// Storage: IbpService OverrideExpiresAt (r:0 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:1)
+	fn approve_override() -> Weight {
+		Weight::from_parts(40_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(23_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService OverrideRequests (r:1 w:1)
+	fn reject_override() -> Weight {
+		Weight::from_parts(14_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: IbpMember Curators (r:1 w:0)
+	/// Storage: IbpService ChilledAssignments (r:2 w:1)
+	/// Storage: IbpMember Members (r:1 w:0)
+	/// Storage: IbpService Services (r:1 w:0)
+	/// Storage: IbpService ServiceOverrides (r:1 w:0)
+	/// Storage: IbpService ServiceAssignments (r:1 w:1)
+	fn unchill_assignment() -> Weight {
+		Weight::from_parts(22_000_000, 3600)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: IbpService OverrideExpiries (r:1 w:1)
+	/// Storage: IbpService ServiceOverrides (r:n w:n)
+	/// Storage: IbpService OverrideExpiresAt (r:0 w:n)
+	/// Storage: IbpMember Members (r:n w:0)
+	/// Storage: IbpService Services (r:n w:0)
+	/// Storage: IbpService ServiceAssignments (r:n w:n)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_overrides(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1600)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(n.into()))
+	}
+	/// Storage: IbpService Services (r:s w:0)
+	/// Storage: IbpService ServiceOverrides (r:s w:0)
+	/// Storage: IbpService ChilledAssignments (r:s w:0)
+	/// Storage: IbpService ServiceAssignments (r:s w:s)
+	/// The range of component `s` is `[0, 256]`.
+	fn refresh_member_assignments(s: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(s.into()))
+	}
+	/// Storage: IbpMember Members (r:m w:0)
+	/// Storage: IbpService ServiceOverrides (r:m w:0)
+	/// Storage: IbpService ChilledAssignments (r:m w:0)
+	/// Storage: IbpService ServiceAssignments (r:m w:m)
+	/// The range of component `m` is `[0, 256]`.
+	fn refresh_service_assignments(m: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 1600)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 2600).saturating_mul(m.into()))
+	}
 }
@@ -0,0 +1,91 @@
+//! Test fixtures shared by the IBP pallet tests.
+//!
+//! The fixtures are generic over the mock runtime, they call the member and service pallets
+//! the way curators and members would.
+
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use pallet_ibp_member::{MemberName, MembershipLevel, Pallet as IbpMember};
+use pallet_ibp_service::{
+    ChainRelation, DomainId, DomainOwner, Pallet as IbpService, ServiceId, ServiceStatus, ServiceType,
+};
+
+pub fn bounded<B: TryFrom<Vec<u8>>>(value: &str) -> B {
+    B::try_from(value.as_bytes().to_vec()).ok().unwrap()
+}
+
+/// The service of `setup_service`
+pub fn service_id() -> ServiceId {
+    bounded("polkadot-rpc")
+}
+
+/// The domain of `setup_service`
+pub fn domain_id() -> DomainId {
+    bounded("example.com")
+}
+
+/// Register a member and have the curator activate it
+pub fn add_member<T: pallet_ibp_member::Config>(curator: &T::AccountId, account_id: &T::AccountId, name: &str) {
+    assert_ok!(IbpMember::<T>::register_member(
+        RawOrigin::Signed(account_id.clone()).into(),
+        bounded::<MemberName>(name),
+    ));
+    assert_ok!(IbpMember::<T>::unlock_member(
+        RawOrigin::Signed(curator.clone()).into(),
+        account_id.clone(),
+        Default::default(),
+    ));
+}
+
+/// Register the `polkadot` relay chain
+pub fn register_polkadot<T: pallet_ibp_service::Config>(curator: &T::AccountId) {
+    assert_ok!(IbpService::<T>::register_chain(
+        RawOrigin::Signed(curator.clone()).into(),
+        bounded("polkadot"),
+        [1u8; 32],
+        ChainRelation::Relay,
+        0,
+        bounded("DOT"),
+    ));
+}
+
+/// Register an active RPC service of the `polkadot` chain
+pub fn register_service<T: pallet_ibp_service::Config>(
+    curator: &T::AccountId,
+    service_id: ServiceId,
+    level: MembershipLevel,
+) {
+    assert_ok!(IbpService::<T>::register_service(
+        RawOrigin::Signed(curator.clone()).into(),
+        service_id,
+        bounded("polkadot"),
+        ServiceType::RPC,
+        level,
+        ServiceStatus::Active,
+    ));
+}
+
+/// A curator, an active member, a monitor, and one active service served under a domain
+pub fn setup_service<T: pallet_ibp_service::Config>(
+    curator: &T::AccountId,
+    member: &T::AccountId,
+    monitor: &T::AccountId,
+) {
+    assert_ok!(IbpMember::<T>::assign_curator(RawOrigin::Root.into(), curator.clone()));
+    add_member::<T>(curator, member, "member");
+    add_member::<T>(curator, monitor, "monitor");
+    assert_ok!(IbpMember::<T>::assign_monitor(RawOrigin::Signed(curator.clone()).into(), monitor.clone()));
+    register_polkadot::<T>(curator);
+    register_service::<T>(curator, service_id(), MembershipLevel::None);
+    assert_ok!(IbpService::<T>::register_domain(
+        RawOrigin::Signed(curator.clone()).into(),
+        domain_id(),
+        DomainOwner::Programme,
+    ));
+    assert_ok!(IbpService::<T>::set_domain_service(
+        RawOrigin::Signed(curator.clone()).into(),
+        domain_id(),
+        service_id(),
+        bounded("rpc.example.com"),
+    ));
+}
@@ -10,7 +10,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify},
+	traits::{
+		AccountIdConversion, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount, NumberFor, One,
+		OpaqueKeys, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -23,8 +26,8 @@ use frame_support::genesis_builder_helper::{build_config, create_default_config}
 pub use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
 	traits::{
-		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
-		StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Currency, KeyOwnerProofSystem, OnUnbalanced,
+		Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -32,7 +35,7 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -51,6 +54,14 @@ pub use pallet_ibp_member;
 pub use pallet_ibp_service;
 pub use pallet_ibp_check;
 pub use pallet_ibp_alert;
+pub use pallet_ibp_reward;
+use pallet_ibp_runtime_api::{
+	AlertFilter, AlertInfo, AlertStats, AlertType, AlertTypeInfo, CheckInfo, MemberInfo,
+	PendingAlertInfo, RollupInfo, ServiceInfo,
+};
+
+/// Session housekeeping and the session keys migration.
+mod session;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -107,10 +118,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 107,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -253,6 +264,26 @@ impl pallet_sudo::Config for Runtime {
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	/// Sessions start at block 0, the length is set in the chain spec (see `pallet_ibp_member::SessionLength`).
+	pub const SessionOffset: BlockNumber = 0;
+}
+
+/// Sessions follow the chain spec session length.
+type IbpSessions = pallet_session::PeriodicSessions<pallet_ibp_member::IbpSessionLength<Runtime>, SessionOffset>;
+
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = <Self as frame_system::Config>::AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = IbpSessions;
+	type NextSessionRotation = IbpSessions;
+	type SessionManager = session::IbpSessionManager;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -283,31 +314,180 @@ impl pallet_template::Config for Runtime {
 use codec::{ Decode, Encode, MaxEncodedLen };
 use scale_info::{self, TypeInfo};
 
+parameter_types! {
+  /// Audit log entries kept per member, older entries are dropped.
+  pub const MaxAuditEntries: u32 = 50;
+  /// Blocks a deleted member waits before withdrawing its bond.
+  pub const MemberUnbondingPeriod: BlockNumber = 7 * DAYS;
+  /// Registered members, bounds the refresh of a service's assignments.
+  pub const MaxMembers: u32 = 256;
+  /// Id of the IBP pot, funds member rewards.
+  pub const IbpPotId: PalletId = PalletId(*b"ibp/pot_");
+  /// Account of the IBP pot, receives slashed member bonds.
+  pub IbpPotAccount: AccountId = IbpPotId::get().into_account_truncating();
+}
+
+/// Deposit slashed member bonds in the IBP pot.
+pub struct SlashToPot;
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for SlashToPot {
+  fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+    Balances::resolve_creating(&IbpPotAccount::get(), amount);
+  }
+}
+
 /// Configure the IBP Member pallet in pallets/ibp-member.
 /// This is the configuration for the pallet-ibp-member.
 impl pallet_ibp_member::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = pallet_ibp_member::weights::SubstrateWeight<Runtime>;
+  type DefaultSessionLength = ConstU32<HOURS>;
+  type OnMemberChanged = IbpService;
+  type MaxAuditEntries = MaxAuditEntries;
+  type Currency = Balances;
+  type UnbondingPeriod = MemberUnbondingPeriod;
+  type Slashed = SlashToPot;
+  type MaxMembers = MaxMembers;
   // type MemberId: Parameter + Member + MaybeSerializeDeserialize + Default + MaxEncodedLen + TypeInfo;
 }
 
+parameter_types! {
+  /// Approved service overrides allowed to expire in the same block.
+  pub const MaxOverrideExpiries: u32 = 16;
+  /// Registered services, bounds the refresh of a member's assignments.
+  pub const MaxServices: u32 = 256;
+}
+
 /// Configure the IBP Service pallet in pallets/ibp-service.
 impl pallet_ibp_service::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = pallet_ibp_service::weights::SubstrateWeight<Runtime>;
+  type MaxOverrideExpiries = MaxOverrideExpiries;
+  type MaxServices = MaxServices;
 }
 
-/// Configure the IBP Service pallet in pallets/ibp-service.
+parameter_types! {
+  /// Health checks kept per (member, service) pair.
+  pub const MaxChecksPerPair: u32 = 100;
+  /// Health checks are kept for one week.
+  pub const CheckRetentionPeriod: BlockNumber = 7 * DAYS;
+  /// Monitors probe member endpoints every ten minutes.
+  pub const ProbeInterval: BlockNumber = 10 * MINUTES;
+  /// Health checks a monitor submits per probe round, in one transaction.
+  pub const MaxChecksPerBatch: u32 = 64;
+  /// Health checks are rolled up per day, for SLA reports over the last 90 days.
+  pub const CheckEpochLength: BlockNumber = DAYS;
+  pub const MaxRollupEpochs: u32 = 90;
+}
+
+/// Configure the IBP Check pallet in pallets/ibp-check.
 impl pallet_ibp_check::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = pallet_ibp_check::weights::SubstrateWeight<Runtime>;
+  type MaxChecksPerPair = MaxChecksPerPair;
+  type CheckRetentionPeriod = CheckRetentionPeriod;
+  type AuthorityId = pallet_ibp_check::offchain::crypto::MonitorAuthId;
+  type Endpoints = IbpService;
+  type ProbeInterval = ProbeInterval;
+  type MaxChecksPerBatch = MaxChecksPerBatch;
+  type EpochLength = CheckEpochLength;
+  type MaxRollupEpochs = MaxRollupEpochs;
+}
+
+/// Signed transactions from offchain workers, used by monitors to submit health checks.
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+  RuntimeCall: From<LocalCall>,
+{
+  fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+    call: RuntimeCall,
+    public: <Signature as Verify>::Signer,
+    account: AccountId,
+    nonce: Nonce,
+  ) -> Option<(RuntimeCall, <UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)> {
+    use sp_runtime::{traits::StaticLookup, SaturatedConversion};
+
+    let period = BlockHashCount::get()
+      .checked_next_power_of_two()
+      .map(|c| c / 2)
+      .unwrap_or(2) as u64;
+    let current_block = System::block_number()
+      .saturated_into::<u64>()
+      // The `System::block_number` is initialized with `n+1`,
+      // so the actual block number is `n`.
+      .saturating_sub(1);
+    let extra: SignedExtra = (
+      frame_system::CheckNonZeroSender::<Runtime>::new(),
+      frame_system::CheckSpecVersion::<Runtime>::new(),
+      frame_system::CheckTxVersion::<Runtime>::new(),
+      frame_system::CheckGenesis::<Runtime>::new(),
+      frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+      frame_system::CheckNonce::<Runtime>::from(nonce),
+      frame_system::CheckWeight::<Runtime>::new(),
+      pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+    );
+    let raw_payload = SignedPayload::new(call, extra).ok()?;
+    let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+    let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+    let (call, extra, _) = raw_payload.deconstruct();
+    Some((call, (address, signature, extra)))
+  }
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+  type Public = <Signature as Verify>::Signer;
+  type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+  RuntimeCall: From<C>,
+{
+  type OverarchingCall = RuntimeCall;
+  type Extrinsic = UncheckedExtrinsic;
+}
+
+parameter_types! {
+  /// Every monitor may report the same pending alert.
+  pub const MaxAlertReports: u32 = 16;
+  pub const MaxPendingAlertExpiries: u32 = 64;
+  /// Resolved alerts kept per member for response time statistics.
+  pub const MaxAlertHistory: u32 = 50;
+  pub const MaxDueEscalations: u32 = 64;
+  /// Alerts nobody resolves expire after a week, unless their type sets another expiry.
+  pub const DefaultAlertTtl: BlockNumber = 7 * DAYS;
 }
 
 /// Configure the IBP Alert pallet in pallets/ibp-alert.
 impl pallet_ibp_alert::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
-  // type WeightInfo = pallet_ibp_alert::weights::SubstrateWeight<Runtime>;
+  type WeightInfo = pallet_ibp_alert::weights::SubstrateWeight<Runtime>;
   type AlertKey = pallet_ibp_alert::AlertKey<Runtime>;
+  type MaxAlertReports = MaxAlertReports;
+  type MaxPendingExpiries = MaxPendingAlertExpiries;
+  type UnixTime = Timestamp;
+  type MaxAlertHistory = MaxAlertHistory;
+  type Members = IbpMember;
+  type Assignments = IbpService;
+  type MaxDueEscalations = MaxDueEscalations;
+  type DefaultAlertTtl = DefaultAlertTtl;
+}
+
+parameter_types! {
+  /// Member rewards are computed once a day.
+  pub const RewardEraLength: BlockNumber = DAYS;
+  /// Members settled per block once an era ends.
+  pub const MaxEraPayoutsPerBlock: u32 = 32;
+}
+
+/// Configure the IBP Reward pallet in pallets/ibp-reward.
+impl pallet_ibp_reward::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = pallet_ibp_reward::weights::SubstrateWeight<Runtime>;
+  type PotId = IbpPotId;
+  type EraLength = RewardEraLength;
+  type Uptime = IbpCheck;
+  type MaxEraPayoutsPerBlock = MaxEraPayoutsPerBlock;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -368,10 +548,18 @@ mod runtime {
   #[runtime::pallet_index(11)]
   pub type IbpAlert = pallet_ibp_alert;
 
+  // Session keys and session boundaries for IBP housekeeping.
+  #[runtime::pallet_index(12)]
+  pub type Session = pallet_session;
+
   // // Include Identity pallet
-  // #[runtime::pallet_index(12)]
+  // #[runtime::pallet_index(13)]
   // pub type Identity = pallet_identity;
 
+  // Include the custom logic from the IBP Reward pallet in the runtime.
+  #[runtime::pallet_index(14)]
+  pub type IbpReward = pallet_ibp_reward;
+
 
 }
 
@@ -397,7 +585,18 @@ pub type SignedExtra = (
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_ibp_member::migrations::v1::BuildMemberNames<Runtime>,
+	pallet_ibp_member::migrations::v2::SeedSessionLength<Runtime>,
+	session::SeedSessionKeys,
+	pallet_ibp_service::migrations::v1::BuildServiceAssignments<Runtime>,
+	pallet_ibp_member::migrations::v3::CountMembers<Runtime>,
+	pallet_ibp_service::migrations::v2::IndexChainServices<Runtime>,
+	pallet_ibp_member::migrations::v4::CountActiveMonitors<Runtime>,
+	pallet_ibp_alert::migrations::v1::AddAlertLifecycle<Runtime>,
+	pallet_ibp_alert::migrations::v2::RegisterAlertTypes<Runtime>,
+	pallet_ibp_alert::migrations::v3::AddAlertExpiry<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -423,6 +622,11 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_template, TemplateModule]
+		[pallet_ibp_member, IbpMember]
+		[pallet_ibp_service, IbpService]
+		[pallet_ibp_check, IbpCheck]
+		[pallet_ibp_alert, IbpAlert]
+		[pallet_ibp_reward, IbpReward]
 	);
 }
 
@@ -594,6 +798,79 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(6)]
+	impl pallet_ibp_runtime_api::IbpApi<Block, AccountId, BlockNumber> for Runtime {
+		fn member(id: AccountId) -> Option<MemberInfo<AccountId>> {
+			let member = IbpMember::members(&id);
+			member.id.is_some().then(|| member.into())
+		}
+
+		fn members() -> Vec<MemberInfo<AccountId>> {
+			pallet_ibp_member::Members::<Runtime>::iter_values().map(Into::into).collect()
+		}
+
+		fn member_audit_log(id: AccountId) -> Vec<pallet_ibp_member::AuditEntry<AccountId, BlockNumber>> {
+			IbpMember::audit_log(&id).into_inner()
+		}
+
+		fn service(id: pallet_ibp_service::ServiceId) -> Option<ServiceInfo> {
+			pallet_ibp_service::Services::<Runtime>::contains_key(&id)
+				.then(|| IbpService::services(&id).into())
+		}
+
+		fn services() -> Vec<ServiceInfo> {
+			pallet_ibp_service::Services::<Runtime>::iter_values().map(Into::into).collect()
+		}
+
+		fn services_for_member(id: AccountId) -> Vec<ServiceInfo> {
+			IbpService::services_for_member(&id).into_iter().map(Into::into).collect()
+		}
+
+		fn members_for_service(service_id: pallet_ibp_service::ServiceId) -> Vec<MemberInfo<AccountId>> {
+			IbpService::members_for_service(&service_id).into_iter().map(Into::into).collect()
+		}
+
+		fn active_alerts(filter: AlertFilter<AccountId>) -> Vec<AlertInfo<AccountId>> {
+			IbpAlert::active_alerts(&filter).into_iter().map(Into::into).collect()
+		}
+
+		fn pending_alerts(filter: AlertFilter<AccountId>) -> Vec<PendingAlertInfo<AccountId, BlockNumber>> {
+			let required = IbpAlert::required_reports(IbpAlert::quorum());
+			IbpAlert::pending_alerts(&filter)
+				.into_iter()
+				.map(|(key, pending)| PendingAlertInfo::new(key, pending, required))
+				.collect()
+		}
+
+		fn alert_stats(member: AccountId) -> AlertStats {
+			IbpAlert::alert_stats(&member)
+		}
+
+		fn alert_types() -> Vec<(AlertType, AlertTypeInfo<BlockNumber>)> {
+			pallet_ibp_alert::AlertTypes::<Runtime>::iter().collect()
+		}
+
+		fn recent_checks(
+			member: AccountId,
+			service: pallet_ibp_service::ServiceId,
+		) -> Vec<CheckInfo<AccountId, BlockNumber>> {
+			IbpCheck::recent_checks(&member, &service).into_iter().map(Into::into).collect()
+		}
+
+		fn uptime_rollups(
+			member: AccountId,
+			service: pallet_ibp_service::ServiceId,
+		) -> Vec<RollupInfo> {
+			IbpCheck::uptime_rollups(&member, &service).into_iter().map(Into::into).collect()
+		}
+	}
+
+	impl pallet_ibp_runtime_api::IbpRewardApi<Block, AccountId, Balance> for Runtime {
+		fn next_payout(member: AccountId) -> pallet_ibp_reward::PayoutPreview<Balance> {
+			IbpReward::next_payout(&member)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,96 @@
+//! Session glue: the IBP housekeeping run at session boundaries, and the migration that starts
+//! sessions on chains created before `pallet-session` was added.
+
+use super::*;
+use frame_support::{
+	storage_alias,
+	traits::{Get, OnRuntimeUpgrade},
+	Twox64Concat,
+};
+use pallet_session::SessionManager;
+use sp_core::crypto::ByteArray;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// The chain of session managers, IBP housekeeping runs at every session boundary.
+/// The validator set is kept as is: authorities come from the session keys.
+pub struct IbpSessionManager;
+
+impl SessionManager<AccountId> for IbpSessionManager {
+	fn new_session(_new_index: u32) -> Option<Vec<AccountId>> {
+		None
+	}
+
+	fn end_session(_end_index: u32) {}
+
+	fn start_session(_start_index: u32) {
+		IbpAlert::rebuild_alert_index();
+	}
+}
+
+#[storage_alias(pallet_name)]
+type Validators = StorageValue<Session, Vec<AccountId>, ValueQuery>;
+
+#[storage_alias(pallet_name)]
+type QueuedKeys = StorageValue<Session, Vec<(AccountId, opaque::SessionKeys)>, ValueQuery>;
+
+#[storage_alias(pallet_name)]
+type NextKeys = StorageMap<Session, Twox64Concat, AccountId, opaque::SessionKeys, OptionQuery>;
+
+#[storage_alias(pallet_name)]
+type KeyOwner = StorageMap<Session, Twox64Concat, (KeyTypeId, Vec<u8>), AccountId, OptionQuery>;
+
+/// Seed the session keys from the current Aura and Grandpa authorities.
+///
+/// Chains created before `pallet-session` was added have no session keys, so the first session
+/// rotation would hand Aura and Grandpa an empty authority set. The authorities are paired by
+/// position, as the chain spec lists them, each under the account of its Aura key. Nothing is
+/// written when validators are already set or the two authority sets differ in length.
+pub struct SeedSessionKeys;
+
+impl OnRuntimeUpgrade for SeedSessionKeys {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if !Validators::get().is_empty() {
+			return db_weight.reads(1);
+		}
+		let aura_authorities = Aura::authorities();
+		let grandpa_authorities = Grandpa::grandpa_authorities();
+		if aura_authorities.len() != grandpa_authorities.len() {
+			return db_weight.reads(3);
+		}
+		let mut queued = Vec::new();
+		for (aura, (grandpa, _)) in aura_authorities.into_iter().zip(grandpa_authorities) {
+			let Ok(account_id) = AccountId::decode(&mut aura.as_slice()) else { continue };
+			let keys = opaque::SessionKeys { aura, grandpa };
+			// as `pallet_session::set_keys` would, the keys hold a consumer reference
+			if !System::account_exists(&account_id) {
+				System::inc_providers(&account_id);
+			}
+			let _ = System::inc_consumers_without_limit(&account_id);
+			for id in opaque::SessionKeys::key_ids() {
+				KeyOwner::insert((*id, keys.get_raw(*id).to_vec()), &account_id);
+			}
+			NextKeys::insert(&account_id, &keys);
+			queued.push((account_id, keys));
+		}
+		let seeded = queued.len() as u64;
+		Validators::put(queued.iter().map(|(account_id, _)| account_id.clone()).collect::<Vec<_>>());
+		QueuedKeys::put(queued);
+		db_weight.reads_writes(3 + seeded, 2 + 4 * seeded)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let validators = Validators::get();
+		frame_support::ensure!(!validators.is_empty(), "no session validators");
+		frame_support::ensure!(
+			QueuedKeys::get().len() == validators.len(),
+			"queued keys do not match the validators"
+		);
+		for account_id in &validators {
+			frame_support::ensure!(NextKeys::contains_key(account_id), "validator has no session keys");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+# Regenerate the weights of the IBP pallets from benchmarks.
+# Run on reference hardware. Pass pallet names (e.g. `check alert`) to benchmark only those.
+# `--check` benchmarks nothing and fails while a pallet still ships hand-written estimates.
+set -e
+
+cd "$(dirname "$0")/.."
+
+PALLETS="member service check alert reward"
+
+if [ "$1" = "--check" ]; then
+   estimated=$(grep -l "HAND-WRITTEN ESTIMATES" pallets/ibp-*/src/weights.rs || true)
+   if [ -n "$estimated" ]; then
+      echo "Weights are hand-written estimates, regenerate them with $0:"
+      echo "$estimated"
+      exit 1
+   fi
+   exit 0
+fi
+
+if [ $# -gt 0 ]; then
+   PALLETS="$*"
+fi
+
+cargo build --release --features runtime-benchmarks
+
+for pallet in $PALLETS; do
+   echo "*** Benchmarking pallet_ibp_$pallet"
+   ./target/release/ibp-node benchmark pallet \
+      --chain dev \
+      --pallet "pallet_ibp_$pallet" \
+      --extrinsic '*' \
+      --steps 50 \
+      --repeat 20 \
+      --wasm-execution compiled \
+      --output "pallets/ibp-$pallet/src/weights.rs" \
+      --template ./.maintain/frame-weight-template.hbs
+done
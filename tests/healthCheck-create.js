@@ -30,7 +30,8 @@ import { getNonce, handleTransactionResponse } from './utils.js';
       'polkadot-rpc',
       'dotters.network',
       'RPC',
-      'Error'
+      'Error',
+      null
     );
     await tx.signAndSend(charlie, { nonce: charlie_nonce++, tip: 1000000000 }, (r) => handleTransactionResponse(api, r))
   }, 5000);
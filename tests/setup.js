@@ -59,9 +59,9 @@ import { getNonce, handleTransactionResponse } from './utils.js';
   await tx.signAndSend(bob, { nonce: bob_nonce++ }, (r) => handleTransactionResponse(api, r))
 
   // unlock ALICE and CHARLIE - must be done by a curator
-  tx = api.tx.ibpMember.unlockMember(alice.address);
+  tx = api.tx.ibpMember.unlockMember(alice.address, 'onboarded');
   await tx.signAndSend(bob, { nonce: bob_nonce++ }, (r) => handleTransactionResponse(api, r))
-  tx = api.tx.ibpMember.unlockMember(charlie.address);
+  tx = api.tx.ibpMember.unlockMember(charlie.address, 'onboarded');
   await tx.signAndSend(bob, { nonce: bob_nonce++ }, (r) => handleTransactionResponse(api, r))
 
   // set ALICE and CHARLIE as level - must be done by a monitor
@@ -89,9 +89,14 @@ import { getNonce, handleTransactionResponse } from './utils.js';
   // sleep 6 seconds to allow charlie to monitor the services
   await new Promise(resolve => setTimeout(resolve, 6000));
 
-  // set a service override for alice asset-hub-westend-rpc
-  tx = api.tx.ibpService.setServiceOverride('asset-hub-westend-rpc');
+  // alice asks for an override of asset-hub-westend-rpc for the next hour, bob approves it
+  const header = await api.rpc.chain.getHeader();
+  const expiresAt = header.number.toNumber() + 600;
+  tx = api.tx.ibpService.requestOverride('asset-hub-westend-rpc', 'node migration', expiresAt);
   await tx.signAndSend(alice, { nonce: alice_nonce++, tip: 1000000000 }, (r) => handleTransactionResponse(api, r));
+  await new Promise(resolve => setTimeout(resolve, 6000));
+  tx = api.tx.ibpService.approveOverride(alice.address, 'asset-hub-westend-rpc');
+  await tx.signAndSend(bob, { nonce: bob_nonce++, tip: 1000000000 }, (r) => handleTransactionResponse(api, r));
 
   // sleep 6 seconds to allow charlie to monitor the services
   await new Promise(resolve => setTimeout(resolve, 6000));
@@ -100,10 +105,10 @@ import { getNonce, handleTransactionResponse } from './utils.js';
   // Alice is the member
   // Charlie is the monitor
   let alerts = [
-    { is: 1, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'westend-rpc', alert_type: 'HTTPTest' },
-    { id: 2, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'kusama-rpc', alert_type: 'HTTPTest' },
-    { id: 3, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'polkadot-rpc', alert_type: 'HTTPTest' },
-    { id: 4, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'asset-hub-westend-rpc', alert_type: 'HTTPTest' },
+    { is: 1, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'westend-rpc', alert_type: 'BlackboxProbeHttpFailure' },
+    { id: 2, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'kusama-rpc', alert_type: 'BlackboxProbeHttpFailure' },
+    { id: 3, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'polkadot-rpc', alert_type: 'BlackboxProbeHttpFailure' },
+    { id: 4, member_id: alice.address, domain_id: 'testnet.ibp.network', service_id: 'asset-hub-westend-rpc', alert_type: 'BlackboxProbeHttpFailure' },
   ]
   for(let i = 0; i < alerts.length; i++) {
     const alert = alerts[i];
@@ -1,34 +1,21 @@
-import { ApiPromise, WsProvider, Keyring } from "@polkadot/api";
-import { customTypes } from "./types.js";  // Import custom types
+import { WsProvider } from "@polkadot/api";
 
 ;(async () => {
   const provider = new WsProvider('ws://localhost:9944');
-  const api = await ApiPromise.create({
-    provider,
-    types: customTypes
-   });
-  await api.isReady;
-
-  // MemberChilled(T::AccountId),
-  // MemberUnChilled(T::AccountId),
-
-  api.query.system.events((events) => {
-    console.log(`\nReceived ${events.length} events:`);
-
-    // Loop through the Vec<EventRecord>
-    events.forEach((record) => {
-      // Extract the phase, event and the event types
-      const { event, phase } = record;
-      const types = event.typeDef;
-
-      // response = { memberId, status }
-      if (event.section === 'ibpMember' && event.method === 'MemberStatusUpdated') {
-        console.log(event.data.toJSON());
-        const [ memberId, prev_status, new_status ] = event.data.toJSON();
-        console.log(`MemberStatusUpdated: ${memberId}, was: ${prev_status}, is now a ${new_status}`);
-      }
-
-    });
+  await provider.isReady;
+
+  // optional member address, watch all members when omitted
+  const member = process.argv[2] || null;
+
+  // the node decodes IbpMember events from finalized blocks and only pushes status changes
+  // notification = { blockHash, memberId, previousStatus, status, reason }
+  await provider.subscribe('ibp_memberStatus', 'ibp_subscribeMemberStatus', [member], (error, notification) => {
+    if (error) {
+      console.error(error);
+      return;
+    }
+    const { blockHash, memberId, previousStatus, status, reason } = notification;
+    console.log(`MemberStatusUpdated: ${memberId}, was: ${previousStatus}, is now ${status}, reason: ${reason} (block ${blockHash})`);
   });
 
   // process.exit(0);